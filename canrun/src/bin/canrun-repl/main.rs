@@ -0,0 +1,338 @@
+//! An interactive shell for running Prolog-like queries with canrun.
+//!
+//! ```text
+//! cargo run --features repl --bin canrun-repl -- rules.pl
+//! ```
+//!
+//! Type a query (e.g. `ancestor(X, joe).`) to see the first answer, then `;`
+//! to ask for the next one or an empty line to stop. Answers are pulled
+//! lazily from a [`Query`] iterator, so only as much of the search is run as
+//! you ask for. Type `:help` for the list of commands.
+
+mod parser;
+mod printer;
+mod program;
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, Write},
+    rc::Rc,
+    time::Instant,
+};
+
+use canrun::{goals::All, Query};
+
+use parser::{parse_program, parse_query};
+use program::{Bindings, Context, Program, Stats, Strategy};
+
+const HELP: &str = "\
+Enter a query such as `parent(X, bob).` to search for answers.
+After each answer, type `;` for the next one or press enter to stop.
+
+Commands:
+  :load <file>        load clauses from a rules file
+  :stats              show statistics for the program and last query
+  :trace [on|off]     toggle tracing of predicate calls
+  :strategy [name]    show or change the search strategy:
+                        dfs          expand every call (default)
+                        bounded <n>  fail calls nested deeper than n
+  :help               show this message
+  :quit               exit";
+
+struct Repl {
+    program: Rc<Program>,
+    strategy: Strategy,
+    trace: bool,
+    last: Option<Rc<Context>>,
+}
+
+impl Repl {
+    fn new() -> Self {
+        Repl {
+            program: Rc::new(Program::default()),
+            strategy: Strategy::DepthFirst,
+            trace: false,
+            last: None,
+        }
+    }
+
+    /// Read lines until the input runs out or the user quits.
+    fn run(&mut self, input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+        loop {
+            write!(out, "?- ")?;
+            out.flush()?;
+            let Some(line) = read_line(input)? else {
+                return Ok(());
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(command) = line.strip_prefix(':') {
+                if !self.command(command, out)? {
+                    return Ok(());
+                }
+            } else {
+                self.query(line, input, out)?;
+            }
+        }
+    }
+
+    /// Returns `false` if the REPL should exit.
+    fn command(&mut self, command: &str, out: &mut impl Write) -> io::Result<bool> {
+        let mut words = command.split_whitespace();
+        match (words.next(), words.collect::<Vec<_>>().as_slice()) {
+            (Some("q" | "quit"), []) => return Ok(false),
+            (Some("h" | "help"), []) => writeln!(out, "{HELP}")?,
+            (Some("l" | "load"), [path]) => self.load(path, out)?,
+            (Some("stats"), []) => {
+                writeln!(
+                    out,
+                    "program: {} predicates, {} clauses",
+                    self.program.predicate_count(),
+                    self.program.clause_count()
+                )?;
+                match &self.last {
+                    Some(context) => writeln!(out, "last query: {}", context.stats)?,
+                    None => writeln!(out, "No queries have been run yet.")?,
+                }
+            }
+            (Some("trace"), args) => {
+                self.trace = match args {
+                    [] => !self.trace,
+                    ["on"] => true,
+                    ["off"] => false,
+                    _ => return usage(out, ":trace [on|off]"),
+                };
+                let status = if self.trace { "on" } else { "off" };
+                writeln!(out, "Tracing is {status}.")?;
+            }
+            (Some("strategy"), args) => {
+                self.strategy = match args {
+                    [] => self.strategy,
+                    ["dfs"] => Strategy::DepthFirst,
+                    ["bounded", limit] => match limit.parse() {
+                        Ok(limit) => Strategy::Bounded(limit),
+                        Err(_) => return usage(out, ":strategy bounded <n>"),
+                    },
+                    _ => return usage(out, ":strategy [dfs|bounded <n>]"),
+                };
+                writeln!(out, "Search strategy: {}", self.strategy)?;
+            }
+            _ => writeln!(out, "Unknown command `:{command}`. Type :help for help.")?,
+        }
+        Ok(true)
+    }
+
+    fn load(&mut self, path: &str, out: &mut impl Write) -> io::Result<()> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) => return writeln!(out, "Could not read {path}: {error}"),
+        };
+        match parse_program(&source) {
+            Ok(clauses) => {
+                let count = clauses.len();
+                let program = Rc::make_mut(&mut self.program);
+                for clause in clauses {
+                    program.add(clause);
+                }
+                writeln!(out, "Loaded {count} clauses from {path}.")
+            }
+            Err(error) => writeln!(out, "Error in {path}, {error}"),
+        }
+    }
+
+    fn query(
+        &mut self,
+        line: &str,
+        input: &mut impl BufRead,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let literals = match parse_query(line) {
+            Ok(literals) => literals,
+            Err(error) => return writeln!(out, "Syntax error, {error}"),
+        };
+        for (name, arity) in literals.iter().filter_map(parser::Ast::functor) {
+            if !self.program.is_defined(name, arity) {
+                writeln!(out, "Warning: unknown predicate {name}/{arity}")?;
+            }
+        }
+
+        let context = Rc::new(Context {
+            program: self.program.clone(),
+            strategy: self.strategy,
+            stats: Stats::default(),
+            trace: self.trace.then(|| RefCell::new(Vec::new())),
+        });
+        self.last = Some(context.clone());
+
+        let mut env = HashMap::new();
+        let goal: All = literals
+            .iter()
+            .map(|literal| context.goal(literal, &mut env, 0))
+            .collect();
+        let mut names = Vec::new();
+        for literal in &literals {
+            literal.collect_vars(&mut names);
+        }
+        let bindings = Bindings(Rc::new(
+            names
+                .into_iter()
+                .map(|name| {
+                    let value = env[&name].clone();
+                    (name, value)
+                })
+                .collect(),
+        ));
+        let mut answers = goal.query(bindings);
+
+        loop {
+            let started = Instant::now();
+            let answer = answers.next();
+            let stats = &context.stats;
+            stats.elapsed.set(stats.elapsed.get() + started.elapsed());
+            if let Some(trace) = &context.trace {
+                for line in trace.borrow_mut().drain(..) {
+                    writeln!(out, "{line}")?;
+                }
+            }
+            let Some(answer) = answer else {
+                return writeln!(out, "false.");
+            };
+            stats.answers.set(stats.answers.get() + 1);
+            if answer.is_empty() {
+                write!(out, "true")?;
+            } else {
+                let answer: Vec<_> = answer
+                    .into_iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect();
+                write!(out, "{}", answer.join(",\n"))?;
+            }
+            write!(out, " ")?;
+            out.flush()?;
+            match read_line(input)? {
+                Some(response) if response.trim() == ";" => writeln!(out)?,
+                _ => return writeln!(out, "."),
+            }
+        }
+    }
+}
+
+fn usage(out: &mut impl Write, usage: &str) -> io::Result<bool> {
+    writeln!(out, "Usage: {usage}")?;
+    Ok(true)
+}
+
+fn read_line(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(line))
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut repl = Repl::new();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for path in std::env::args().skip(1) {
+        repl.load(&path, &mut out)?;
+    }
+    repl.run(&mut io::stdin().lock(), &mut out)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::Repl;
+
+    static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+    fn session(rules: &str, input: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "canrun-repl-{}-{}.pl",
+            std::process::id(),
+            SESSIONS.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(rules.as_bytes())
+            .unwrap();
+
+        let mut repl = Repl::new();
+        let mut out = Vec::new();
+        repl.load(path.to_str().unwrap(), &mut Vec::new()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        repl.run(&mut input.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    const RULES: &str = "
+        color(red).
+        color(green).
+        color(blue).
+        nat(z).
+        nat(s(N)) :- nat(N).
+    ";
+
+    #[test]
+    fn steps_through_answers() {
+        let out = session(RULES, "color(X).\n;\n;\n;\n");
+        assert_eq!(out, "?- X = red \nX = green \nX = blue \nfalse.\n?- ");
+    }
+
+    #[test]
+    fn stops_when_asked() {
+        let out = session(RULES, "color(X).\n\n:stats\n");
+        assert!(out.starts_with(
+            "?- X = red .\n?- program: 2 predicates, 5 clauses\nlast query: answers: 1, calls: 1,"
+        ));
+    }
+
+    #[test]
+    fn streams_infinite_answers_lazily() {
+        let out = session(RULES, "nat(X).\n;\n;\n\n");
+        assert_eq!(out, "?- X = z \nX = s(z) \nX = s(s(z)) .\n?- ");
+    }
+
+    #[test]
+    fn reports_true_and_false() {
+        let out = session(RULES, "color(red).\n\ncolor(pink).\n");
+        assert_eq!(out, "?- true .\n?- false.\n?- ");
+    }
+
+    #[test]
+    fn traces_calls() {
+        let out = session(RULES, ":trace\ncolor(red).\n");
+        assert!(out.contains("Tracing is on."));
+        assert!(out.contains("call: color(red)"));
+        assert!(out.contains("match: color(red)"));
+    }
+
+    #[test]
+    fn changes_strategy() {
+        let out = session(RULES, ":strategy bounded 2\nnat(X).\n;\n;\n");
+        assert_eq!(
+            out,
+            "?- Search strategy: bounded 2\n?- X = z \nX = s(z) \nfalse.\n?- "
+        );
+    }
+
+    #[test]
+    fn warns_about_unknown_predicates() {
+        let out = session(RULES, "colour(X).\n:nope\n:quit\ncolor(X).\n");
+        assert_eq!(
+            out,
+            "?- Warning: unknown predicate colour/1\nfalse.\n?- Unknown command `:nope`. Type :help for help.\n?- "
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! A small Prolog-flavored reader for rules files and queries.
+//!
+//! The accepted syntax is intentionally minimal:
+//! ```text
+//! % comments run to the end of the line
+//! parent(tom, bob).
+//! ancestor(X, Y) :- parent(X, Y).
+//! ancestor(X, Y) :- parent(X, Z), ancestor(Z, Y).
+//! likes(X, [1, 2 | Rest]) :- X = "a string", X \= other.
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use canrun::{term::Term, Value};
+
+/// A parsed term that still refers to variables by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ast {
+    Var(String),
+    Atom(String),
+    Int(i64),
+    Str(String),
+    Compound(String, Vec<Ast>),
+}
+
+impl Ast {
+    pub fn nil() -> Ast {
+        Ast::Atom("[]".to_string())
+    }
+
+    pub fn cons(head: Ast, tail: Ast) -> Ast {
+        Ast::Compound(".".to_string(), vec![head, tail])
+    }
+
+    /// The name and arity used to look up matching clauses.
+    pub fn functor(&self) -> Option<(&str, usize)> {
+        match self {
+            Ast::Atom(name) => Some((name, 0)),
+            Ast::Compound(name, args) => Some((name, args.len())),
+            _ => None,
+        }
+    }
+
+    /// Build a [`Value<Term>`], creating a fresh [`LVar`](canrun::LVar) the
+    /// first time each named variable is seen. `_` is always fresh. Proper
+    /// lists become a [`Term::List`], and lists with a tail become a chain of
+    /// [`Term::Cons`] cells.
+    pub fn instantiate(&self, env: &mut HashMap<String, Value<Term>>) -> Value<Term> {
+        match self {
+            Ast::Var(name) if name == "_" => Value::var(),
+            Ast::Var(name) => env.entry(name.clone()).or_insert_with(Value::var).clone(),
+            Ast::Atom(name) if name == "[]" => Value::new(Term::List(Vec::new())),
+            Ast::Atom(name) => Value::new(Term::Atom(name.clone())),
+            Ast::Int(n) => Value::new(Term::Int(*n)),
+            Ast::Str(s) => Value::new(Term::Str(s.clone())),
+            Ast::Compound(name, args) if name == "." && args.len() == 2 => {
+                let mut items = Vec::new();
+                let mut tail = self;
+                while let Ast::Compound(name, args) = tail {
+                    if name != "." || args.len() != 2 {
+                        break;
+                    }
+                    items.push(args[0].instantiate(env));
+                    tail = &args[1];
+                }
+                if tail == &Ast::nil() {
+                    Value::new(Term::List(items))
+                } else {
+                    let tail = tail.instantiate(env);
+                    items
+                        .into_iter()
+                        .rev()
+                        .fold(tail, |tail, head| Value::new(Term::Cons(head, tail)))
+                }
+            }
+            Ast::Compound(name, args) => Value::new(Term::Compound(
+                name.clone(),
+                args.iter().map(|arg| arg.instantiate(env)).collect(),
+            )),
+        }
+    }
+
+    /// Named variables in order of first appearance, skipping any that start
+    /// with an underscore.
+    pub fn collect_vars(&self, vars: &mut Vec<String>) {
+        match self {
+            Ast::Var(name) if !name.starts_with('_') && !vars.contains(name) => {
+                vars.push(name.clone());
+            }
+            Ast::Compound(_, args) => args.iter().for_each(|arg| arg.collect_vars(vars)),
+            _ => {}
+        }
+    }
+}
+
+/// A fact (with an empty body) or a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    pub head: Ast,
+    pub body: Vec<Ast>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Atom(String),
+    Var(String),
+    Int(i64),
+    Str(String),
+    Punct(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Atom(s) | Token::Var(s) => write!(f, "`{s}`"),
+            Token::Int(n) => write!(f, "`{n}`"),
+            Token::Str(s) => write!(f, "{s:?}"),
+            Token::Punct(p) => write!(f, "`{p}`"),
+        }
+    }
+}
+
+const PUNCTUATION: [&str; 11] = [":-", "?-", "\\=", "(", ")", "[", "]", ",", "|", ".", "="];
+
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let error = move |message: String| ParseError { line, message };
+        if c == '\n' {
+            line += 1;
+            chars.next();
+        } else if c.is_whitespace() {
+            chars.next();
+        } else if c == '%' {
+            while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut ident = String::new();
+            while let Some((_, c)) = chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_') {
+                ident.push(c);
+            }
+            if c.is_ascii_lowercase() {
+                tokens.push((Token::Atom(ident), line));
+            } else {
+                tokens.push((Token::Var(ident), line));
+            }
+        } else if c.is_ascii_digit() || (c == '-' && starts_number(&src[start + 1..])) {
+            chars.next();
+            let mut digits = c.to_string();
+            while let Some((_, c)) = chars.next_if(|&(_, c)| c.is_ascii_digit()) {
+                digits.push(c);
+            }
+            let n = digits
+                .parse()
+                .map_err(|_| error(format!("integer out of range: {digits}")))?;
+            tokens.push((Token::Int(n), line));
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    None => return Err(error("unterminated quote".to_string())),
+                    Some((_, q)) if q == c => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, other)) => text.push(other),
+                        None => return Err(error("unterminated quote".to_string())),
+                    },
+                    Some((_, '\n')) => {
+                        line += 1;
+                        text.push('\n');
+                    }
+                    Some((_, other)) => text.push(other),
+                }
+            }
+            if c == '"' {
+                tokens.push((Token::Str(text), line));
+            } else {
+                tokens.push((Token::Atom(text), line));
+            }
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| src[start..].starts_with(*p)) {
+            for _ in 0..punct.len() {
+                chars.next();
+            }
+            tokens.push((Token::Punct(punct), line));
+        } else {
+            return Err(error(format!("unexpected character `{c}`")));
+        }
+    }
+    Ok(tokens)
+}
+
+fn starts_number(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_ascii_digit())
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(src: &str) -> Result<Self, ParseError> {
+        Ok(Parser {
+            tokens: tokenize(src)?,
+            pos: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            line: self.line(),
+            message: message.into(),
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), ParseError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            match self.peek() {
+                Some(found) => self.error(format!("expected `{punct}`, found {found}")),
+                None => self.error(format!("expected `{punct}`, found end of input")),
+            }
+        }
+    }
+
+    fn clause(&mut self) -> Result<Clause, ParseError> {
+        let head = self.term()?;
+        if head.functor().is_none() {
+            return self.error("a clause head must be an atom or compound term");
+        }
+        let body = if self.eat(":-") {
+            self.body()?
+        } else {
+            Vec::new()
+        };
+        self.expect(".")?;
+        Ok(Clause { head, body })
+    }
+
+    fn body(&mut self) -> Result<Vec<Ast>, ParseError> {
+        let mut goals = vec![self.goal()?];
+        while self.eat(",") {
+            goals.push(self.goal()?);
+        }
+        Ok(goals)
+    }
+
+    fn goal(&mut self) -> Result<Ast, ParseError> {
+        let left = self.term()?;
+        for op in ["=", "\\="] {
+            if self.eat(op) {
+                let right = self.term()?;
+                return Ok(Ast::Compound(op.to_string(), vec![left, right]));
+            }
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Ast, ParseError> {
+        let Some((token, _)) = self.tokens.get(self.pos).cloned() else {
+            return self.error("expected a term, found end of input");
+        };
+        self.pos += 1;
+        match token {
+            Token::Var(name) => Ok(Ast::Var(name)),
+            Token::Int(n) => Ok(Ast::Int(n)),
+            Token::Str(s) => Ok(Ast::Str(s)),
+            Token::Atom(name) => {
+                if self.eat("(") {
+                    let args = self.args()?;
+                    self.expect(")")?;
+                    Ok(Ast::Compound(name, args))
+                } else {
+                    Ok(Ast::Atom(name))
+                }
+            }
+            Token::Punct("[") => self.list(),
+            Token::Punct(punct) => {
+                self.pos -= 1;
+                self.error(format!("expected a term, found `{punct}`"))
+            }
+        }
+    }
+
+    fn args(&mut self) -> Result<Vec<Ast>, ParseError> {
+        let mut args = vec![self.term()?];
+        while self.eat(",") {
+            args.push(self.term()?);
+        }
+        Ok(args)
+    }
+
+    fn list(&mut self) -> Result<Ast, ParseError> {
+        if self.eat("]") {
+            return Ok(Ast::nil());
+        }
+        let items = self.args()?;
+        let tail = if self.eat("|") {
+            self.term()?
+        } else {
+            Ast::nil()
+        };
+        self.expect("]")?;
+        Ok(items
+            .into_iter()
+            .rev()
+            .fold(tail, |tail, head| Ast::cons(head, tail)))
+    }
+}
+
+/// Parse the contents of a rules file.
+pub fn parse_program(src: &str) -> Result<Vec<Clause>, ParseError> {
+    let mut parser = Parser::new(src)?;
+    let mut clauses = Vec::new();
+    while !parser.is_done() {
+        clauses.push(parser.clause()?);
+    }
+    Ok(clauses)
+}
+
+/// Parse a query: a comma separated list of goals with an optional leading
+/// `?-` and trailing `.`.
+pub fn parse_query(src: &str) -> Result<Vec<Ast>, ParseError> {
+    let mut parser = Parser::new(src)?;
+    parser.eat("?-");
+    let goals = parser.body()?;
+    parser.eat(".");
+    if parser.is_done() {
+        Ok(goals)
+    } else {
+        parser.error("unexpected input after the end of the query")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atom(s: &str) -> Ast {
+        Ast::Atom(s.to_string())
+    }
+
+    fn var(s: &str) -> Ast {
+        Ast::Var(s.to_string())
+    }
+
+    fn compound(name: &str, args: Vec<Ast>) -> Ast {
+        Ast::Compound(name.to_string(), args)
+    }
+
+    #[test]
+    fn parses_facts_and_rules() {
+        let clauses = parse_program(
+            "% a comment
+            parent(tom, bob).
+            ancestor(X, Y) :- parent(X, Z), ancestor(Z, Y).",
+        )
+        .unwrap();
+        assert_eq!(
+            clauses,
+            vec![
+                Clause {
+                    head: compound("parent", vec![atom("tom"), atom("bob")]),
+                    body: vec![],
+                },
+                Clause {
+                    head: compound("ancestor", vec![var("X"), var("Y")]),
+                    body: vec![
+                        compound("parent", vec![var("X"), var("Z")]),
+                        compound("ancestor", vec![var("Z"), var("Y")]),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_lists_with_tails() {
+        let query = parse_query("?- X = [1, -2 | T].").unwrap();
+        let list = Ast::cons(Ast::Int(1), Ast::cons(Ast::Int(-2), var("T")));
+        assert_eq!(query, vec![compound("=", vec![var("X"), list])]);
+    }
+
+    #[test]
+    fn parses_quoted_atoms_and_strings() {
+        let query = parse_query(r#"x('Hello world', "a \"string\"")"#).unwrap();
+        assert_eq!(
+            query,
+            vec![compound(
+                "x",
+                vec![atom("Hello world"), Ast::Str("a \"string\"".to_string())]
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let error = parse_program("a.\nb(.\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.to_string(), "line 2: expected a term, found `.`");
+    }
+
+    #[test]
+    fn rejects_variable_heads() {
+        assert!(parse_program("X :- a.").is_err());
+    }
+
+    #[test]
+    fn collects_named_vars_in_order() {
+        let query = parse_query("p(Y, _, _Z, X, Y)").unwrap();
+        let mut vars = Vec::new();
+        for goal in &query {
+            goal.collect_vars(&mut vars);
+        }
+        assert_eq!(vars, vec!["Y".to_string(), "X".to_string()]);
+    }
+}
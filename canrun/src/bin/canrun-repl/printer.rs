@@ -0,0 +1,158 @@
+//! Prints terms using the variable bindings from a state.
+
+use std::{collections::HashMap, fmt::Write};
+
+use canrun::{term::Term, LVar, Value};
+
+/// Writes terms in Prolog syntax, giving unbound variables stable `_G<n>`
+/// names for as long as the printer is kept around.
+pub struct Printer<'a> {
+    resolve: &'a dyn Fn(&Value<Term>) -> Value<Term>,
+    names: HashMap<LVar<Term>, usize>,
+}
+
+impl<'a> Printer<'a> {
+    /// Create a printer that resolves variables with the passed in function,
+    /// typically [`State::resolve`] or
+    /// [`ReadyState::resolve`](canrun::ReadyState::resolve).
+    pub fn new(resolve: &'a dyn Fn(&Value<Term>) -> Value<Term>) -> Self {
+        Printer {
+            resolve,
+            names: HashMap::new(),
+        }
+    }
+
+    pub fn print(&mut self, value: &Value<Term>) -> String {
+        let mut out = String::new();
+        self.write(value, &mut out);
+        out
+    }
+
+    fn write(&mut self, value: &Value<Term>, out: &mut String) {
+        match (self.resolve)(value) {
+            Value::Var(var) => {
+                let next = self.names.len();
+                let n = *self.names.entry(var).or_insert(next);
+                let _ = write!(out, "_G{n}");
+            }
+            Value::Resolved(term) => match term.as_ref() {
+                Term::Atom(name) => out.push_str(name),
+                Term::Int(n) => out.push_str(&n.to_string()),
+                Term::Float(n) => {
+                    let _ = write!(out, "{n:?}");
+                }
+                Term::Str(s) => {
+                    let _ = write!(out, "{s:?}");
+                }
+                Term::Compound(name, args) if (name == "=" || name == "\\=") && args.len() == 2 => {
+                    self.write(&args[0], out);
+                    let _ = write!(out, " {name} ");
+                    self.write(&args[1], out);
+                }
+                Term::Compound(name, args) => {
+                    out.push_str(name);
+                    out.push('(');
+                    self.write_all(args, out);
+                    out.push(')');
+                }
+                Term::List(items) => {
+                    out.push('[');
+                    self.write_all(items, out);
+                    out.push(']');
+                }
+                Term::Cons(head, tail) => self.write_list(head, tail, out),
+            },
+        }
+    }
+
+    fn write_all(&mut self, values: &[Value<Term>], out: &mut String) {
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                out.push_str(", ");
+            }
+            self.write(value, out);
+        }
+    }
+
+    fn write_list(&mut self, head: &Value<Term>, tail: &Value<Term>, out: &mut String) {
+        out.push('[');
+        self.write(head, out);
+        let mut tail = tail.clone();
+        loop {
+            match (self.resolve)(&tail) {
+                Value::Resolved(term) => match term.as_ref() {
+                    Term::List(items) => {
+                        for item in items {
+                            out.push_str(", ");
+                            self.write(item, out);
+                        }
+                        break;
+                    }
+                    Term::Cons(head, next) => {
+                        out.push_str(", ");
+                        self.write(head, out);
+                        tail = next.clone();
+                    }
+                    _ => {
+                        out.push_str(" | ");
+                        self.write(&tail, out);
+                        break;
+                    }
+                },
+                Value::Var(_) => {
+                    out.push_str(" | ");
+                    self.write(&tail, out);
+                    break;
+                }
+            }
+        }
+        out.push(']');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use canrun::{term::Term, State, Value};
+
+    use super::Printer;
+    use crate::parser::parse_query;
+
+    fn value(src: &str) -> Value<Term> {
+        parse_query(src).unwrap()[0].instantiate(&mut HashMap::new())
+    }
+
+    #[test]
+    fn prints_resolved_bindings() {
+        let mut env = HashMap::new();
+        let goal = &parse_query("f(X, [1, Y]) = f(a, [Z, \"s\"])").unwrap()[0];
+        let goal = goal.instantiate(&mut env);
+        let Term::Compound(_, args) = goal.resolved().unwrap() else {
+            panic!()
+        };
+        let state = State::new().unify(&args[0], &args[1]).unwrap();
+        let resolve = |v: &Value<Term>| state.resolve(v);
+        let mut printer = Printer::new(&resolve);
+        assert_eq!(printer.print(&env["X"]), "a");
+        assert_eq!(printer.print(&env["Y"]), "\"s\"");
+        assert_eq!(printer.print(&env["Z"]), "1");
+    }
+
+    #[test]
+    fn fails_on_mismatched_arity() {
+        let state = State::new().unify(&value("f(a)"), &value("f(a, b)"));
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn prints_lists_and_fresh_vars() {
+        let resolve = |v: &Value<Term>| v.clone();
+        let mut printer = Printer::new(&resolve);
+        assert_eq!(
+            printer.print(&value("[a, X, X | T]")),
+            "[a, _G0, _G0 | _G1]"
+        );
+        assert_eq!(printer.print(&value("p([], 'x y', -3)")), "p([], x y, -3)");
+    }
+}
@@ -0,0 +1,312 @@
+//! Clause storage and the goals used to resolve queries against it.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    iter::empty,
+    rc::Rc,
+    time::Duration,
+};
+
+use canrun::{
+    goals::{not, unify, Fail, Goal, Succeed},
+    term::Term,
+    Fork, ReadyState, Reify, State, StateIter, Value,
+};
+
+use crate::{
+    parser::{Ast, Clause},
+    printer::Printer,
+};
+
+/// All of the loaded clauses, indexed by predicate name and arity.
+#[derive(Clone, Default)]
+pub struct Program {
+    predicates: HashMap<(String, usize), Vec<Rc<Clause>>>,
+}
+
+impl Program {
+    pub fn add(&mut self, clause: Clause) {
+        let (name, arity) = clause.head.functor().expect("clause heads have a functor");
+        self.predicates
+            .entry((name.to_string(), arity))
+            .or_default()
+            .push(Rc::new(clause));
+    }
+
+    pub fn clauses(&self, name: &str, arity: usize) -> Vec<Rc<Clause>> {
+        self.predicates
+            .get(&(name.to_string(), arity))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn is_defined(&self, name: &str, arity: usize) -> bool {
+        is_builtin(name, arity) || self.predicates.contains_key(&(name.to_string(), arity))
+    }
+
+    pub fn predicate_count(&self) -> usize {
+        self.predicates.len()
+    }
+
+    pub fn clause_count(&self) -> usize {
+        self.predicates.values().map(Vec::len).sum()
+    }
+}
+
+fn is_builtin(name: &str, arity: usize) -> bool {
+    matches!((name, arity), ("true" | "fail", 0) | ("=" | "\\=", 2))
+}
+
+/// How predicate calls are expanded while searching for answers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// Expand every call, however deep the proof gets.
+    DepthFirst,
+    /// Fail any call nested more than this many levels deep. Useful for
+    /// exploring left recursive or otherwise infinite programs.
+    Bounded(usize),
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::DepthFirst => write!(f, "dfs"),
+            Strategy::Bounded(limit) => write!(f, "bounded {limit}"),
+        }
+    }
+}
+
+/// Counters collected while a query runs.
+#[derive(Default)]
+pub struct Stats {
+    pub calls: Cell<usize>,
+    pub clauses_tried: Cell<usize>,
+    pub cutoffs: Cell<usize>,
+    pub answers: Cell<usize>,
+    pub elapsed: Cell<Duration>,
+}
+
+impl Stats {
+    fn bump(counter: &Cell<usize>) {
+        counter.set(counter.get() + 1);
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "answers: {}, calls: {}, clauses tried: {}, depth cutoffs: {}, time: {:?}",
+            self.answers.get(),
+            self.calls.get(),
+            self.clauses_tried.get(),
+            self.cutoffs.get(),
+            self.elapsed.get(),
+        )
+    }
+}
+
+/// Shared by every goal created for a single query.
+pub struct Context {
+    pub program: Rc<Program>,
+    pub strategy: Strategy,
+    pub stats: Stats,
+    /// Collects trace lines when tracing is enabled.
+    pub trace: Option<RefCell<Vec<String>>>,
+}
+
+impl Context {
+    /// Build the goal for one literal in a clause body or query.
+    pub fn goal(
+        self: &Rc<Self>,
+        literal: &Ast,
+        env: &mut HashMap<String, Value<Term>>,
+        depth: usize,
+    ) -> Box<dyn Goal> {
+        match literal {
+            Ast::Atom(name) if name == "true" => Box::new(Succeed),
+            Ast::Atom(name) if name == "fail" => Box::new(Fail),
+            Ast::Compound(name, args) if name == "=" && args.len() == 2 => {
+                Box::new(unify(args[0].instantiate(env), args[1].instantiate(env)))
+            }
+            Ast::Compound(name, args) if name == "\\=" && args.len() == 2 => Box::new(not(unify(
+                args[0].instantiate(env),
+                args[1].instantiate(env),
+            ))),
+            _ => Box::new(Call {
+                term: literal.instantiate(env),
+                depth,
+                context: self.clone(),
+            }),
+        }
+    }
+
+    fn trace(&self, depth: usize, state: &State, port: &str, term: &Value<Term>) {
+        if let Some(lines) = &self.trace {
+            let resolve = |v: &Value<Term>| state.resolve(v);
+            let term = Printer::new(&resolve).print(term);
+            let indent = "  ".repeat(depth);
+            lines.borrow_mut().push(format!("{indent}{port}: {term}"));
+        }
+    }
+}
+
+/// A call to a user defined predicate. Matching clauses are tried lazily (in
+/// order) when the fork is expanded.
+#[derive(Clone)]
+pub struct Call {
+    term: Value<Term>,
+    depth: usize,
+    context: Rc<Context>,
+}
+
+impl fmt::Debug for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Call {:?}", self.term)
+    }
+}
+
+impl Goal for Call {
+    fn apply(&self, state: State) -> Option<State> {
+        state.fork(self.clone())
+    }
+}
+
+impl Fork for Call {
+    fn fork(&self, state: &State) -> StateIter {
+        let context = self.context.clone();
+        let depth = self.depth;
+        Stats::bump(&context.stats.calls);
+        if let Strategy::Bounded(limit) = context.strategy {
+            if depth >= limit {
+                Stats::bump(&context.stats.cutoffs);
+                context.trace(depth, state, "cutoff", &self.term);
+                return Box::new(empty());
+            }
+        }
+        context.trace(depth, state, "call", &self.term);
+        let clauses = match state.resolve(&self.term) {
+            Value::Resolved(term) => match term.as_ref() {
+                Term::Atom(name) => context.program.clauses(name, 0),
+                Term::Compound(name, args) => context.program.clauses(name, args.len()),
+                _ => Vec::new(),
+            },
+            Value::Var(_) => Vec::new(),
+        };
+        let term = self.term.clone();
+        let state = state.clone();
+        Box::new(clauses.into_iter().filter_map(move |clause| {
+            Stats::bump(&context.stats.clauses_tried);
+            let mut env = HashMap::new();
+            let head = clause.head.instantiate(&mut env);
+            let state = state.clone().unify(&head, &term)?;
+            context.trace(depth, &state, "match", &term);
+            clause.body.iter().try_fold(state, |state, literal| {
+                context.goal(literal, &mut env, depth + 1).apply(state)
+            })
+        }))
+    }
+}
+
+/// The named variables of a query, reified into printable bindings.
+#[derive(Clone)]
+pub struct Bindings(pub Rc<Vec<(String, Value<Term>)>>);
+
+impl Reify for Bindings {
+    type Reified = Vec<(String, String)>;
+
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        let resolve = |v: &Value<Term>| state.resolve(v);
+        let mut printer = Printer::new(&resolve);
+        Some(
+            self.0
+                .iter()
+                .map(|(name, value)| (name.clone(), printer.print(value)))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, rc::Rc};
+
+    use canrun::{
+        goals::{All, Goal},
+        Query,
+    };
+
+    use super::{Bindings, Context, Program, Stats, Strategy};
+    use crate::parser::{parse_program, parse_query};
+
+    const FAMILY: &str = "
+        parent(tom, bob).
+        parent(bob, ann).
+        parent(ann, joe).
+        ancestor(X, Y) :- parent(X, Y).
+        ancestor(X, Y) :- parent(X, Z), ancestor(Z, Y).
+        loop(X) :- loop(X).
+    ";
+
+    fn solve(query: &str, strategy: Strategy) -> (Vec<String>, Rc<Context>) {
+        let mut program = Program::default();
+        parse_program(FAMILY)
+            .unwrap()
+            .into_iter()
+            .for_each(|clause| program.add(clause));
+        let context = Rc::new(Context {
+            program: Rc::new(program),
+            strategy,
+            stats: Stats::default(),
+            trace: None,
+        });
+        let literals = parse_query(query).unwrap();
+        let mut env = HashMap::new();
+        let goals: Vec<Box<dyn Goal>> = literals
+            .iter()
+            .map(|literal| context.goal(literal, &mut env, 0))
+            .collect();
+        let bindings = Bindings(Rc::new(env.into_iter().filter(|(k, _)| k == "X").collect()));
+        let answers = All::from(goals)
+            .query(bindings)
+            .map(|answer| answer.into_iter().map(|(_, v)| v).collect::<String>())
+            .collect();
+        (answers, context)
+    }
+
+    #[test]
+    fn resolves_recursive_rules() {
+        let (answers, context) = solve("ancestor(X, joe)", Strategy::DepthFirst);
+        assert_eq!(answers, vec!["ann", "tom", "bob"]);
+        assert!(context.stats.calls.get() > 3);
+    }
+
+    #[test]
+    fn handles_builtins() {
+        let (answers, _) = solve("parent(X, Y), X \\= tom, Y = ann", Strategy::DepthFirst);
+        assert_eq!(answers, vec!["bob"]);
+    }
+
+    #[test]
+    fn bounded_strategy_cuts_off_infinite_recursion() {
+        let (answers, context) = solve("loop(X)", Strategy::Bounded(5));
+        assert!(answers.is_empty());
+        assert_eq!(context.stats.cutoffs.get(), 1);
+    }
+
+    #[test]
+    fn program_counts() {
+        let mut program = Program::default();
+        parse_program(FAMILY)
+            .unwrap()
+            .into_iter()
+            .for_each(|clause| program.add(clause));
+        assert_eq!(program.predicate_count(), 3);
+        assert_eq!(program.clause_count(), 6);
+        assert!(program.is_defined("ancestor", 2));
+        assert!(program.is_defined("=", 2));
+        assert!(!program.is_defined("ancestor", 1));
+    }
+}
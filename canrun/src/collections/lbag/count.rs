@@ -0,0 +1,107 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::LBag;
+use crate::{
+    constraints::{Constraint, ResolveFn},
+    goals::Goal,
+    LVarList, State, Unify, Value,
+};
+
+/** Relate an element of an [`LBag`] to the number of times it appears.
+Create with [`count`]. */
+#[derive(Debug)]
+pub struct Count<T: Unify + Eq + Hash + Debug> {
+    bag: Value<LBag<T>>,
+    item: Value<T>,
+    times: Value<usize>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> Clone for Count<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bag: self.bag.clone(),
+            item: self.item.clone(),
+            times: self.times.clone(),
+        }
+    }
+}
+
+/** Assert that `item` appears `count` times in [`LBag`] `bag`.
+
+A count of zero means the item is absent. Since a variable element could
+still turn out to be `item`, this waits until the item and every element of
+the bag are resolved.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lbag::{lbag, count};
+
+let n = LVar::new();
+let goal = count(lbag! {1, 2, 1}, 1, n);
+let results: Vec<_> = goal.query(n).collect();
+assert_eq!(results, vec![2]);
+```
+*/
+pub fn count<T>(
+    bag: impl Into<Value<LBag<T>>>,
+    item: impl Into<Value<T>>,
+    count: impl Into<Value<usize>>,
+) -> Count<T>
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    Count {
+        bag: bag.into(),
+        item: item.into(),
+        times: count.into(),
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Goal for Count<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Constraint for Count<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let bag = match state.resolve(&self.bag) {
+            Value::Resolved(bag) => bag,
+            Value::Var(var) => return Err(LVarList::one(&var)),
+        };
+        let item = state.resolve(&self.item);
+        let items = bag.expand(state);
+        let unresolved = items.iter().chain([&item]).filter_map(|value| match value {
+            Value::Var(var) => Some(LVarList::one(var)),
+            Value::Resolved(_) => None,
+        });
+        let unresolved = LVarList::flatten(unresolved);
+        if !unresolved.is_empty() {
+            return Err(unresolved);
+        }
+        let found = Value::new(items.iter().filter(|other| **other == item).count());
+        let times = self.times.clone();
+        Ok(Box::new(move |state| state.unify(&times, &found)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lbag::{count, lbag};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn counts_after_binding() {
+        let (x, n) = (LVar::new(), LVar::new());
+        let goals = goal_vec![count(lbag! {x, 2, 2}, 2, n), unify(x, 2)];
+        goals.assert_permutations_resolve_to(&n, vec![3]);
+    }
+
+    #[test]
+    fn zero_when_absent() {
+        let n = LVar::new();
+        let goals = goal_vec![count(lbag! {1}, 2, n)];
+        goals.assert_permutations_resolve_to(&n, vec![0]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::LBag;
+use crate::{
+    constraints::{Constraint, OneOfTwo, ResolveFn},
+    goals::{custom, Any, Goal},
+    LVarList, State, Unify, Value,
+};
+
+/** Relate an [`LBag`] to the same bag with one more of `item`. Create with
+[`insert`] or [`remove`]. */
+#[derive(Debug)]
+pub struct Insert<T: Unify + Eq + Hash + Debug> {
+    bag: Value<LBag<T>>,
+    item: Value<T>,
+    bigger: Value<LBag<T>>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> Clone for Insert<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bag: self.bag.clone(),
+            item: self.item.clone(),
+            bigger: self.bigger.clone(),
+        }
+    }
+}
+
+/** Assert that [`LBag`] `bigger` is `bag` with one more of `item`.
+
+This works in either direction. If only `bigger` is known, the state will be
+forked for each distinct element that could be `item`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lbag::{lbag, insert};
+use std::collections::HashMap;
+
+let bigger = LVar::new();
+let goal = insert(lbag! {1, 2}, 1, &bigger);
+let results: Vec<_> = goal.query(bigger).collect();
+assert_eq!(results, vec![HashMap::from([(1, 2), (2, 1)])]);
+```
+*/
+pub fn insert<T>(
+    bag: impl Into<Value<LBag<T>>>,
+    item: impl Into<Value<T>>,
+    bigger: impl Into<Value<LBag<T>>>,
+) -> Insert<T>
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    Insert {
+        bag: bag.into(),
+        item: item.into(),
+        bigger: bigger.into(),
+    }
+}
+
+/** Assert that [`LBag`] `smaller` is `bag` with one less of `item`.
+
+This is [`insert`] with the bags swapped, so it also works in either
+direction.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lbag::{lbag, remove};
+
+let x = LVar::new();
+let goal = remove(lbag! {1, 2}, x, lbag! {2});
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1]);
+```
+*/
+pub fn remove<T>(
+    bag: impl Into<Value<LBag<T>>>,
+    item: impl Into<Value<T>>,
+    smaller: impl Into<Value<LBag<T>>>,
+) -> Insert<T>
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    insert(smaller, item, bag)
+}
+
+impl<T: Unify + Eq + Hash + Debug> Goal for Insert<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Constraint for Insert<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        match OneOfTwo::resolve(&self.bag, &self.bigger, state)? {
+            OneOfTwo::A(bag, bigger) => {
+                let mut expected = LBag {
+                    counts: bag.counts.clone(),
+                };
+                expected.insert(self.item.clone());
+                let expected = Value::new(expected);
+                Ok(Box::new(move |state| state.unify(&bigger, &expected)))
+            }
+            OneOfTwo::B(bag, bigger) => {
+                let items = bigger.expand(state);
+                let branches: Any = (0..items.len())
+                    .filter(|&index| !items[..index].contains(&items[index]))
+                    .map(|index| {
+                        let mut rest = items.clone();
+                        let found = rest.swap_remove(index);
+                        let rest = Value::new(rest.into_iter().collect::<LBag<T>>());
+                        let (bag, item) = (bag.clone(), self.item.clone());
+                        Rc::new(custom(move |state| {
+                            state.unify(&item, &found)?.unify(&bag, &rest)
+                        })) as Rc<dyn Goal>
+                    })
+                    .collect();
+                Ok(Box::new(move |state| branches.apply(state)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::lbag::{insert, lbag, remove};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn inserts() {
+        let (bag, bigger) = (LVar::new(), LVar::new());
+        let goals = goal_vec![insert(&bag, 3, &bigger), unify(bag, lbag! {3})];
+        goals.assert_permutations_resolve_to(&bigger, vec![HashMap::from([(3, 2)])]);
+    }
+
+    #[test]
+    fn removes_each_distinct_item() {
+        let (x, rest) = (LVar::new(), LVar::new());
+        let goals = goal_vec![remove(lbag! {1, 1, 2}, x, &rest)];
+        goals.assert_permutations_resolve_to(
+            &(x, rest),
+            vec![
+                (1, HashMap::from([(1, 1), (2, 1)])),
+                (2, HashMap::from([(1, 2)])),
+            ],
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_item() {
+        let rest = LVar::new();
+        let goals = goal_vec![remove(lbag! {1, 2}, 3, &rest)];
+        goals.assert_permutations_resolve_to(&rest, vec![]);
+    }
+}
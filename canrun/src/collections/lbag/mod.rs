@@ -0,0 +1,251 @@
+//! A multiset (or "bag") data structure with [`Value`](crate::Value)
+//! elements.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::iter::repeat;
+use std::rc::Rc;
+
+use crate::{Fork, ReadyState, Reify, State, StateIter, Unify, Value};
+
+mod count;
+mod insert;
+
+pub use count::count;
+pub use insert::{insert, remove};
+
+/// A multiset (or "bag") data structure with [`Value`](crate::Value)
+/// elements.
+///
+/// Like [`LSet`](crate::lset::LSet), unification does not depend on the order
+/// of the elements. Unlike a set, each element is counted and two bags only
+/// unify if every element appears the same number of times in both.
+#[derive(Clone, Default, Debug)]
+pub struct LBag<T: Unify + Eq + Hash + Debug> {
+    counts: HashMap<Value<T>, usize>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> LBag<T> {
+    /** Create a new [`LBag`] value.
+
+    You may also be interested in the [`lbag!`] macro.
+
+    # Example:
+    ```
+    use canrun::lbag::LBag;
+
+    let bag: LBag<i32> = LBag::new();
+    ```
+    */
+    pub fn new() -> Self {
+        LBag {
+            counts: HashMap::new(),
+        }
+    }
+
+    /** Add one of an element to an existing [`LBag`].
+
+    # Example:
+    ```
+    use canrun::lbag::LBag;
+
+    let mut bag: LBag<i32> = LBag::new();
+    bag.insert(1);
+    bag.insert(1);
+    assert_eq!(bag.len(), 2);
+    ```
+    */
+    pub fn insert(&mut self, item: impl Into<Value<T>>) {
+        *self.counts.entry(item.into()).or_insert(0) += 1;
+    }
+
+    /// Returns the total number of elements in the [`LBag`], including
+    /// duplicates.
+    pub fn len(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns true if the [`LBag`] contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// List every element with its current value in the state, repeating
+    /// each one according to its count.
+    fn expand(&self, state: &State) -> Vec<Value<T>> {
+        self.counts
+            .iter()
+            .flat_map(|(item, count)| repeat(state.resolve(item)).take(*count))
+            .collect()
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> FromIterator<Value<T>> for LBag<T> {
+    fn from_iter<I: IntoIterator<Item = Value<T>>>(iter: I) -> Self {
+        let mut bag = LBag::new();
+        for item in iter {
+            bag.insert(item);
+        }
+        bag
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Unify for LBag<T> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        let mut a_rest = Vec::new();
+        let mut b_rest = b.expand(&state);
+        for a_item in a.expand(&state) {
+            match b_rest.iter().position(|b_item| *b_item == a_item) {
+                Some(index) => {
+                    b_rest.swap_remove(index);
+                }
+                None => a_rest.push(a_item),
+            }
+        }
+        if a_rest.len() != b_rest.len() {
+            None
+        } else if a_rest.is_empty() {
+            Some(state)
+        } else {
+            // Whatever is left over has to be matched up one by one, which
+            // means considering every possible pairing.
+            state.fork(LBagFork {
+                a: a_rest,
+                b: b_rest,
+            })
+        }
+    }
+}
+
+/// Match the first leftover element of `a` with each distinct element of `b`,
+/// then unify what remains.
+#[derive(Debug)]
+struct LBagFork<T: Unify + Eq + Hash + Debug> {
+    a: Vec<Value<T>>,
+    b: Vec<Value<T>>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> LBagFork<T> {
+    fn candidates(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.b.len()).filter(|&index| !self.b[..index].contains(&self.b[index]))
+    }
+
+    fn pair(&self, state: State, index: usize) -> Option<State> {
+        let state = state.unify(&self.a[0], &self.b[index])?;
+        let a: LBag<T> = self.a[1..].iter().cloned().collect();
+        let mut b = self.b.clone();
+        b.swap_remove(index);
+        let b: LBag<T> = b.into_iter().collect();
+        state.unify(&Value::new(a), &Value::new(b))
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Fork for LBagFork<T> {
+    fn fork(&self, state: &State) -> StateIter {
+        let fork = LBagFork {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        };
+        let candidates: Vec<_> = self.candidates().collect();
+        let state = state.clone();
+        Box::new(
+            candidates
+                .into_iter()
+                .filter_map(move |index| fork.pair(state.clone(), index)),
+        )
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        let matches = self
+            .candidates()
+            .filter(|&index| state.clone().unify(&self.a[0], &self.b[index]).is_some());
+        Some(matches.count())
+    }
+}
+
+impl<T, R> Reify for LBag<T>
+where
+    T: Unify + Eq + Hash + Debug + Reify<Reified = R>,
+    R: Eq + Hash,
+{
+    type Reified = HashMap<R, usize>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        let init = HashMap::with_capacity(self.counts.len());
+        self.counts
+            .iter()
+            .try_fold(init, |mut counts, (item, count)| {
+                // Distinct variables may have resolved to the same value.
+                *counts.entry(state.reify(item)?).or_insert(0) += count;
+                Some(counts)
+            })
+    }
+}
+
+/// Create an [`LBag`](crate::lbag::LBag) with automatic `Into<Value<T>>`
+/// wrapping. Repeated elements are counted.
+///
+/// # Example:
+/// ```
+/// use canrun::LVar;
+/// use canrun::lbag::{lbag, LBag};
+///
+/// let x = LVar::new();
+/// let bag: LBag<i32> = lbag!{x, 1, 1};
+/// ```
+#[macro_export]
+macro_rules! lbag {
+    ($($item:expr),* $(,)?) => {
+        {
+            let mut bag = $crate::lbag::LBag::new();
+            $(bag.insert($item);)*
+            bag
+        }
+    };
+}
+
+#[doc(inline)]
+pub use lbag;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{goal_vec, unify, LVar, StateIterator};
+
+    #[test]
+    fn ignores_order() {
+        let goal = unify(lbag! {1, 2, 1}, lbag! {2, 1, 1});
+        assert_eq!(goal.into_states().count(), 1);
+    }
+
+    #[test]
+    fn counts_duplicates() {
+        let goal = unify(lbag! {1, 2}, lbag! {1, 1, 2});
+        assert_eq!(goal.into_states().count(), 0);
+        let goal = unify(lbag! {1, 1, 2}, lbag! {1, 2, 2});
+        assert_eq!(goal.into_states().count(), 0);
+    }
+
+    #[test]
+    fn forks_over_variables() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(lbag! {x, y, 1}, lbag! {1, 1, 2})];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn variables_can_repeat() {
+        let x = LVar::new();
+        let goals = goal_vec![unify(lbag! {x, x, 2}, lbag! {3, 2, 3})];
+        goals.assert_permutations_resolve_to(&x, vec![3]);
+        let goals = goal_vec![unify(lbag! {x, x, 2}, lbag! {3, 2, 4})];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn reifies_counts() {
+        let (b, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(&b, lbag! {x, 1, 2}), unify(x, 1)];
+        goals.assert_permutations_resolve_to(&b, vec![HashMap::from([(1, 2), (2, 1)])]);
+    }
+}
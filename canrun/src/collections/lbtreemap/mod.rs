@@ -0,0 +1,136 @@
+//! A [`BTreeMap`](std::collections::BTreeMap)-like data structure with
+//! [`LVar`](crate::LVar) keys and values.
+//!
+//! This is an [`LMap`](crate::lmap::LMap) backed by a
+//! [`BTreeMap`](std::collections::BTreeMap), so it requires [`Ord`] keys. In
+//! exchange, candidate keys are always tried in order, so the results of a
+//! query come out in the same order on every run. The [`get`], [`subset`]
+//! and [`superset`] goals are shared with `LMap`.
+use std::collections::BTreeMap;
+
+use crate::lmap::{LMap, MapStore};
+use crate::{ReadyState, Reify, Unify, Value};
+
+pub use crate::lmap::{get, subset, superset};
+
+/// A [`BTreeMap`](std::collections::BTreeMap)-like data structure with
+/// [`LVar`](crate::LVar) keys and values.
+///
+/// Resolved keys are kept in their natural order, while keys that are still
+/// unresolved variables sort before them in order of creation.
+pub type LBTreeMap<K, V> = LMap<K, V, BTreeMap<Value<K>, Value<V>>>;
+
+impl<K: Unify + Ord, V: Unify> MapStore<K, V> for BTreeMap<Value<K>, Value<V>> {
+    fn insert(&mut self, key: Value<K>, value: Value<V>) -> Option<Value<V>> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &Value<K>) -> Option<&Value<V>> {
+        BTreeMap::get(self, key)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&Value<K>, &Value<V>)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+impl<Kv, Kr, Vv, Vr> Reify for LBTreeMap<Kv, Vv>
+where
+    Kv: Unify + Ord + Reify<Reified = Kr>,
+    Kr: Ord,
+    Vv: Unify + Reify<Reified = Vr>,
+{
+    type Reified = BTreeMap<Kr, Vr>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        self.map
+            .entries()
+            .map(|(k, v)| Some((state.reify(k)?, state.reify(v)?)))
+            .collect()
+    }
+}
+
+/// Create an [`LBTreeMap`](crate::lbtreemap::LBTreeMap) with automatic
+/// key/value `Into<Value<T>>` wrapping.
+///
+/// # Example:
+/// ```
+/// use canrun::LVar;
+/// use canrun::lbtreemap::{lbtreemap, LBTreeMap};
+///
+/// let x = LVar::new();
+/// let map: LBTreeMap<i32, i32> = lbtreemap!{x => 1, 2 => 3};
+/// ```
+#[macro_export]
+macro_rules! lbtreemap {
+    ($($key:expr => $value:expr),* $(,)?) => {
+        {
+            let mut map = $crate::lbtreemap::LBTreeMap::new();
+            $(map.insert($key, $value);)*
+            map
+        }
+    };
+}
+
+#[doc(inline)]
+pub use lbtreemap;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::lbtreemap::{get, subset, superset};
+    use crate::{goal_vec, unify, LVar, Query};
+
+    #[test]
+    fn succeeds_with_variable_key_and_value() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goal = unify(lbtreemap! {1 => 2}, lbtreemap! {x => y});
+        let results: Vec<_> = goal.query((x, y)).collect();
+        assert_eq!(results, vec![(1, 2)]);
+    }
+
+    #[test]
+    fn fails_with_different() {
+        let x: LVar<i32> = LVar::new();
+        let goal = unify(lbtreemap! {1 => 2}, lbtreemap! {1 => 3});
+        assert_eq!(goal.query(x).count(), 0);
+    }
+
+    #[test]
+    fn enumerates_in_key_order() {
+        for _ in 0..10 {
+            let x = LVar::new();
+            let goal = unify(
+                lbtreemap! {x => 1},
+                lbtreemap! {5 => 1, 3 => 1, 9 => 1, 1 => 1},
+            );
+            assert_eq!(goal.query(x).count(), 0);
+            let goal = get(x, 1, lbtreemap! {5 => 1, 3 => 1, 9 => 1, 1 => 1});
+            let results: Vec<_> = goal.query(x).collect();
+            assert_eq!(results, vec![1, 3, 5, 9]);
+        }
+    }
+
+    #[test]
+    fn shares_subset_and_superset() {
+        let x = LVar::new();
+        let goal = subset(lbtreemap! {&x => 2}, lbtreemap! {3 => 2, 1 => 2, 2 => 4});
+        let results: Vec<_> = goal.query(x).collect();
+        assert_eq!(results, vec![1, 3]);
+
+        let x = LVar::new();
+        let goal = superset(lbtreemap! {3 => 2, 1 => 2, 2 => 4}, lbtreemap! {&x => 2});
+        let results: Vec<_> = goal.query(x).collect();
+        assert_eq!(results, vec![1, 3]);
+    }
+
+    #[test]
+    fn mergeable_keys() {
+        let (m, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(&m, lbtreemap!(x => 1, 1 => 1)),
+            unify(&m, lbtreemap!(1 => 1)),
+        ];
+        goals.assert_permutations_resolve_to(&(m, x), vec![(BTreeMap::from([(1, 1)]), 1)]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::core::{LVar, Unify, Value};
+use crate::goals::{all, both, either, lazy, unify, Either};
+
+use super::LList;
+
+/** Create a [`Goal`](crate::goals::Goal) that succeeds when `ab` is the list
+`a` followed by the list `b`.
+
+Like the classic relation, this can be run in any direction. When `ab` is
+known it will find each way to split it in two.
+
+# Examples:
+```
+use canrun::{llist, llist::appendo, LVar, Query};
+
+let ab = LVar::new();
+let goal = appendo(llist![1, 2], llist![3], &ab);
+let results: Vec<_> = goal.query(ab).collect();
+assert_eq!(results, vec![vec![1, 2, 3]]);
+```
+
+```
+# use canrun::{llist, llist::appendo, LVar, Query};
+let (a, b) = (LVar::new(), LVar::new());
+let goal = appendo(&a, &b, llist![1, 2]);
+let results: Vec<_> = goal.query((a, b)).collect();
+assert_eq!(results, vec![
+    (vec![], vec![1, 2]),
+    (vec![1], vec![2]),
+    (vec![1, 2], vec![]),
+]);
+```
+*/
+pub fn appendo<T: Unify>(
+    a: impl Into<Value<LList<T>>>,
+    b: impl Into<Value<LList<T>>>,
+    ab: impl Into<Value<LList<T>>>,
+) -> Either {
+    let (a, b, ab) = (a.into(), b.into(), ab.into());
+    either(
+        both(unify(a.clone(), LList::Nil), unify(b.clone(), ab.clone())),
+        lazy(move || {
+            let (head, rest_a, rest_ab) = (LVar::new(), LVar::new(), LVar::new());
+            all![
+                unify(a.clone(), LList::cons(&head, &rest_a)),
+                unify(ab.clone(), LList::cons(head, &rest_ab)),
+                appendo(rest_a, b.clone(), rest_ab),
+            ]
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, llist, llist::LList};
+
+    use super::appendo;
+
+    #[test]
+    fn finds_suffix() {
+        let b = LVar::new();
+        let goal = appendo(llist![1, 2], &b, llist![1, 2, 3, 4]);
+        assert_eq!(goal.query(b).collect::<Vec<_>>(), vec![vec![3, 4]]);
+    }
+
+    #[test]
+    fn finds_prefix() {
+        let a = LVar::new();
+        let goal = appendo(&a, llist![3, 4], llist![1, 2, 3, 4]);
+        assert_eq!(goal.query(a).collect::<Vec<_>>(), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn fails_on_mismatch() {
+        let a: LVar<LList<i32>> = LVar::new();
+        let goal = appendo(&a, llist![3], llist![1, 2]);
+        assert_eq!(goal.query(a).count(), 0);
+    }
+
+    #[test]
+    fn with_other_goals() {
+        let (a, b, x) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            appendo(&a, &b, llist![1, 2, 3]),
+            unify(a, llist![1 | &x]),
+            unify(b, llist![3]),
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![vec![2]]);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::core::{Unify, Value};
+use crate::goals::{unify, Goal};
+
+use super::LList;
+
+/** Create a [`Goal`] that relates a list to its head and tail.
+
+This is equivalent to unifying `list` with
+[`LList::cons(head, tail)`](LList::cons), and works in any direction.
+
+# Example:
+```
+use canrun::{llist, llist::conso, LVar, Query};
+
+let (head, tail) = (LVar::new(), LVar::new());
+let goal = conso(head, &tail, llist![1, 2, 3]);
+let results: Vec<_> = goal.query((head, tail)).collect();
+assert_eq!(results, vec![(1, vec![2, 3])]);
+```
+*/
+pub fn conso<T: Unify>(
+    head: impl Into<Value<T>>,
+    tail: impl Into<Value<LList<T>>>,
+    list: impl Into<Value<LList<T>>>,
+) -> impl Goal {
+    unify(list, LList::cons(head, tail))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, llist};
+
+    use super::conso;
+
+    #[test]
+    fn builds_list() {
+        let (list, tail) = (LVar::new(), LVar::new());
+        let goals = goal_vec![conso(1, &tail, &list), unify(tail, llist![2, 3])];
+        goals.assert_permutations_resolve_to(&list, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn fails_on_empty() {
+        let head: LVar<i32> = LVar::new();
+        let goal = conso(head, LVar::new(), llist![]);
+        assert_eq!(goal.query(head).count(), 0);
+    }
+}
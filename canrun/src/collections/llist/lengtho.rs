@@ -0,0 +1,118 @@
+use crate::core::{
+    constraints::{Constraint, OneOfTwo, ResolveFn},
+    LVar, LVarList, State, Unify, Value,
+};
+use crate::goals::{all, both, either, lazy, unify, Either, Goal};
+use std::rc::Rc;
+
+use super::LList;
+
+/** Create a [`Goal`] that succeeds when `list` has `length` items.
+
+When the length is known, a list of that many fresh items is generated.
+When the list is known, its length is counted. If neither is known, lists of
+increasing length are generated without end.
+
+# Examples:
+```
+use canrun::{llist, llist::lengtho, LVar, Query};
+
+let n = LVar::new();
+let goal = lengtho(llist![1, 2, 3], n);
+let results: Vec<_> = goal.query(n).collect();
+assert_eq!(results, vec![3]);
+```
+
+```
+# use canrun::{llist, llist::{appendo, lengtho}, all, LVar, Query};
+let (a, b) = (LVar::new(), LVar::new());
+let goal = all![lengtho(&a, 2), appendo(&a, &b, llist![1, 2, 3])];
+let results: Vec<_> = goal.query((a, b)).collect();
+assert_eq!(results, vec![(vec![1, 2], vec![3])]);
+```
+*/
+pub fn lengtho<T: Unify>(
+    list: impl Into<Value<LList<T>>>,
+    length: impl Into<Value<usize>>,
+) -> Either {
+    let (list, length) = (list.into(), length.into());
+    either(
+        both(unify(list.clone(), LList::Nil), unify(length.clone(), 0)),
+        lazy(move || {
+            let (head, tail, tail_length) = (LVar::<T>::new(), LVar::new(), LVar::new());
+            all![
+                unify(list.clone(), LList::cons(head, &tail)),
+                Succ {
+                    n: tail_length.into(),
+                    succ: length.clone()
+                },
+                lengtho(tail, tail_length),
+            ]
+        }),
+    )
+}
+
+/// Relates a natural number to the one after it, without underflowing when
+/// `succ` is zero.
+#[derive(Debug, Clone)]
+struct Succ {
+    n: Value<usize>,
+    succ: Value<usize>,
+}
+
+impl Goal for Succ {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl Constraint for Succ {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (var, value) = match OneOfTwo::resolve(&self.n, &self.succ, state)? {
+            OneOfTwo::A(n, succ) => (succ, Value::new(*n + 1)),
+            OneOfTwo::B(n, succ) => match succ.checked_sub(1) {
+                Some(prev) => (n, Value::new(prev)),
+                None => return Ok(Box::new(|_| None)),
+            },
+        };
+        Ok(Box::new(move |state| state.unify(&var, &value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, llist, llist::LList};
+
+    use super::lengtho;
+
+    #[test]
+    fn counts_items() {
+        let n = LVar::new();
+        let goal = lengtho(llist![1, 2, 3, 4], n);
+        assert_eq!(goal.query(n).collect::<Vec<_>>(), vec![4]);
+        let goal = lengtho(LList::<i32>::Nil, n);
+        assert_eq!(goal.query(n).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn builds_lists() {
+        let list = LVar::new();
+        let goals = goal_vec![lengtho(&list, 3), unify(&list, llist![1, 2 | llist![3]])];
+        goals.assert_permutations_resolve_to(&list, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn fails_on_wrong_length() {
+        let list: LVar<LList<i32>> = LVar::new();
+        let goals = goal_vec![lengtho(&list, 2), unify(&list, llist![1, 2, 3])];
+        goals.assert_permutations_resolve_to(&list, vec![]);
+    }
+
+    #[test]
+    fn generates_lengths() {
+        let (list, n) = (LVar::<LList<i32>>::new(), LVar::new());
+        let goal = lengtho(list, n);
+        let lengths: Vec<_> = goal.query(n).take(3).collect();
+        assert_eq!(lengths, vec![0, 1, 2]);
+    }
+}
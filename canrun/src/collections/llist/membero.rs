@@ -0,0 +1,79 @@
+use crate::core::{LVar, Unify, Value};
+use crate::goals::{both, either, lazy, unify, Goal};
+
+use super::LList;
+
+/** Create a [`Goal`] that succeeds once for each item of `list` that unifies
+with `item`.
+
+Unlike [`lvec::member`](crate::lvec::member), the list does not need to be
+fully known. If the tail is left open, new lists containing `item` will be
+generated without end.
+
+# Examples:
+```
+use canrun::{llist, llist::membero, LVar, Query};
+
+let x = LVar::new();
+let goal = membero(x, llist![1, 2, 3]);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1, 2, 3]);
+```
+
+```
+# use canrun::{llist, llist::membero, LVar, Query};
+let (x, rest) = (LVar::new(), LVar::new());
+let goal = membero(3, llist![1, x | rest]);
+let results: Vec<_> = goal.query(x).take(1).collect();
+assert_eq!(results, vec![3]);
+```
+*/
+pub fn membero<T: Unify>(item: impl Into<Value<T>>, list: impl Into<Value<LList<T>>>) -> impl Goal {
+    let (item, list) = (item.into(), list.into());
+    lazy(move || {
+        let (head, tail) = (LVar::new(), LVar::new());
+        both(
+            unify(list.clone(), LList::cons(&head, &tail)),
+            either(unify(item.clone(), head), membero(item.clone(), tail)),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, llist, llist::LList};
+
+    use super::membero;
+
+    #[test]
+    fn finds_every_match() {
+        let goal = membero(2, llist![1, 2, 3, 2]);
+        assert_eq!(goal.query(1).count(), 2);
+    }
+
+    #[test]
+    fn fails_without_match() {
+        let x: LVar<LList<i32>> = LVar::new();
+        let goals = goal_vec![membero(4, &x), unify(&x, llist![1, 2, 3])];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn with_other_goals() {
+        let x = LVar::new();
+        let goals = goal_vec![membero(x, llist![1, 2, 3]), unify(x, 2)];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+    }
+
+    #[test]
+    fn fills_in_partial_lists() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let list = LVar::new();
+        let goals = goal_vec![
+            unify(&list, llist![x, y]),
+            membero(1, &list),
+            membero(2, list),
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 2), (2, 1)]);
+    }
+}
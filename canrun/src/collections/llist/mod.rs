@@ -0,0 +1,248 @@
+/*! A classic cons-list with [`Value`](crate::Value) heads and tails.
+
+Unlike [`LVec`](crate::lvec::LVec), the tail of an [`LList`] can itself be a
+logic variable. This makes it possible to describe lists of unknown length and
+to write the traditional recursive list relations found in Prolog and
+miniKanren, such as [`appendo`] and [`membero`].
+*/
+
+mod appendo;
+mod conso;
+mod lengtho;
+mod membero;
+mod reverseo;
+
+use crate::{
+    core::{Reify, State, Unify, Value},
+    ReadyState,
+};
+pub use appendo::appendo;
+pub use conso::conso;
+pub use lengtho::lengtho;
+pub use membero::membero;
+pub use reverseo::reverseo;
+
+use std::{fmt, rc::Rc};
+
+/// A singly linked list with [`Value`](crate::Value) heads and tails.
+///
+/// Construct with the [`llist!`](crate::llist!) macro, [`LList::cons`], or
+/// the `From<Vec<Value<T>>>` and `FromIterator` trait implementations.
+pub enum LList<T: Unify> {
+    /// The empty list.
+    Nil,
+    /// A head item followed by the rest of the list.
+    Cons(Value<T>, Value<LList<T>>),
+}
+
+impl<T: Unify> LList<T> {
+    /// Create a list with the given head and tail.
+    ///
+    /// ```
+    /// use canrun::{llist::LList, LVar};
+    ///
+    /// let rest = LVar::new();
+    /// let list: LList<i32> = LList::cons(1, rest);
+    /// ```
+    pub fn cons(head: impl Into<Value<T>>, tail: impl Into<Value<LList<T>>>) -> Self {
+        LList::Cons(head.into(), tail.into())
+    }
+
+    /// Returns true if this is the empty list.
+    ///
+    /// Note that a non-empty list may still have an unresolved tail.
+    pub fn is_nil(&self) -> bool {
+        matches!(self, LList::Nil)
+    }
+}
+
+impl<T: Unify> Clone for LList<T> {
+    fn clone(&self) -> Self {
+        match self {
+            LList::Nil => LList::Nil,
+            LList::Cons(head, tail) => LList::Cons(head.clone(), tail.clone()),
+        }
+    }
+}
+
+impl<T: Unify> fmt::Debug for LList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LList::Nil => write!(f, "Nil"),
+            LList::Cons(head, tail) => f.debug_tuple("Cons").field(head).field(tail).finish(),
+        }
+    }
+}
+
+/** Create an [`LList<T>`](crate::collections::llist::LList) with automatic
+`Into<Value<T>>` conversion.
+
+Like [`lvec!`](crate::lvec!), items can be any mix of `T`,
+[`Value<T>`](crate::Value) and [`LVar<T>`](crate::LVar). A Prolog style
+`| tail` may be given after the items to specify the rest of the list.
+
+Items containing a bare `|` or `,` (such as closures or generic calls) need to
+be wrapped in parentheses.
+
+# Example:
+```
+use canrun::{llist, unify, LVar, Query};
+
+let (x, rest) = (LVar::new(), LVar::new());
+let goal = unify(llist![x, 2 | &rest], llist![1, 2, 3]);
+let results: Vec<_> = goal.query((x, rest)).collect();
+assert_eq!(results, vec![(1, vec![3])]);
+```
+*/
+#[macro_export]
+macro_rules! llist {
+    (@build [] $tail:expr) => {
+        $tail
+    };
+    (@build [$head:tt $($rest:tt)*] $tail:expr) => {
+        $crate::collections::llist::LList::cons($head, $crate::llist!(@build [$($rest)*] $tail))
+    };
+    (@munch [$($items:tt)*] []) => {
+        $crate::llist!(@build [$($items)*] $crate::collections::llist::LList::Nil)
+    };
+    (@munch [$($items:tt)*] [$($item:tt)+]) => {
+        $crate::llist!(@build [$($items)* ($($item)+)] $crate::collections::llist::LList::Nil)
+    };
+    (@munch [$($items:tt)*] [$($item:tt)+] , $($rest:tt)*) => {
+        $crate::llist!(@munch [$($items)* ($($item)+)] [] $($rest)*)
+    };
+    (@munch [$($items:tt)*] [$($item:tt)+] | $tail:expr) => {
+        $crate::llist!(@build [$($items)* ($($item)+)] $tail)
+    };
+    (@munch [$($items:tt)*] [$($item:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::llist!(@munch [$($items)*] [$($item)* $next] $($rest)*)
+    };
+    () => {
+        $crate::collections::llist::LList::Nil
+    };
+    ($($tokens:tt)+) => {
+        $crate::llist!(@munch [] [] $($tokens)+)
+    };
+}
+
+impl<T: Unify> Unify for LList<T> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        match (a.as_ref(), b.as_ref()) {
+            (LList::Nil, LList::Nil) => Some(state),
+            (LList::Cons(a_head, a_tail), LList::Cons(b_head, b_tail)) => {
+                state.unify(a_head, b_head)?.unify(a_tail, b_tail)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: Unify + Reify> Reify for LList<T> {
+    type Reified = Vec<T::Reified>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Vec<T::Reified>> {
+        let mut items = Vec::new();
+        let (mut head, mut tail) = match self {
+            LList::Nil => return Some(items),
+            LList::Cons(head, tail) => (head.clone(), tail.clone()),
+        };
+        // Walk the tails iteratively so long lists don't overflow the stack.
+        loop {
+            items.push(head.reify_in(state)?);
+            match state.resolve(&tail).resolved()? {
+                LList::Nil => return Some(items),
+                LList::Cons(next_head, next_tail) => {
+                    (head, tail) = (next_head.clone(), next_tail.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<T: Unify> From<Vec<Value<T>>> for LList<T> {
+    fn from(vec: Vec<Value<T>>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T: Unify> FromIterator<Value<T>> for LList<T> {
+    fn from_iter<I: IntoIterator<Item = Value<T>>>(iter: I) -> Self {
+        let items: Vec<_> = iter.into_iter().collect();
+        items
+            .into_iter()
+            .rev()
+            .fold(LList::Nil, |tail, head| LList::cons(head, tail))
+    }
+}
+
+impl<T: Unify> FromIterator<T> for LList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().map(Value::new).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goals::unify, llist::LList, Value};
+
+    #[test]
+    fn succeeds() {
+        let x = LVar::new();
+        let goal = unify(llist![x, 2], llist![1, 2]);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn fails() {
+        let x = LVar::new();
+        let goal = unify(llist![x, 1], llist![1, 2]);
+        assert_eq!(goal.query(x).count(), 0);
+        let goal = unify(llist![x, 1], llist![1, 2, 3]);
+        assert_eq!(goal.query(x).count(), 0);
+    }
+
+    #[test]
+    fn unifies_tails() {
+        let (x, rest) = (LVar::new(), LVar::new());
+        let goal = unify(llist![1 | &rest], llist![x, 2, 3]);
+        assert_eq!(
+            goal.query((x, rest)).collect::<Vec<_>>(),
+            vec![(1, vec![2, 3])]
+        );
+    }
+
+    #[test]
+    fn macro_forms() {
+        let empty: LList<i32> = llist![];
+        assert!(empty.is_nil());
+        let trailing: LList<i32> = llist![1, 2,];
+        let tail: LList<i32> = llist![3];
+        let nested: LList<i32> = llist![1, (1 + 1) | tail];
+        let goal = unify(trailing, llist![1, 2]);
+        assert_eq!(goal.query(1).count(), 1);
+        let goal = unify(nested, llist![1, 2, 3]);
+        assert_eq!(goal.query(1).count(), 1);
+    }
+
+    #[test]
+    fn does_not_reify_open_tail() {
+        let rest: LVar<LList<i32>> = LVar::new();
+        let x = LVar::new();
+        let goal = unify(&x, llist![1, 2 | rest]);
+        assert_eq!(goal.query(x).count(), 0);
+    }
+
+    #[test]
+    fn reifies_long_lists() {
+        let list: LList<usize> = (0..10_000).collect();
+        let goal = unify(1, 1);
+        let results: Vec<_> = goal.query(Value::new(list)).collect();
+        assert_eq!(results[0].len(), 10_000);
+    }
+
+    #[test]
+    fn from_vec() {
+        let list: LList<usize> = vec![Value::new(1), Value::new(2)].into();
+        let goal = unify(list, llist![1, 2]);
+        assert_eq!(goal.query(1).count(), 1);
+    }
+}
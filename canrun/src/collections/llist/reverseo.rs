@@ -0,0 +1,110 @@
+use crate::core::{LVar, Unify, Value};
+use crate::goals::{all, both, either, lazy, unify, Either, Goal};
+
+use super::LList;
+
+/** Create a [`Goal`] that succeeds when `reversed` holds the items of `list`
+in reverse order.
+
+This terminates when either side has a known length.
+
+# Example:
+```
+use canrun::{llist, llist::reverseo, LVar, Query};
+
+let x = LVar::new();
+let goal = reverseo(&x, llist![1, 2, 3]);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![vec![3, 2, 1]]);
+```
+*/
+pub fn reverseo<T: Unify>(
+    list: impl Into<Value<LList<T>>>,
+    reversed: impl Into<Value<LList<T>>>,
+) -> impl Goal {
+    let (list, reversed) = (list.into(), reversed.into());
+    // Walking both lists in step keeps the accumulator from running past the
+    // end when only `reversed` is known.
+    both(
+        same_length(list.clone(), reversed.clone()),
+        reverse_onto(list, Value::new(LList::Nil), reversed),
+    )
+}
+
+fn same_length<T: Unify>(a: Value<LList<T>>, b: Value<LList<T>>) -> Either {
+    either(
+        both(unify(a.clone(), LList::Nil), unify(b.clone(), LList::Nil)),
+        lazy(move || {
+            let (a_tail, b_tail) = (LVar::new(), LVar::new());
+            all![
+                unify(a.clone(), LList::cons(LVar::new(), &a_tail)),
+                unify(b.clone(), LList::cons(LVar::new(), &b_tail)),
+                same_length(a_tail.into(), b_tail.into()),
+            ]
+        }),
+    )
+}
+
+/// Push each item of `list` onto `acc`, unifying the final result with
+/// `reversed`.
+fn reverse_onto<T: Unify>(
+    list: Value<LList<T>>,
+    acc: Value<LList<T>>,
+    reversed: Value<LList<T>>,
+) -> Either {
+    either(
+        both(
+            unify(list.clone(), LList::Nil),
+            unify(acc.clone(), reversed.clone()),
+        ),
+        lazy(move || {
+            let (head, tail) = (LVar::new(), LVar::new());
+            both(
+                unify(list.clone(), LList::cons(&head, &tail)),
+                reverse_onto(
+                    tail.into(),
+                    Value::new(LList::cons(head, acc.clone())),
+                    reversed.clone(),
+                ),
+            )
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, llist, llist::LList};
+
+    use super::reverseo;
+
+    #[test]
+    fn reverses_forward() {
+        let x = LVar::new();
+        let goal = reverseo(llist![1, 2, 3], &x);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![vec![3, 2, 1]]);
+    }
+
+    #[test]
+    fn reverses_backward() {
+        let x = LVar::new();
+        let goal = reverseo(&x, llist![1, 2, 3]);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![vec![3, 2, 1]]);
+    }
+
+    #[test]
+    fn empty_list() {
+        let x: LVar<LList<i32>> = LVar::new();
+        let goal = reverseo(&x, llist![]);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![vec![]]);
+    }
+
+    #[test]
+    fn palindromes() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![reverseo(llist![1, x, 3], llist![y, 2, 1])];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(2, 3)]);
+        let list: LVar<LList<i32>> = LVar::new();
+        let goals = goal_vec![unify(&list, llist![1, 2]), reverseo(&list, &list)];
+        goals.assert_permutations_resolve_to(&list, vec![]);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::{ascending, LMap, Pair};
+use crate::{
+    constraints::{Constraint, ResolveFn},
+    goals::Goal,
+    lvec::LVec,
+    LVarList, State, Unify, Value,
+};
+
+/** Assert that [`LVec`] `entries` holds exactly the key/value pairs of
+[`LMap`] `map`.
+
+The entries are listed in ascending key order. If every key in `entries` is
+known, `map` must contain exactly those entries, and the keys must already be
+sorted with no repeats. Like [`keys`](super::keys), this is a sorted view of
+the map, so listing the entries in any other order fails. Otherwise, once the keys of `map` are known, `entries`
+is bound to its pairs in sorted order.
+
+# Example:
+```
+use canrun::{LVar, Query, lvec, ltup};
+use canrun::lmap::{lmap, entries};
+use std::collections::HashMap;
+
+let m = LVar::new();
+let goal = entries(&m, lvec![ltup!(1, "a"), ltup!(2, "b")]);
+let results: Vec<_> = goal.query(m).collect();
+assert_eq!(results, vec![HashMap::from([(1, "a"), (2, "b")])]);
+```
+*/
+pub fn entries<K, V>(
+    map: impl Into<Value<LMap<K, V>>>,
+    entries: impl Into<Value<LVec<Pair<K, V>>>>,
+) -> impl Goal
+where
+    K: Unify + Eq + Hash + Ord + Debug,
+    V: Unify + Debug,
+{
+    Entries {
+        map: map.into(),
+        entries: entries.into(),
+    }
+}
+
+#[derive(Debug)]
+struct Entries<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
+    map: Value<LMap<K, V>>,
+    entries: Value<LVec<Pair<K, V>>>,
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Clone for Entries<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Goal for Entries<K, V> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Entries<K, V> {
+    /// Build a map out of the entries, or list the unresolved ones. Returns
+    /// `Ok(None)` if the keys are out of order or repeated.
+    fn entries_map(&self, state: &State) -> Result<Option<LMap<K, V>>, LVarList> {
+        let entries = match state.resolve(&self.entries) {
+            Value::Resolved(entries) => entries,
+            Value::Var(var) => return Err(LVarList::one(&var)),
+        };
+        let mut pairs = Vec::new();
+        let mut unresolved = Vec::new();
+        for entry in entries.as_slice() {
+            match state.resolve(entry) {
+                Value::Resolved(pair) => pairs.push(pair),
+                Value::Var(var) => unresolved.push(LVarList::one(&var)),
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(LVarList::flatten(unresolved.into_iter()));
+        }
+        let Some(keys) = ascending(pairs.iter().map(|pair| &pair.0), state)? else {
+            return Ok(None);
+        };
+        let mut map = LMap::new();
+        for (key, pair) in keys.into_iter().zip(&pairs) {
+            map.insert(key, pair.1.clone());
+        }
+        Ok(Some(map))
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Constraint for Entries<K, V> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (map, entries) = (self.map.clone(), self.entries.clone());
+        let waiting = match self.entries_map(state) {
+            Ok(Some(expected)) => {
+                let expected = Value::new(expected);
+                return Ok(Box::new(move |state| state.unify(&map, &expected)));
+            }
+            Ok(None) => return Ok(Box::new(|_| None)),
+            Err(waiting) => waiting,
+        };
+        match state.resolve(&self.map) {
+            Value::Resolved(found) => {
+                let found: LVec<Pair<K, V>> = found
+                    .sorted_entries(state)?
+                    .into_iter()
+                    .map(Value::new)
+                    .collect();
+                let found = Value::new(found);
+                Ok(Box::new(move |state| state.unify(&entries, &found)))
+            }
+            Value::Var(var) => Err(LVarList::flatten(
+                [waiting, LVarList::one(&var)].into_iter(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lmap::{entries, lmap};
+    use crate::{goal_vec, ltup, lvec, unify, LVar};
+
+    #[test]
+    fn lists_entries() {
+        let es = LVar::new();
+        let goals = goal_vec![entries(lmap! {3 => 30, 1 => 10, 4 => 40, 2 => 20}, &es)];
+        goals.assert_permutations_resolve_to(&es, vec![vec![(1, 10), (2, 20), (3, 30), (4, 40)]]);
+    }
+
+    #[test]
+    fn matches_entries() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            entries(lmap! {1 => 2, 3 => 4}, lvec![ltup!(y, 2), ltup!(3, x)]),
+            unify(x, 4),
+        ];
+        goals.assert_permutations_resolve_to(&y, vec![1]);
+    }
+
+    #[test]
+    fn builds_map() {
+        let (m, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            entries(
+                &m,
+                lvec![ltup!(1, 10), ltup!(2, x), ltup!(3, 30), ltup!(4, 40)]
+            ),
+            unify(&m, lmap! {4 => 40, 2 => 20, 3 => 30, 1 => 10}),
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![20]);
+        let goals = goal_vec![
+            entries(&m, lvec![ltup!(2, 20), ltup!(1, 10)]),
+            unify(&m, lmap! {1 => 10, 2 => 20}),
+        ];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+    }
+}
@@ -1,14 +1,16 @@
-use std::{fmt::Debug, hash::Hash};
+use std::fmt::Debug;
 
-use crate::{goals::Goal, lmap, Unify, Value};
+use crate::{goals::Goal, Unify, Value};
 
-use super::{subset, LMap};
+use super::{subset, LMap, MapStore};
 
 /**
 Assert that the a given key and value combination can be found in an
 [`LMap`]
 
 This is essentially a single key case of [`subset`](crate::lmap::subset::subset).
+It works with any [`MapStore`], including an
+[`LBTreeMap`](crate::lbtreemap::LBTreeMap).
 
 # Example:
 ```
@@ -20,16 +22,19 @@ let goal = get(1, x, lmap! {1 => 2});
 let results: Vec<_> = goal.query(x).collect();
 ```
 */
-pub fn get<K, V>(
+pub fn get<K, V, M>(
     key: impl Into<Value<K>>,
     value: impl Into<Value<V>>,
-    b: impl Into<Value<LMap<K, V>>>,
+    b: impl Into<Value<LMap<K, V, M>>>,
 ) -> impl Goal
 where
-    K: Unify + Eq + Hash + Debug,
+    K: Unify + Debug,
     V: Unify + Debug,
+    M: MapStore<K, V>,
 {
-    subset(lmap! {key => value}, b)
+    let mut a = LMap::new();
+    a.insert(key, value);
+    subset(a, b)
 }
 
 #[cfg(test)]
@@ -0,0 +1,201 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::LMap;
+use crate::{
+    constraints::{Constraint, ResolveFn},
+    goals::{custom, Any, Goal},
+    LVarList, State, Unify, Value,
+};
+
+/** Assert that [`LMap`] `bigger` is `map` with the extra entry `key => value`.
+
+The key must not already be in `map`. If `map` is known this waits for the
+key and every key already in `map` to be resolved, and then adds the entry. If only `bigger` is known, the state
+will be forked for each entry whose key could be `key`, with that entry
+removed to form `map`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lmap::{lmap, insert};
+use std::collections::HashMap;
+
+let m = LVar::new();
+let goal = insert(lmap! {1 => 2}, 3, 4, &m);
+let results: Vec<_> = goal.query(m).collect();
+assert_eq!(results, vec![HashMap::from([(1, 2), (3, 4)])]);
+```
+*/
+pub fn insert<K, V>(
+    map: impl Into<Value<LMap<K, V>>>,
+    key: impl Into<Value<K>>,
+    value: impl Into<Value<V>>,
+    bigger: impl Into<Value<LMap<K, V>>>,
+) -> impl Goal
+where
+    K: Unify + Eq + Hash + Debug,
+    V: Unify + Debug,
+{
+    Insert {
+        map: map.into(),
+        key: key.into(),
+        value: value.into(),
+        bigger: bigger.into(),
+    }
+}
+
+/** Assert that [`LMap`] `smaller` is `map` without the entry for `key`.
+
+This is [`insert`] with the maps swapped and the value ignored, so it also
+works in either direction.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lmap::{lmap, remove};
+use std::collections::HashMap;
+
+let m = LVar::new();
+let goal = remove(lmap! {1 => 2, 3 => 4}, 3, &m);
+let results: Vec<_> = goal.query(m).collect();
+assert_eq!(results, vec![HashMap::from([(1, 2)])]);
+```
+*/
+pub fn remove<K, V>(
+    map: impl Into<Value<LMap<K, V>>>,
+    key: impl Into<Value<K>>,
+    smaller: impl Into<Value<LMap<K, V>>>,
+) -> impl Goal
+where
+    K: Unify + Eq + Hash + Debug,
+    V: Unify + Debug,
+{
+    insert(smaller, key, Value::var(), map)
+}
+
+#[derive(Debug)]
+struct Insert<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
+    map: Value<LMap<K, V>>,
+    key: Value<K>,
+    value: Value<V>,
+    bigger: Value<LMap<K, V>>,
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Clone for Insert<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            key: self.key.clone(),
+            value: self.value.clone(),
+            bigger: self.bigger.clone(),
+        }
+    }
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Goal for Insert<K, V> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Constraint for Insert<K, V> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (map, bigger) = (self.map.clone(), self.bigger.clone());
+        match (state.resolve(&self.map), state.resolve(&self.bigger)) {
+            (Value::Resolved(found), _) => {
+                let key = match state.resolve(&self.key) {
+                    Value::Resolved(key) => Value::Resolved(key),
+                    Value::Var(var) => return Err(LVarList::one(&var)),
+                };
+                // A key that is still a variable could turn out to be the
+                // one being inserted, so wait until they are all known.
+                let mut unresolved = Vec::new();
+                for existing in found.map.keys() {
+                    match state.resolve(existing) {
+                        Value::Var(var) => unresolved.push(LVarList::one(&var)),
+                        existing if existing == key => return Ok(Box::new(|_| None)),
+                        Value::Resolved(_) => {}
+                    }
+                }
+                if !unresolved.is_empty() {
+                    return Err(LVarList::flatten(unresolved.into_iter()));
+                }
+                let mut expected = LMap::from_store(found.map.clone());
+                expected.insert(key, self.value.clone());
+                let expected = Value::new(expected);
+                Ok(Box::new(move |state| state.unify(&bigger, &expected)))
+            }
+            (Value::Var(_), Value::Resolved(found)) => {
+                let branches: Any = found
+                    .map
+                    .keys()
+                    .map(|found_key| {
+                        let mut rest = LMap::from_store(found.map.clone());
+                        let found_value = rest.map.remove(found_key).expect("key was just found");
+                        let (found_key, rest) = (found_key.clone(), Value::new(rest));
+                        let (map, key, value) = (map.clone(), self.key.clone(), self.value.clone());
+                        Rc::new(custom(move |state| {
+                            state
+                                .unify(&key, &found_key)?
+                                .unify(&value, &found_value)?
+                                .unify(&map, &rest)
+                        })) as Rc<dyn Goal>
+                    })
+                    .collect();
+                Ok(Box::new(move |state| branches.apply(state)))
+            }
+            (Value::Var(map), Value::Var(bigger)) => Err(LVarList::two(&map, &bigger)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::lmap::{insert, lmap, remove};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn inserts() {
+        let (m, bigger) = (LVar::new(), LVar::new());
+        let goals = goal_vec![insert(&m, 2, 3, &bigger), unify(m, lmap! {1 => 1})];
+        goals.assert_permutations_resolve_to(&bigger, vec![HashMap::from([(1, 1), (2, 3)])]);
+    }
+
+    #[test]
+    fn fails_on_existing_key() {
+        let bigger = LVar::new();
+        let goals = goal_vec![insert(lmap! {1 => 1}, 1, 2, &bigger)];
+        goals.assert_permutations_resolve_to(&bigger, vec![]);
+    }
+
+    #[test]
+    fn waits_for_variable_keys() {
+        let (x, bigger) = (LVar::new(), LVar::new());
+        let goals = goal_vec![insert(lmap! {&x => 1}, 2, 5, &bigger), unify(x, 2)];
+        goals.assert_permutations_resolve_to(&bigger, vec![]);
+        let goals = goal_vec![insert(lmap! {&x => 1}, 2, 5, &bigger), unify(x, 3)];
+        goals.assert_permutations_resolve_to(&bigger, vec![HashMap::from([(3, 1), (2, 5)])]);
+    }
+
+    #[test]
+    fn removes_any_matching_key() {
+        let (k, v, rest) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![insert(&rest, k, v, lmap! {1 => 10, 2 => 20})];
+        goals.assert_permutations_resolve_to(
+            &(k, v, rest),
+            vec![
+                (1, 10, HashMap::from([(2, 20)])),
+                (2, 20, HashMap::from([(1, 10)])),
+            ],
+        );
+    }
+
+    #[test]
+    fn remove_fails_on_missing_key() {
+        let rest = LVar::new();
+        let goals = goal_vec![remove(lmap! {1 => 10}, 2, &rest)];
+        goals.assert_permutations_resolve_to(&rest, vec![]);
+    }
+}
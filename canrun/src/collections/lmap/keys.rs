@@ -0,0 +1,157 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::{ascending, LMap};
+use crate::{
+    constraints::{Constraint, ResolveFn},
+    goals::Goal,
+    lvec::LVec,
+    LVarList, State, Unify, Value,
+};
+
+/** Assert that [`LVec`] `keys` holds exactly the keys of [`LMap`] `map`.
+
+The keys are listed in ascending order. If every key in `keys` is known, `map`
+must have exactly those keys (with any values), and the keys must already be
+sorted with no repeats. Otherwise, once the keys of `map` are known, `keys` is
+bound to them in sorted order.
+
+# Example:
+```
+use canrun::{LVar, Query, lvec};
+use canrun::lmap::{lmap, keys};
+
+let x = LVar::new();
+let goal = keys(lmap! {1 => "a", 2 => "b"}, lvec![x, 2]);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1]);
+```
+
+This makes `keys` a sorted view of the map rather than a relation to every
+ordering of its keys, so there is exactly one answer for a known map. Listing
+the right keys in another order fails. Combine it with
+[`permutation`](crate::lvec::permutation) to accept any order:
+```
+use canrun::{LVar, Query, lvec, all};
+use canrun::lmap::{lmap, keys};
+
+let m = lmap! {1 => "a", 2 => "b"};
+assert_eq!(keys(m.clone(), lvec![2, 1]).query(1).count(), 0);
+
+let sorted = LVar::new();
+let goal = all![keys(m, &sorted), lvec::permutation(&sorted, lvec![2, 1])];
+assert_eq!(goal.query(1).count(), 1);
+```
+*/
+pub fn keys<K, V>(map: impl Into<Value<LMap<K, V>>>, keys: impl Into<Value<LVec<K>>>) -> impl Goal
+where
+    K: Unify + Eq + Hash + Ord + Debug,
+    V: Unify + Debug,
+{
+    Keys {
+        map: map.into(),
+        keys: keys.into(),
+    }
+}
+
+#[derive(Debug)]
+struct Keys<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
+    map: Value<LMap<K, V>>,
+    keys: Value<LVec<K>>,
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Clone for Keys<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Goal for Keys<K, V> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Constraint for Keys<K, V> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (map, keys) = (self.map.clone(), self.keys.clone());
+        let waiting = match state.resolve(&self.keys) {
+            Value::Resolved(listed) => match ascending(listed.as_slice().iter(), state) {
+                Ok(Some(listed)) => {
+                    let mut expected = LMap::new();
+                    for key in listed {
+                        expected.insert(key, Value::var());
+                    }
+                    let expected = Value::new(expected);
+                    return Ok(Box::new(move |state| state.unify(&map, &expected)));
+                }
+                Ok(None) => return Ok(Box::new(|_| None)),
+                Err(waiting) => waiting,
+            },
+            Value::Var(var) => LVarList::one(&var),
+        };
+        match state.resolve(&self.map) {
+            Value::Resolved(found) => {
+                let found: LVec<K> = found
+                    .sorted_entries(state)?
+                    .into_iter()
+                    .map(|(key, _)| key)
+                    .collect();
+                let found = Value::new(found);
+                Ok(Box::new(move |state| state.unify(&keys, &found)))
+            }
+            Value::Var(var) => Err(LVarList::flatten(
+                [waiting, LVarList::one(&var)].into_iter(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lmap::{keys, lmap};
+    use crate::{goal_vec, lvec, unify, LVar};
+
+    #[test]
+    fn lists_keys() {
+        let ks = LVar::new();
+        let goals = goal_vec![keys(lmap! {3 => 0, 1 => 0, 4 => 0, 2 => 0}, &ks)];
+        goals.assert_permutations_resolve_to(&ks, vec![vec![1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn matches_known_keys() {
+        let (m, ks, x) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            keys(&m, &ks),
+            unify(m, lmap! {4 => 0, 2 => 0, 3 => 0, 1 => 0}),
+            unify(ks, lvec![1, x, 3, 4]),
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+    }
+
+    #[test]
+    fn rejects_unsorted_or_repeated_keys() {
+        let m = LVar::new();
+        let goals = goal_vec![keys(&m, lvec![2, 1]), unify(&m, lmap! {1 => 0, 2 => 0})];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+        let goals = goal_vec![keys(&m, lvec![1, 1]), unify(&m, lmap! {1 => 0})];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+    }
+
+    #[test]
+    fn builds_map() {
+        let (m, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![keys(&m, lvec![1]), unify(x, 3), unify(&m, lmap! {1 => x})];
+        goals.assert_permutations_resolve_to(&x, vec![3]);
+        let goals = goal_vec![keys(&m, lvec![1, 2]), unify(&m, lmap! {1 => x})];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![
+            keys(&m, lvec![1, 2, 3]),
+            unify(m, lmap! {3 => 30, x => 10, 2 => 20})
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![1]);
+    }
+}
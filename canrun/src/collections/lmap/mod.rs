@@ -2,25 +2,72 @@
 //! [`LVar`](crate::LVar) keys and values.
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
 use std::rc::Rc;
 
+mod entries;
 mod get;
+mod insert;
+mod keys;
 mod subset;
 mod superset;
+mod values;
 
+pub use entries::entries;
 pub use get::get;
+pub use insert::{insert, remove};
+pub use keys::keys;
 pub use subset::subset;
 pub use superset::superset;
+pub use values::values;
+
+type Pair<K, V> = (Value<K>, Value<V>);
 
 /// A [`HashMap`](std::collections::HashMap)-like data structure with
 /// [`LVar`](crate::LVar) keys and values.
+///
+/// The entries are kept in a [`MapStore`], which defaults to a [`HashMap`].
+/// See [`LBTreeMap`](crate::lbtreemap::LBTreeMap) for one backed by a
+/// [`BTreeMap`](std::collections::BTreeMap) instead.
 #[derive(Clone, Default, Debug)]
-pub struct LMap<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
-    map: HashMap<Value<K>, Value<V>>,
+pub struct LMap<K: Unify + Debug, V: Unify + Debug, M = HashMap<Value<K>, Value<V>>> {
+    pub(crate) map: M,
+    entries: PhantomData<fn() -> (K, V)>,
 }
 
-impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> LMap<K, V> {
+/// The map that an [`LMap`] keeps its entries in.
+pub trait MapStore<K: Unify, V: Unify>: Clone + Default + Debug + 'static {
+    /// Insert an entry, returning the value previously stored under the key.
+    fn insert(&mut self, key: Value<K>, value: Value<V>) -> Option<Value<V>>;
+
+    /// The value stored under a key.
+    fn get(&self, key: &Value<K>) -> Option<&Value<V>>;
+
+    /// Iterate over the entries, in whatever order the store keeps them.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&Value<K>, &Value<V>)> + '_>;
+}
+
+impl<K, V, S> MapStore<K, V> for HashMap<Value<K>, Value<V>, S>
+where
+    K: Unify + Eq + Hash,
+    V: Unify,
+    S: BuildHasher + Clone + Default + 'static,
+{
+    fn insert(&mut self, key: Value<K>, value: Value<V>) -> Option<Value<V>> {
+        HashMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &Value<K>) -> Option<&Value<V>> {
+        HashMap::get(self, key)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&Value<K>, &Value<V>)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+impl<K: Unify + Debug, V: Unify + Debug, M: MapStore<K, V>> LMap<K, V, M> {
     /** Create a new [`LMap`] value.
 
     You may also be interested in the [`lmap!`] macro.
@@ -33,8 +80,13 @@ impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> LMap<K, V> {
     ```
     */
     pub fn new() -> Self {
+        LMap::from_store(M::default())
+    }
+
+    fn from_store(map: M) -> Self {
         LMap {
-            map: HashMap::new(),
+            map,
+            entries: PhantomData,
         }
     }
 
@@ -54,8 +106,8 @@ impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> LMap<K, V> {
 
     fn resolve_in(&self, state: State) -> Option<(State, Rc<Self>)> {
         let mut state = state;
-        let mut resolved: HashMap<Value<K>, Value<V>> = HashMap::new();
-        for (key, value) in &self.map {
+        let mut resolved = M::default();
+        for (key, value) in self.map.entries() {
             let resolved_key = state.resolve(key).clone();
             let resolved_value = state.resolve(value).clone();
             let existing = resolved.insert(resolved_key, resolved_value);
@@ -65,11 +117,54 @@ impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> LMap<K, V> {
                 state = state.unify(value, &existing_value)?;
             }
         }
-        Some((state, Rc::new(LMap { map: resolved })))
+        Some((state, Rc::new(LMap::from_store(resolved))))
     }
 }
 
-impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Unify for LMap<K, V> {
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> LMap<K, V> {
+    /// The entries of the map in ascending key order, or the variables that
+    /// still stand in for keys.
+    fn sorted_entries(&self, state: &State) -> Result<Vec<Pair<K, V>>, LVarList> {
+        let mut entries = Vec::with_capacity(self.map.len());
+        let mut unresolved = Vec::new();
+        for (key, value) in &self.map {
+            match state.resolve(key) {
+                Value::Resolved(key) => entries.push((Value::Resolved(key), value.clone())),
+                Value::Var(var) => unresolved.push(LVarList::one(&var)),
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(LVarList::flatten(unresolved.into_iter()));
+        }
+        entries.sort_by(|(a, _), (b, _)| a.resolved().cmp(&b.resolved()));
+        Ok(entries)
+    }
+}
+
+/// Resolve a list of keys, checking that they are in strictly ascending order.
+/// Returns `Ok(None)` if they are out of order or repeated.
+fn ascending<'a, K: Unify + Ord>(
+    keys: impl Iterator<Item = &'a Value<K>>,
+    state: &State,
+) -> Result<Option<Vec<Value<K>>>, LVarList> {
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    for key in keys {
+        match state.resolve(key) {
+            Value::Resolved(key) => resolved.push(Value::Resolved(key)),
+            Value::Var(var) => unresolved.push(LVarList::one(&var)),
+        }
+    }
+    if !unresolved.is_empty() {
+        return Err(LVarList::flatten(unresolved.into_iter()));
+    }
+    let in_order = resolved
+        .windows(2)
+        .all(|pair| pair[0].resolved() < pair[1].resolved());
+    Ok(in_order.then_some(resolved))
+}
+
+impl<K: Unify + Debug, V: Unify + Debug, M: MapStore<K, V>> Unify for LMap<K, V, M> {
     fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
         let (state, a) = a.resolve_in(state)?;
         let (state, b) = b.resolve_in(state)?;
@@ -79,12 +174,12 @@ impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Unify for LMap<K, V> {
     }
 }
 
-fn unify_entries<K: Unify + Eq + Hash + Debug, V: Unify + Debug>(
+fn unify_entries<K: Unify + Debug, V: Unify + Debug, M: MapStore<K, V>>(
     mut state: State,
-    a: &Rc<LMap<K, V>>,
-    b: &Rc<LMap<K, V>>,
+    a: &Rc<LMap<K, V, M>>,
+    b: &Rc<LMap<K, V, M>>,
 ) -> Option<State> {
-    for (a_key, a_value) in &a.map {
+    for (a_key, a_value) in a.map.entries() {
         // In the best case, all of the keys in `a` exist in both maps
         if let Some(b_value) = b.map.get(a_key) {
             // So we can unify directly and continue or bail
@@ -94,7 +189,8 @@ fn unify_entries<K: Unify + Eq + Hash + Debug, V: Unify + Debug>(
             // forking. The bad news is that this could blow up to a lot of
             // alternates if the map is large. The good news is that even if we
             // queue up a fork, any other matching keys that fail to unify will
-            // abort the whole state.
+            // abort the whole state. Candidates are tried in the order the
+            // backing store keeps them.
             state = state.fork(LMapFork {
                 a_key: a_key.clone(),
                 a_value: a_value.clone(),
@@ -106,25 +202,41 @@ fn unify_entries<K: Unify + Eq + Hash + Debug, V: Unify + Debug>(
 }
 
 #[derive(Debug)]
-struct LMapFork<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
+struct LMapFork<K: Unify + Debug, V: Unify + Debug, M> {
     a_key: Value<K>,
     a_value: Value<V>,
-    b_map: Rc<LMap<K, V>>,
+    b_map: Rc<LMap<K, V, M>>,
 }
 
-impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Fork for LMapFork<K, V> {
+impl<K: Unify + Debug, V: Unify + Debug, M: MapStore<K, V>> Fork for LMapFork<K, V, M> {
     fn fork(&self, state: &State) -> StateIter {
         let a_key = self.a_key.clone();
         let a_value = self.a_value.clone();
-        let b_map = self.b_map.map.clone();
+        let b_entries: Vec<Pair<K, V>> = self
+            .b_map
+            .map
+            .entries()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
         let state = state.clone();
-        Box::new(b_map.into_iter().filter_map(move |(b_key, b_value)| {
+        Box::new(b_entries.into_iter().filter_map(move |(b_key, b_value)| {
             state
                 .clone()
                 .unify(&a_key, &b_key)?
                 .unify(&a_value, &b_value)
         }))
     }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        let matches = self.b_map.map.entries().filter(|(b_key, b_value)| {
+            state
+                .clone()
+                .unify(&self.a_key, b_key)
+                .and_then(|s| s.unify(&self.a_value, b_value))
+                .is_some()
+        });
+        Some(matches.count())
+    }
 }
 
 impl<Kv, Kr, Vv, Vr> Reify for LMap<Kv, Vv>
@@ -135,7 +247,7 @@ where
 {
     type Reified = HashMap<Kr, Vr>;
     fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
-        let LMap { map } = self;
+        let map = &self.map;
         let init = HashMap::with_capacity(map.len());
         map.iter().try_fold(init, |mut map, (k, v)| {
             let key = state.reify(k)?;
@@ -164,7 +276,7 @@ where
 macro_rules! lmap {
     ($($key:expr => $value:expr),*) => {
         {
-            let mut map = $crate::lmap::LMap::new();
+            let mut map = $crate::lmap::LMap::<_, _>::new();
             $(map.insert($key, $value);)*
             map
         }
@@ -174,7 +286,7 @@ macro_rules! lmap {
 #[doc(inline)]
 pub use lmap;
 
-use crate::{Fork, ReadyState, Reify, State, StateIter, Unify, Value};
+use crate::{Fork, LVarList, ReadyState, Reify, State, StateIter, Unify, Value};
 
 #[cfg(test)]
 mod tests {
@@ -1,11 +1,11 @@
-use std::{fmt::Debug, hash::Hash};
+use std::fmt::Debug;
 
 use crate::{
     goals::{custom, project_2, Goal},
     Unify, Value,
 };
 
-use super::{unify_entries, LMap};
+use super::{unify_entries, LMap, MapStore};
 
 /** Assert that [`LMap`] `a` is a subset of [`LMap`] `b`.
 
@@ -23,10 +23,14 @@ let results: Vec<_> = goal.query(x).collect();
 assert_eq!(results, vec![1]);
 ```
 */
-pub fn subset<K, V>(a: impl Into<Value<LMap<K, V>>>, b: impl Into<Value<LMap<K, V>>>) -> impl Goal
+pub fn subset<K, V, M>(
+    a: impl Into<Value<LMap<K, V, M>>>,
+    b: impl Into<Value<LMap<K, V, M>>>,
+) -> impl Goal
 where
-    K: Unify + Eq + Hash + Debug,
+    K: Unify + Debug,
     V: Unify + Debug,
+    M: MapStore<K, V>,
 {
     project_2(a, b, |a, b| {
         Box::new(custom(move |state| unify_entries(state, &a, &b)))
@@ -1,8 +1,8 @@
-use std::{fmt::Debug, hash::Hash};
+use std::fmt::Debug;
 
 use crate::{goals::Goal, Unify, Value};
 
-use super::{subset, LMap};
+use super::{subset, LMap, MapStore};
 
 /**
 Assert that [`LMap`] `a` is a superset of [`LMap`] `b`.
@@ -21,10 +21,14 @@ let results: Vec<_> = goal.query(x).collect();
 assert_eq!(results, vec![1]);
 ```
 */
-pub fn superset<K, V>(a: impl Into<Value<LMap<K, V>>>, b: impl Into<Value<LMap<K, V>>>) -> impl Goal
+pub fn superset<K, V, M>(
+    a: impl Into<Value<LMap<K, V, M>>>,
+    b: impl Into<Value<LMap<K, V, M>>>,
+) -> impl Goal
 where
-    K: Unify + Eq + Hash + Debug,
+    K: Unify + Debug,
     V: Unify + Debug,
+    M: MapStore<K, V>,
 {
     subset(b, a)
 }
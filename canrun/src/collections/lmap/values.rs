@@ -0,0 +1,124 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use super::LMap;
+use crate::{
+    constraints::{resolve_1, Constraint, ResolveFn},
+    goals::Goal,
+    lvec::LVec,
+    LVarList, State, Unify, Value,
+};
+
+/** Assert that [`LVec`] `values` holds the values of [`LMap`] `map`.
+
+The values are listed in ascending order of their keys, matching the order of
+[`keys`](super::keys). This waits until the keys of `map` are known. The values
+may still be unresolved on either side, so a known `values` list can fill in
+the values of a map whose keys are known.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lmap::{lmap, values};
+
+let vs = LVar::new();
+let goal = values(lmap! {1 => "a"}, &vs);
+let results: Vec<_> = goal.query(vs).collect();
+assert_eq!(results, vec![vec!["a"]]);
+```
+*/
+pub fn values<K, V>(
+    map: impl Into<Value<LMap<K, V>>>,
+    values: impl Into<Value<LVec<V>>>,
+) -> impl Goal
+where
+    K: Unify + Eq + Hash + Ord + Debug,
+    V: Unify + Debug,
+{
+    Values {
+        map: map.into(),
+        values: values.into(),
+    }
+}
+
+#[derive(Debug)]
+struct Values<K: Unify + Eq + Hash + Debug, V: Unify + Debug> {
+    map: Value<LMap<K, V>>,
+    values: Value<LVec<V>>,
+}
+
+impl<K: Unify + Eq + Hash + Debug, V: Unify + Debug> Clone for Values<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Goal for Values<K, V> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<K: Unify + Eq + Hash + Ord + Debug, V: Unify + Debug> Constraint for Values<K, V> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let map = resolve_1(&self.map, state)?;
+        let found: LVec<V> = map
+            .sorted_entries(state)?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        let (values, found) = (self.values.clone(), Value::new(found));
+        Ok(Box::new(move |state| state.unify(&values, &found)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::lmap::{keys, lmap, values};
+    use crate::{all, goal_vec, lvec, lvec::zip, unify, LVar, Query, Value};
+
+    #[test]
+    fn waits_for_map() {
+        let (m, vs) = (LVar::new(), LVar::new());
+        let goals = goal_vec![values(&m, &vs), unify(m, lmap! {1 => 2})];
+        goals.assert_permutations_resolve_to(&vs, vec![vec![2]]);
+    }
+
+    #[test]
+    fn matches_known_values() {
+        let m = LVar::new();
+        let goals = goal_vec![
+            values(&m, lvec![10, 20, 30, 40]),
+            unify(&m, lmap! {3 => 30, 1 => 10, 4 => 40, 2 => 20}),
+        ];
+        goals.assert_permutations_resolve_to(
+            &m,
+            vec![HashMap::from([(1, 10), (2, 20), (3, 30), (4, 40)])],
+        );
+        let goals = goal_vec![
+            values(&m, lvec![40, 30, 20, 10]),
+            unify(&m, lmap! {3 => 30, 1 => 10, 4 => 40, 2 => 20}),
+        ];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+    }
+
+    #[test]
+    fn fills_in_values() {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![values(lmap! {3 => z, 1 => x, 2 => y}, lvec![10, 20, 30])];
+        goals.assert_permutations_resolve_to(&(x, y, z), vec![(10, 20, 30)]);
+    }
+
+    #[test]
+    fn lines_up_with_keys() {
+        let (ks, vs, pairs) = (LVar::new(), LVar::new(), LVar::new());
+        let map = Value::new(lmap! {3 => 30, 1 => 10, 4 => 40, 2 => 20});
+        let goal = all![keys(&map, &ks), values(&map, &vs), zip(ks, vs, &pairs)];
+        let results: Vec<Vec<(i32, i32)>> = goal.query(pairs).collect();
+        assert_eq!(results, vec![vec![(1, 10), (2, 20), (3, 30), (4, 40)]]);
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{
+    goals::{not, project_2, unify, All, Goal},
+    Unify, Value,
+};
+
+use super::LSet;
+
+/** Assert that no element of [`LSet`] `a` can unify with an element of
+[`LSet`] `b`.
+
+Each pair of elements is checked with [`not`](crate::goals::not()), so
+unresolved elements will be re-checked as they become known.
+
+# Example:
+```
+use canrun::{LVar, Query, all, any, unify};
+use canrun::lset::{lset, disjoint};
+
+let x = LVar::new();
+let goal = all![
+    any![unify(x, 1), unify(x, 2)],
+    disjoint(lset! {x}, lset! {1, 3}),
+];
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![2]);
+```
+*/
+pub fn disjoint<T>(a: impl Into<Value<LSet<T>>>, b: impl Into<Value<LSet<T>>>) -> impl Goal
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    project_2(a, b, |a, b| {
+        let goals: All = a
+            .set
+            .iter()
+            .flat_map(|a_item| {
+                b.set.iter().map(move |b_item| {
+                    Box::new(not(unify(a_item.clone(), b_item.clone()))) as Box<dyn Goal>
+                })
+            })
+            .collect();
+        Box::new(goals)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lset::{disjoint, lset};
+    use crate::{LVar, Query};
+
+    #[test]
+    fn succeeds() {
+        let goal = disjoint(lset! {1, 2}, lset! {3, 4});
+        assert_eq!(goal.query(1).count(), 1);
+    }
+
+    #[test]
+    fn fails_on_overlap() {
+        let x: LVar<i32> = LVar::new();
+        let goal = disjoint(lset! {1, 2}, lset! {2, 3});
+        assert_eq!(goal.query(x).count(), 0);
+    }
+}
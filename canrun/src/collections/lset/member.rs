@@ -0,0 +1,59 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+use crate::{
+    goals::{project_1, unify, Any, Goal},
+    Unify, Value,
+};
+
+use super::LSet;
+
+/** Assert that `item` unifies with an element of [`LSet`] `set`.
+
+This will fork the state for each element that could match.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lset::{lset, member};
+
+let x = LVar::new();
+let goal = member(x, lset! {1, 2});
+let mut results: Vec<_> = goal.query(x).collect();
+results.sort();
+assert_eq!(results, vec![1, 2]);
+```
+*/
+pub fn member<T>(item: impl Into<Value<T>>, set: impl Into<Value<LSet<T>>>) -> impl Goal
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    let item = item.into();
+    project_1(set, move |set| {
+        let any: Any = set
+            .set
+            .iter()
+            .map(|element| Rc::new(unify(item.clone(), element.clone())) as Rc<dyn Goal>)
+            .collect();
+        Box::new(any)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lset::{lset, member};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn finds_known_item() {
+        let s = LVar::new();
+        let goals = goal_vec![member(2, &s), unify(&s, lset! {1, 2, 3})];
+        goals.assert_permutations_resolve_to(&s, vec![[1, 2, 3].into()]);
+    }
+
+    #[test]
+    fn fails_on_missing_item() {
+        let s = LVar::new();
+        let goals = goal_vec![member(4, &s), unify(&s, lset! {1, 2, 3})];
+        goals.assert_permutations_resolve_to(&s, vec![]);
+    }
+}
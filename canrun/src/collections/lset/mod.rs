@@ -0,0 +1,251 @@
+//! A [`HashSet`](std::collections::HashSet)-like data structure with
+//! [`Value`](crate::Value) elements.
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::{Fork, ReadyState, Reify, State, StateIter, Unify, Value};
+
+mod disjoint;
+mod member;
+mod subset;
+mod union;
+
+pub use disjoint::disjoint;
+pub use member::member;
+pub use subset::subset;
+pub use union::union;
+
+/// A [`HashSet`](std::collections::HashSet)-like data structure with
+/// [`Value`](crate::Value) elements.
+///
+/// Unlike [`LVec`](crate::lvec::LVec), unification does not depend on the
+/// order of the elements. Elements that are still unresolved variables may
+/// match any element of the other set, so unifying two sets will fork the
+/// state for every possible match.
+///
+/// Like a [`HashSet`], the elements have no defined order. That includes the
+/// order in which those forks (and the forks made by [`member`],
+/// [`subset`] and the other set goals) yield their results, which may differ
+/// between runs. Sort the results, or reify through [`Ordered`], when the
+/// order matters.
+#[derive(Clone, Default, Debug)]
+pub struct LSet<T: Unify + Eq + Hash + Debug> {
+    set: HashSet<Value<T>>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> LSet<T> {
+    /** Create a new [`LSet`] value.
+
+    You may also be interested in the [`lset!`] macro.
+
+    # Example:
+    ```
+    use canrun::lset::LSet;
+
+    let set: LSet<i32> = LSet::new();
+    ```
+    */
+    pub fn new() -> Self {
+        LSet {
+            set: HashSet::new(),
+        }
+    }
+
+    /** Add an element to an existing [`LSet`].
+
+    # Example:
+    ```
+    use canrun::lset::LSet;
+
+    let mut set: LSet<i32> = LSet::new();
+    set.insert(1);
+    ```
+    */
+    pub fn insert(&mut self, item: impl Into<Value<T>>) {
+        self.set.insert(item.into());
+    }
+
+    /// Returns the number of elements in the [`LSet`]. Note that unresolved
+    /// elements may later turn out to be duplicates.
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns true if the [`LSet`] contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Replace every element with its current value in the state, merging any
+    /// that turn out to be identical.
+    fn resolve_in(&self, state: &State) -> Rc<Self> {
+        let set = self.set.iter().map(|item| state.resolve(item)).collect();
+        Rc::new(LSet { set })
+    }
+}
+
+impl<T: Unify + Eq + Hash + Debug> Unify for LSet<T> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        let (a, b) = (a.resolve_in(&state), b.resolve_in(&state));
+        let state = unify_elements(state, &a, &b)?;
+        unify_elements(state, &b, &a)
+    }
+}
+
+/// Ensure every element of `a` is matched by some element of `b`.
+fn unify_elements<T: Unify + Eq + Hash + Debug>(
+    mut state: State,
+    a: &Rc<LSet<T>>,
+    b: &Rc<LSet<T>>,
+) -> Option<State> {
+    for a_item in &a.set {
+        if b.set.contains(a_item) {
+            continue;
+        }
+        // Like `LMap` keys, an element that isn't found directly could still
+        // match any element of the other set.
+        state = state.fork(LSetFork {
+            a_item: a_item.clone(),
+            b_set: b.clone(),
+        })?;
+    }
+    Some(state)
+}
+
+#[derive(Debug)]
+struct LSetFork<T: Unify + Eq + Hash + Debug> {
+    a_item: Value<T>,
+    b_set: Rc<LSet<T>>,
+}
+
+impl<T: Unify + Eq + Hash + Debug> Fork for LSetFork<T> {
+    fn fork(&self, state: &State) -> StateIter {
+        let a_item = self.a_item.clone();
+        let b_set = self.b_set.set.clone();
+        let state = state.clone();
+        Box::new(
+            b_set
+                .into_iter()
+                .filter_map(move |b_item| state.clone().unify(&a_item, &b_item)),
+        )
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        let matches = self
+            .b_set
+            .set
+            .iter()
+            .filter(|b_item| state.clone().unify(&self.a_item, b_item).is_some());
+        Some(matches.count())
+    }
+}
+
+impl<T, R> Reify for LSet<T>
+where
+    T: Unify + Eq + Hash + Debug + Reify<Reified = R>,
+    R: Eq + Hash,
+{
+    type Reified = HashSet<R>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        self.set.iter().map(|item| state.reify(item)).collect()
+    }
+}
+
+/** Reify a set-like value into a [`BTreeSet`] instead of a
+[`HashSet`](std::collections::HashSet).
+
+# Example:
+```
+use canrun::{unify, LVar, Query};
+use canrun::lset::{lset, Ordered};
+
+let s = LVar::new();
+let goal = unify(&s, lset! {3, 1, 2});
+let results: Vec<_> = goal.query(Ordered(s)).collect();
+assert_eq!(results, vec![[1, 2, 3].into()]);
+```
+*/
+#[derive(Clone, Debug)]
+pub struct Ordered<Q>(pub Q);
+
+impl<Q, R> Reify for Ordered<Q>
+where
+    Q: Reify<Reified = HashSet<R>>,
+    R: Ord,
+{
+    type Reified = BTreeSet<R>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        Some(self.0.reify_in(state)?.into_iter().collect())
+    }
+}
+
+/// Create an [`LSet`](crate::lset::LSet) with automatic `Into<Value<T>>`
+/// wrapping.
+///
+/// The primary benefit is that it allows freely mixing resolved values and
+/// [`LVar`s](crate::LVar).
+///
+/// # Example:
+/// ```
+/// use canrun::LVar;
+/// use canrun::lset::{lset, LSet};
+///
+/// let x = LVar::new();
+/// let set: LSet<i32> = lset!{x, 1, 2};
+/// ```
+#[macro_export]
+macro_rules! lset {
+    ($($item:expr),* $(,)?) => {
+        {
+            let mut set = $crate::lset::LSet::new();
+            $(set.insert($item);)*
+            set
+        }
+    };
+}
+
+#[doc(inline)]
+pub use lset;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{goal_vec, unify, LVar, Query, StateIterator};
+
+    #[test]
+    fn ignores_order() {
+        let goal = unify(lset! {1, 2, 3}, lset! {3, 1, 2});
+        assert_eq!(goal.into_states().count(), 1);
+    }
+
+    #[test]
+    fn fails_with_different() {
+        let goal = unify(lset! {1, 2}, lset! {1, 3});
+        assert_eq!(goal.into_states().count(), 0);
+    }
+
+    #[test]
+    fn forks_over_variables() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(lset! {1, x, y}, lset! {1, 2, 3})];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(2, 3), (3, 2)]);
+    }
+
+    #[test]
+    fn merges_duplicates() {
+        let (s, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(&s, lset! {x, 1}), unify(&s, lset! {1})];
+        goals.assert_permutations_resolve_to(&(s, x), vec![(HashSet::from([1]), 1)]);
+    }
+
+    #[test]
+    fn reifies() {
+        let s = LVar::new();
+        let goal = unify(&s, lset! {1, 2, 2});
+        let results: Vec<_> = goal.query(s).collect();
+        assert_eq!(results, vec![HashSet::from([1, 2])]);
+    }
+}
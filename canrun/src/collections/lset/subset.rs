@@ -0,0 +1,54 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{
+    goals::{custom, project_2, Goal},
+    Unify, Value,
+};
+
+use super::{unify_elements, LSet};
+
+/** Assert that every element of [`LSet`] `a` unifies with an element of
+[`LSet`] `b`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lset::{lset, subset};
+
+let x = LVar::new();
+let goal = subset(lset! {x, 2}, lset! {2, 3});
+let mut results: Vec<_> = goal.query(x).collect();
+results.sort();
+assert_eq!(results, vec![2, 3]);
+```
+*/
+pub fn subset<T>(a: impl Into<Value<LSet<T>>>, b: impl Into<Value<LSet<T>>>) -> impl Goal
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    project_2(a, b, |a, b| {
+        Box::new(custom(move |state| {
+            let (a, b) = (a.resolve_in(&state), b.resolve_in(&state));
+            unify_elements(state, &a, &b)
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lset::{lset, subset};
+    use crate::{LVar, Query};
+
+    #[test]
+    fn succeeds() {
+        let goal = subset(lset! {1, 3}, lset! {1, 2, 3});
+        assert_eq!(goal.query(1).count(), 1);
+    }
+
+    #[test]
+    fn fails() {
+        let x: LVar<i32> = LVar::new();
+        let goal = subset(lset! {1, 4}, lset! {1, 2, 3});
+        assert_eq!(goal.query(x).count(), 0);
+    }
+}
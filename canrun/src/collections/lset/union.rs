@@ -0,0 +1,62 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{
+    goals::{project_2, unify, Goal},
+    Unify, Value,
+};
+
+use super::LSet;
+
+/** Assert that [`LSet`] `ab` contains exactly the elements of `a` and `b`.
+
+This waits until both `a` and `b` are resolved, at which point their union is
+unified with `ab`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::lset::{lset, union, Ordered};
+
+let ab = LVar::new();
+let goal = union(lset! {1, 2}, lset! {2, 3}, &ab);
+let results: Vec<_> = goal.query(Ordered(ab)).collect();
+assert_eq!(results, vec![[1, 2, 3].into()]);
+```
+*/
+pub fn union<T>(
+    a: impl Into<Value<LSet<T>>>,
+    b: impl Into<Value<LSet<T>>>,
+    ab: impl Into<Value<LSet<T>>>,
+) -> impl Goal
+where
+    T: Unify + Eq + Hash + Debug,
+{
+    let ab = ab.into();
+    project_2(a, b, move |a, b| {
+        let set = a.set.union(&b.set).cloned().collect();
+        Box::new(unify(ab.clone(), LSet { set }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lset::{lset, union};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn binds_variables() {
+        let (x, a) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            union(&a, lset! {3}, lset! {1, 2, 3}),
+            unify(a, lset! {1, x}),
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+    }
+
+    #[test]
+    fn fails_with_extra() {
+        let ab = LVar::new();
+        let goals = goal_vec![union(lset! {1}, lset! {2}, &ab), unify(&ab, lset! {1})];
+        goals.assert_permutations_resolve_to(&ab, vec![]);
+    }
+}
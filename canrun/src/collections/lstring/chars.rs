@@ -0,0 +1,116 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::goals::Goal;
+use crate::lvec::LVec;
+use crate::{LVarList, State, Value};
+use std::rc::Rc;
+
+/**
+Assert that string `s` is made up of the `char`s in [`LVec`] `chars`.
+
+Once `s` is known it will be split into `chars`. Once `chars` and all of its
+items are known they will be collected into `s`.
+
+# Examples:
+```
+use canrun::{LVar, lstring, Query};
+
+let cs = LVar::new();
+let goal = lstring::chars("hi".to_string(), &cs);
+let results: Vec<_> = goal.query(cs).collect();
+assert_eq!(results, vec![vec!['h', 'i']]);
+```
+
+```
+# use canrun::{LVar, lstring, lvec, Query};
+let s = LVar::new();
+let goal = lstring::chars(&s, lvec!['h', 'i']);
+let results: Vec<_> = goal.query(s).collect();
+assert_eq!(results, vec!["hi"]);
+```
+*/
+pub fn chars(s: impl Into<Value<String>>, chars: impl Into<Value<LVec<char>>>) -> Chars {
+    Chars {
+        s: s.into(),
+        chars: chars.into(),
+    }
+}
+
+/**
+Assert that string `s` is made up of the `char`s in [`LVec`] `chars`. Create
+with [`chars`].
+*/
+#[derive(Debug, Clone)]
+pub struct Chars {
+    s: Value<String>,
+    chars: Value<LVec<char>>,
+}
+
+impl Goal for Chars {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl Constraint for Chars {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let chars = match state.resolve(&self.s) {
+            Value::Resolved(s) => {
+                let split: LVec<char> = s.chars().collect();
+                let chars = self.chars.clone();
+                return Ok(Box::new(move |state| {
+                    state.unify(&chars, &Value::new(split))
+                }));
+            }
+            Value::Var(s_var) => match state.resolve(&self.chars) {
+                Value::Resolved(chars) => chars,
+                Value::Var(chars_var) => {
+                    return Err(LVarList::flatten(
+                        [LVarList::one(&s_var), LVarList::one(&chars_var)].into_iter(),
+                    ));
+                }
+            },
+        };
+        let mut joined = String::with_capacity(chars.len());
+        let mut vars = vec![];
+        for item in chars.as_slice() {
+            match state.resolve(item) {
+                Value::Resolved(c) => joined.push(*c),
+                Value::Var(var) => vars.push(LVarList::one(&var)),
+            }
+        }
+        if vars.is_empty() {
+            let s = self.s.clone();
+            Ok(Box::new(move |state| state.unify(&s, &Value::new(joined))))
+        } else {
+            Err(LVarList::flatten(vars.into_iter()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, lvec, LVar};
+
+    #[test]
+    fn splits() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![lstring::chars("ab".to_string(), lvec![x, y])];
+        goals.assert_permutations_resolve_to(&(x, y), vec![('a', 'b')]);
+    }
+
+    #[test]
+    fn joins_once_items_are_known() {
+        let (s, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![lstring::chars(&s, lvec!['a', x]), unify(x, 'b')];
+        goals.assert_permutations_resolve_to(&s, vec!["ab".into()]);
+    }
+
+    #[test]
+    fn fails_on_length_mismatch() {
+        let x = LVar::new();
+        let goals = goal_vec![lstring::chars("abc".to_string(), lvec!['a', x])];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::goals::{both, unify, Any, Goal};
+use crate::{LVarList, State, Value};
+use std::rc::Rc;
+
+/**
+Assert that string `ab` is string `a` followed by string `b`.
+
+This works in any direction. Once any two of the three strings are known the
+third is derived directly, and if only `ab` is known this goal will fork the
+state for each way it can be split in two.
+
+# Examples:
+```
+use canrun::{LVar, lstring, Query};
+
+let ab = LVar::new();
+let goal = lstring::concat("foo".to_string(), "bar".to_string(), &ab);
+let results: Vec<_> = goal.query(ab).collect();
+assert_eq!(results, vec!["foobar".to_string()]);
+```
+
+```
+# use canrun::{LVar, lstring, Query};
+let (a, b) = (LVar::new(), LVar::new());
+let goal = lstring::concat(&a, &b, "ab".to_string());
+let results: Vec<(String, String)> = goal.query((a, b)).collect();
+assert_eq!(results, vec![
+    ("".into(), "ab".into()),
+    ("a".into(), "b".into()),
+    ("ab".into(), "".into()),
+]);
+```
+*/
+pub fn concat(
+    a: impl Into<Value<String>>,
+    b: impl Into<Value<String>>,
+    ab: impl Into<Value<String>>,
+) -> Concat {
+    Concat {
+        a: a.into(),
+        b: b.into(),
+        ab: ab.into(),
+    }
+}
+
+/**
+Assert that string `ab` is string `a` followed by string `b`. Create with
+[`concat()`].
+*/
+#[derive(Debug, Clone)]
+pub struct Concat {
+    a: Value<String>,
+    b: Value<String>,
+    ab: Value<String>,
+}
+
+impl Goal for Concat {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl Constraint for Concat {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let a = state.resolve(&self.a);
+        let b = state.resolve(&self.b);
+        let ab = state.resolve(&self.ab);
+
+        // Split `ab` at byte `index`, unifying the two halves with `a` and `b`.
+        let split = |ab: &str, index: usize| {
+            let (prefix, suffix) = ab.split_at(index);
+            both(
+                unify(self.a.clone(), prefix.to_string()),
+                unify(self.b.clone(), suffix.to_string()),
+            )
+        };
+
+        match (a, b, ab) {
+            (Value::Resolved(a), Value::Resolved(b), ab) => {
+                let joined = format!("{a}{b}");
+                Ok(Box::new(move |state| state.unify(&ab, &Value::new(joined))))
+            }
+            (Value::Resolved(a), _, Value::Resolved(ab)) => {
+                if !ab.starts_with(a.as_str()) {
+                    return Ok(Box::new(|_| None));
+                }
+                let goal = split(&ab, a.len());
+                Ok(Box::new(move |state| goal.apply(state)))
+            }
+            (_, Value::Resolved(b), Value::Resolved(ab)) => {
+                if !ab.ends_with(b.as_str()) {
+                    return Ok(Box::new(|_| None));
+                }
+                let goal = split(&ab, ab.len() - b.len());
+                Ok(Box::new(move |state| goal.apply(state)))
+            }
+            (Value::Var(_), Value::Var(_), Value::Resolved(ab)) => {
+                let boundaries = ab.char_indices().map(|(index, _)| index);
+                let goals: Any = boundaries
+                    .chain(std::iter::once(ab.len()))
+                    .map(|index| Rc::new(split(&ab, index)) as Rc<dyn Goal>)
+                    .collect();
+                Ok(Box::new(move |state| goals.apply(state)))
+            }
+            (a, b, ab) => {
+                let vars = [a, b, ab].into_iter().filter_map(|value| match value {
+                    Value::Var(var) => Some(LVarList::one(&var)),
+                    Value::Resolved(_) => None,
+                });
+                Err(LVarList::flatten(vars))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, LVar};
+
+    #[test]
+    fn joins() {
+        let ab = LVar::new();
+        let goals = goal_vec![lstring::concat("a".to_string(), "bc".to_string(), &ab)];
+        goals.assert_permutations_resolve_to(&ab, vec!["abc".to_string()]);
+    }
+
+    #[test]
+    fn finds_prefix_and_suffix() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lstring::concat(&a, "c".to_string(), "abc".to_string()),
+            lstring::concat("a".to_string(), &b, "abc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&(a, b), vec![("ab".into(), "bc".into())]);
+    }
+
+    #[test]
+    fn enumerates_splits_on_char_boundaries() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![lstring::concat(&a, &b, "åb".to_string())];
+        goals.assert_permutations_resolve_to(
+            &(a, b),
+            vec![
+                (String::new(), "åb".into()),
+                ("å".into(), "b".into()),
+                ("åb".into(), String::new()),
+            ],
+        );
+    }
+
+    #[test]
+    fn waits_for_two_values() {
+        let (a, b, ab) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lstring::concat(&a, &b, &ab),
+            unify(a, "x".to_string()),
+            unify(ab, "xy".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&b, vec!["y".into()]);
+    }
+
+    #[test]
+    fn fails_on_mismatch() {
+        let b = LVar::new();
+        let goals = goal_vec![lstring::concat("x".to_string(), &b, "yz".to_string())];
+        goals.assert_permutations_resolve_to(&b, vec![]);
+        let goals = goal_vec![lstring::concat(&b, "x".to_string(), "yz".to_string())];
+        goals.assert_permutations_resolve_to(&b, vec![]);
+    }
+}
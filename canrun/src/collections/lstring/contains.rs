@@ -0,0 +1,120 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::goals::{unify, Any, Goal};
+use crate::{LVarList, State, Value};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/**
+Assert that string `s` contains string `sub`.
+
+String `s` must be resolved. If `sub` is not known, this goal will fork the
+state once for each distinct substring of `s`, including the empty string.
+
+# Example:
+```
+use canrun::{LVar, lstring, Query};
+
+let sub = LVar::new();
+let goal = lstring::contains("aa".to_string(), &sub);
+let results: Vec<_> = goal.query(sub).collect();
+assert_eq!(results, vec!["", "a", "aa"]);
+```
+*/
+pub fn contains(s: impl Into<Value<String>>, sub: impl Into<Value<String>>) -> Contains {
+    Contains {
+        s: s.into(),
+        sub: sub.into(),
+    }
+}
+
+/**
+Assert that string `s` contains string `sub`. Create with [`contains`].
+*/
+#[derive(Debug, Clone)]
+pub struct Contains {
+    s: Value<String>,
+    sub: Value<String>,
+}
+
+impl Goal for Contains {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl Constraint for Contains {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let s = match state.resolve(&self.s) {
+            Value::Resolved(s) => s,
+            Value::Var(var) => return Err(LVarList::one(&var)),
+        };
+        match state.resolve(&self.sub) {
+            Value::Resolved(sub) => {
+                let found = s.contains(sub.as_str());
+                Ok(Box::new(
+                    move |state| if found { Some(state) } else { None },
+                ))
+            }
+            Value::Var(_) => {
+                let mut boundaries: Vec<_> = s.char_indices().map(|(index, _)| index).collect();
+                boundaries.push(s.len());
+                let s = s.as_str();
+                let mut seen = HashSet::new();
+                let substrings = boundaries
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &start)| boundaries[i..].iter().map(move |&end| &s[start..end]));
+                let goals: Any = substrings
+                    .filter(|sub| seen.insert(*sub))
+                    .map(|sub| Rc::new(unify(self.sub.clone(), sub.to_string())) as Rc<dyn Goal>)
+                    .collect();
+                Ok(Box::new(move |state| goals.apply(state)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (s, sub) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lstring::contains(&s, &sub),
+            unify(&s, "abc".to_string()),
+            unify(sub, "bc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec!["abc".into()]);
+    }
+
+    #[test]
+    fn fails() {
+        let s = LVar::new();
+        let goals = goal_vec![
+            lstring::contains(&s, "ac".to_string()),
+            unify(&s, "abc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec![]);
+    }
+
+    #[test]
+    fn enumerates_distinct_substrings() {
+        let sub = LVar::new();
+        let goals = goal_vec![lstring::contains("aba".to_string(), &sub)];
+        goals.assert_permutations_resolve_to(
+            &sub,
+            vec![
+                String::new(),
+                "a".into(),
+                "ab".into(),
+                "aba".into(),
+                "b".into(),
+                "ba".into(),
+            ],
+        );
+    }
+}
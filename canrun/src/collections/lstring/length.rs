@@ -0,0 +1,39 @@
+use crate::goals::{project_1, unify, Project1};
+use crate::Value;
+
+/**
+Assert that string `s` is `len` `char`s long.
+
+There are endless strings of any given length, so this goal waits until `s`
+is resolved.
+
+# Example:
+```
+use canrun::{LVar, lstring, Query};
+
+let len = LVar::new();
+let goal = lstring::length("héllo".to_string(), len);
+let results: Vec<_> = goal.query(len).collect();
+assert_eq!(results, vec![5]);
+```
+*/
+pub fn length(s: impl Into<Value<String>>, len: impl Into<Value<usize>>) -> Project1<String> {
+    let len = len.into();
+    project_1(s, move |s| Box::new(unify(len.clone(), s.chars().count())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, LVar};
+
+    #[test]
+    fn counts_chars() {
+        let s = LVar::new();
+        let goals = goal_vec![lstring::length(&s, 2), unify(&s, "ßa".to_string())];
+        goals.assert_permutations_resolve_to(&s, vec!["ßa".into()]);
+        let goals = goal_vec![lstring::length(&s, 4), unify(&s, "ßa".to_string())];
+        goals.assert_permutations_resolve_to(&s, vec![]);
+    }
+}
@@ -0,0 +1,24 @@
+/*! Relational operations on [`String`] values.
+
+These goals work on plain [`Value<String>`](crate::Value)s, so they can be
+freely mixed with [`unify`](crate::goals::unify) and the other goals. Most of
+them work in more than one direction, which makes it possible to write text
+processing rules that can both parse and generate strings. Splits are always
+made on `char` boundaries.
+*/
+
+mod chars;
+mod concat;
+mod contains;
+mod length;
+mod prefix;
+mod suffix;
+mod to_upper;
+
+pub use chars::{chars, Chars};
+pub use concat::{concat, Concat};
+pub use contains::{contains, Contains};
+pub use length::length;
+pub use prefix::prefix;
+pub use suffix::suffix;
+pub use to_upper::to_upper;
@@ -0,0 +1,49 @@
+use super::{concat, Concat};
+use crate::{LVar, Value};
+
+/**
+Assert that string `s` starts with string `prefix`.
+
+If only `s` is known, this goal will fork the state for every prefix of `s`,
+from the empty string up to `s` itself.
+
+# Example:
+```
+use canrun::{LVar, lstring, Query};
+
+let p = LVar::new();
+let goal = lstring::prefix(&p, "ab".to_string());
+let results: Vec<_> = goal.query(p).collect();
+assert_eq!(results, vec!["", "a", "ab"]);
+```
+*/
+pub fn prefix(prefix: impl Into<Value<String>>, s: impl Into<Value<String>>) -> Concat {
+    concat(prefix, LVar::new(), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, LVar};
+
+    #[test]
+    fn succeeds() {
+        let s = LVar::new();
+        let goals = goal_vec![
+            lstring::prefix("ab".to_string(), &s),
+            unify(&s, "abc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec!["abc".into()]);
+    }
+
+    #[test]
+    fn fails() {
+        let s = LVar::new();
+        let goals = goal_vec![
+            lstring::prefix("bc".to_string(), &s),
+            unify(&s, "abc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec![]);
+    }
+}
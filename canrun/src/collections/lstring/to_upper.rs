@@ -0,0 +1,49 @@
+use crate::goals::{project_1, unify, Project1};
+use crate::Value;
+
+/**
+Assert that string `upper` is the uppercase form of string `s`.
+
+Uppercasing can't be reversed, so this goal waits until `s` is resolved.
+
+# Example:
+```
+use canrun::{LVar, lstring, Query};
+
+let upper = LVar::new();
+let goal = lstring::to_upper("Hi".to_string(), &upper);
+let results: Vec<_> = goal.query(upper).collect();
+assert_eq!(results, vec!["HI"]);
+```
+*/
+pub fn to_upper(s: impl Into<Value<String>>, upper: impl Into<Value<String>>) -> Project1<String> {
+    let upper = upper.into();
+    project_1(s, move |s| Box::new(unify(upper.clone(), s.to_uppercase())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lstring, LVar};
+
+    #[test]
+    fn succeeds() {
+        let s = LVar::new();
+        let goals = goal_vec![
+            lstring::to_upper(&s, "STRASSE".to_string()),
+            unify(&s, "straße".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec!["straße".into()]);
+    }
+
+    #[test]
+    fn fails() {
+        let s = LVar::new();
+        let goals = goal_vec![
+            lstring::to_upper(&s, "abc".to_string()),
+            unify(&s, "abc".to_string()),
+        ];
+        goals.assert_permutations_resolve_to(&s, vec![]);
+    }
+}
@@ -54,6 +54,13 @@ impl_unify_tuple!(Av => Ar, Bv => Br);
 impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr);
 impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr);
 impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr, Kv => Kr);
+impl_unify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr, Kv => Kr, Lv => Lr);
 
 macro_rules! impl_reify_tuple {
     ($($t:ident => $r:ident),+) => {
@@ -73,3 +80,29 @@ impl_reify_tuple!(Av => Ar, Bv => Br);
 impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr);
 impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr);
 impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr, Kv => Kr);
+impl_reify_tuple!(Av => Ar, Bv => Br, Cv => Cr, Dv => Dr, Ev => Er, Fv => Fr, Gv => Gr, Hv => Hr, Iv => Ir, Jv => Jr, Kv => Kr, Lv => Lr);
+
+#[cfg(test)]
+mod tests {
+    use crate::{goals::unify, LVar, Query};
+
+    #[test]
+    fn twelve_tuple() {
+        let x = LVar::new();
+        let goal = unify(
+            ltup!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, x),
+            ltup!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12),
+        );
+        let results: Vec<_> = goal.query((x, x, x, x, x, x, x, x, x, x, x, x)).collect();
+        assert_eq!(
+            results,
+            vec![(12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12)]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::goals::{both, unify, Any, Goal};
+use crate::lvec::LVec;
+use crate::{LVarList, State, Unify, Value};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/**
+Assert that [`LVec`] `ab` is [`LVec`] `a` followed by [`LVec`] `b`.
+
+This works in any direction. Once any two of the three vectors are known the
+third is derived directly, and if only `ab` is known this goal will fork the
+state for each way it can be split in two. The items of each vector don't
+need to be resolved, only the vectors themselves.
+
+# Examples:
+```
+use canrun::{LVar, lvec, Query};
+
+let ab = LVar::new();
+let goal = lvec::append(lvec![1, 2], lvec![3], &ab);
+let results: Vec<_> = goal.query(ab).collect();
+assert_eq!(results, vec![vec![1, 2, 3]]);
+```
+
+```
+# use canrun::{LVar, lvec, Query};
+let a = LVar::new();
+let b = LVar::new();
+let goal = lvec::append(&a, &b, lvec![1, 2]);
+let results: Vec<_> = goal.query((a, b)).collect();
+assert_eq!(results, vec![
+    (vec![], vec![1, 2]),
+    (vec![1], vec![2]),
+    (vec![1, 2], vec![]),
+]);
+```
+*/
+pub fn append<T>(
+    a: impl Into<Value<LVec<T>>>,
+    b: impl Into<Value<LVec<T>>>,
+    ab: impl Into<Value<LVec<T>>>,
+) -> Append<T>
+where
+    T: Unify,
+    LVec<T>: Unify,
+{
+    Append {
+        a: a.into(),
+        b: b.into(),
+        ab: ab.into(),
+    }
+}
+
+/**
+Assert that [`LVec`] `ab` is [`LVec`] `a` followed by [`LVec`] `b`. Create with
+[`append`].
+*/
+#[derive(Debug)]
+pub struct Append<T: Unify> {
+    a: Value<LVec<T>>,
+    b: Value<LVec<T>>,
+    ab: Value<LVec<T>>,
+}
+
+impl<T: Unify> Clone for Append<T> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            ab: self.ab.clone(),
+        }
+    }
+}
+
+impl<T: Unify> Goal for Append<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify> Constraint for Append<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let a = state.resolve(&self.a);
+        let b = state.resolve(&self.b);
+        let ab = state.resolve(&self.ab);
+
+        // Split `ab` at `index`, unifying the two halves with `a` and `b`.
+        let split = |ab: &LVec<T>, index: usize| {
+            let prefix: LVec<T> = ab.vec[..index].into();
+            let suffix: LVec<T> = ab.vec[index..].into();
+            both(unify(self.a.clone(), prefix), unify(self.b.clone(), suffix))
+        };
+
+        match (a, b, ab) {
+            (Value::Resolved(a), Value::Resolved(b), ab) => {
+                let joined: LVec<T> = a.vec.iter().chain(b.vec.iter()).cloned().collect();
+                Ok(Box::new(move |state| state.unify(&ab, &Value::new(joined))))
+            }
+            (Value::Resolved(a), _, Value::Resolved(ab)) => {
+                if a.len() > ab.len() {
+                    return Ok(Box::new(|_| None));
+                }
+                let goal = split(&ab, a.len());
+                Ok(Box::new(move |state| goal.apply(state)))
+            }
+            (_, Value::Resolved(b), Value::Resolved(ab)) => {
+                let Some(index) = ab.len().checked_sub(b.len()) else {
+                    return Ok(Box::new(|_| None));
+                };
+                let goal = split(&ab, index);
+                Ok(Box::new(move |state| goal.apply(state)))
+            }
+            (Value::Var(_), Value::Var(_), Value::Resolved(ab)) => {
+                let goals: Any = (0..=ab.len())
+                    .map(|index| Rc::new(split(&ab, index)) as Rc<dyn Goal>)
+                    .collect();
+                Ok(Box::new(move |state| goals.apply(state)))
+            }
+            (a, b, ab) => {
+                let vars = [a, b, ab].into_iter().filter_map(|value| match value {
+                    Value::Var(var) => Some(LVarList::one(&var)),
+                    Value::Resolved(_) => None,
+                });
+                Err(LVarList::flatten(vars))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::goal_vec;
+    use crate::goals::unify;
+    use crate::{lvec, LVar};
+
+    #[test]
+    fn joins() {
+        let ab = LVar::new();
+        let goals = goal_vec![lvec::append(lvec![1], lvec![2, 3], &ab)];
+        goals.assert_permutations_resolve_to(&ab, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn finds_prefix_and_suffix() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lvec::append(&a, lvec![3], lvec![1, 2, 3]),
+            lvec::append(lvec![1], &b, lvec![1, 2, 3]),
+        ];
+        goals.assert_permutations_resolve_to(&(a, b), vec![(vec![1, 2], vec![2, 3])]);
+    }
+
+    #[test]
+    fn enumerates_splits() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![lvec::append(&a, &b, lvec![1, 2])];
+        goals.assert_permutations_resolve_to(
+            &(a, b),
+            vec![
+                (vec![], vec![1, 2]),
+                (vec![1], vec![2]),
+                (vec![1, 2], vec![]),
+            ],
+        );
+    }
+
+    #[test]
+    fn partially_known_items() {
+        let (x, y, b) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lvec::append(lvec![x, y], &b, lvec![1, 2, 3]),
+            unify(b, lvec![3]),
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn waits_for_two_values() {
+        let (a, b, ab) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lvec::append(&a, &b, &ab),
+            unify(a, lvec![1]),
+            unify(ab, lvec![1, 2]),
+        ];
+        goals.assert_permutations_resolve_to(&b, vec![vec![2]]);
+    }
+
+    #[test]
+    fn fails_when_too_long() {
+        let (a, b) = (LVar::<lvec::LVec<i32>>::new(), LVar::new());
+        let goals = goal_vec![
+            lvec::append(lvec![1, 2, 3], &b, lvec![1, 2]),
+            lvec::append(a, lvec![1, 2, 3], lvec![1, 2]),
+        ];
+        goals.assert_permutations_resolve_to(&b, vec![]);
+        let goals = goal_vec![lvec::append(lvec![1], &b, lvec![2, 3])];
+        goals.assert_permutations_resolve_to(&b, vec![]);
+    }
+}
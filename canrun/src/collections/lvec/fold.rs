@@ -0,0 +1,130 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{resolve_1, Constraint, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::{All, Goal},
+};
+use std::{fmt, rc::Rc};
+
+/**
+A [`Goal`] that threads an accumulator through the items of an [`LVec`].
+Create with [`fold`]. */
+#[allow(clippy::type_complexity)]
+pub struct Fold<T: Unify, A: Unify> {
+    collection: Value<LVec<T>>,
+    init: Value<A>,
+    result: Value<A>,
+    f: Rc<dyn Fn(Value<A>, Value<T>, Value<A>) -> Box<dyn Goal>>,
+}
+
+impl<T: Unify, A: Unify> Clone for Fold<T, A> {
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection.clone(),
+            init: self.init.clone(),
+            result: self.result.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<T: Unify, A: Unify> fmt::Debug for Fold<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Fold {:?} {:?} {:?}",
+            self.collection, self.init, self.result
+        )
+    }
+}
+
+/**
+Create a [`Goal`] that threads an accumulator through the items of an
+[`LVec`], like Prolog's `foldl/4`.
+
+Once the vector is resolved, `func` is called for each item with the
+accumulator before and after that item. The first accumulator is `init` and
+the last is `result`, with fresh variables linking the ones in between.
+
+# Example:
+```
+use canrun::{LVar, lvec, Query};
+use canrun::ops::add;
+
+let total = LVar::new();
+let goal = lvec::fold(lvec![1, 2, 3], 0, total, |acc, x, next| add(acc, x, next));
+let results: Vec<_> = goal.query(total).collect();
+assert_eq!(results, vec![6]);
+```
+*/
+pub fn fold<T, A, G>(
+    collection: impl Into<Value<LVec<T>>>,
+    init: impl Into<Value<A>>,
+    result: impl Into<Value<A>>,
+    func: impl Fn(Value<A>, Value<T>, Value<A>) -> G + 'static,
+) -> Fold<T, A>
+where
+    T: Unify,
+    A: Unify,
+    G: Goal,
+{
+    Fold {
+        collection: collection.into(),
+        init: init.into(),
+        result: result.into(),
+        f: Rc::new(move |acc, item, next| Box::new(func(acc, item, next))),
+    }
+}
+
+impl<T: Unify, A: Unify> Goal for Fold<T, A> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify, A: Unify> Constraint for Fold<T, A> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let collection = resolve_1(&self.collection, state)?;
+        let mut goals = Vec::with_capacity(collection.len() + 1);
+        let mut acc = self.init.clone();
+        for item in &collection.vec {
+            let next = Value::var();
+            goals.push((self.f)(acc, item.clone(), next.clone()));
+            acc = next;
+        }
+        let (last, result) = (acc, self.result.clone());
+        let goal = All::from(goals);
+        Ok(Box::new(move |state| {
+            goal.apply(state.unify(&last, &result)?)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, goal_vec, goals::unify, lvec, ops::add};
+
+    #[test]
+    fn folds() {
+        let (total, xs) = (LVar::new(), LVar::new());
+        let goals = goal_vec![fold(&xs, 1, total, add), unify(xs, lvec![1, 2, 3])];
+        goals.assert_permutations_resolve_to(&total, vec![7]);
+    }
+
+    #[test]
+    fn empty_is_init() {
+        let total = LVar::new();
+        let goals = goal_vec![fold(LVec::<i32>::fresh(0), 5, total, add)];
+        goals.assert_permutations_resolve_to(&total, vec![5]);
+    }
+
+    #[test]
+    fn solves_for_an_item() {
+        let x = LVar::new();
+        let goals = goal_vec![fold(lvec![1, x, 3], 0, 10, add)];
+        goals.assert_permutations_resolve_to(&x, vec![6]);
+    }
+}
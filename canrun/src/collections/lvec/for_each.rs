@@ -0,0 +1,107 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{resolve_1, Constraint, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::{All, Goal},
+};
+use std::{fmt, rc::Rc};
+
+/**
+A [`Goal`] that holds for every item in an [`LVec`]. Create with
+[`for_each`]. */
+#[allow(clippy::type_complexity)]
+pub struct ForEach<T: Unify> {
+    collection: Value<LVec<T>>,
+    f: Rc<dyn Fn(Value<T>) -> Box<dyn Goal>>,
+}
+
+impl<T: Unify> Clone for ForEach<T> {
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<T: Unify> fmt::Debug for ForEach<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ForEach {:?}", self.collection)
+    }
+}
+
+/**
+Create a [`Goal`] that holds for every item in an [`LVec`].
+
+The goal for each item is created by calling `func` once the vector itself is
+resolved. The items don't need to be resolved.
+
+# Example:
+```
+use canrun::{LVar, all, unify, lvec, Query};
+
+let (x, y, xs) = (LVar::new(), LVar::new(), LVar::new());
+let goal = all![
+    unify(&xs, lvec![x, y]),
+    lvec::for_each(xs, |item| unify(item, 1)),
+];
+let results: Vec<_> = goal.query((x, y)).collect();
+assert_eq!(results, vec![(1, 1)]);
+```
+*/
+pub fn for_each<T, G>(
+    collection: impl Into<Value<LVec<T>>>,
+    func: impl Fn(Value<T>) -> G + 'static,
+) -> ForEach<T>
+where
+    T: Unify,
+    G: Goal,
+{
+    ForEach {
+        collection: collection.into(),
+        f: Rc::new(move |item| Box::new(func(item))),
+    }
+}
+
+impl<T: Unify> Goal for ForEach<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify> Constraint for ForEach<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let collection = resolve_1(&self.collection, state)?;
+        let goal: All = collection
+            .vec
+            .iter()
+            .map(|item| (self.f)(item.clone()))
+            .collect();
+        Ok(Box::new(move |state| goal.apply(state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn binds_every_item() {
+        let (x, y, xs) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![for_each(&xs, |item| unify(item, 1)), unify(xs, lvec![x, y])];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn fails_if_any_item_fails() {
+        let xs = LVar::new();
+        let goals = goal_vec![
+            for_each(&xs, |item| unify(item, 1)),
+            unify(&xs, lvec![1, 2])
+        ];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+    }
+}
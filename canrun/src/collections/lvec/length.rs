@@ -0,0 +1,118 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{Constraint, OneOfTwo, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::Goal,
+};
+use std::{fmt::Debug, rc::Rc};
+
+/**
+Relate an [`LVec`] to its length. Create with [`length`]. */
+#[derive(Debug)]
+pub struct Length<T: Unify> {
+    collection: Value<LVec<T>>,
+    length: Value<usize>,
+}
+
+impl<T: Unify> Clone for Length<T> {
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection.clone(),
+            length: self.length.clone(),
+        }
+    }
+}
+
+/**
+Create a [`Goal`] that relates an [`LVec`] to its length.
+
+If only the length is known, the collection will be bound to a vector of
+[fresh](LVec::fresh) variables of that length.
+
+# Examples:
+```
+use canrun::{LVar, lvec, Query};
+
+let n = LVar::new();
+let goal = lvec::length(lvec![1, 2, 3], n);
+let results: Vec<_> = goal.query(n).collect();
+assert_eq!(results, vec![3]);
+```
+
+```
+use canrun::{LVar, all, unify, lvec, Query};
+
+let xs = LVar::new();
+let goal = all![
+    lvec::length(&xs, 2),
+    lvec::get(1, 0, &xs),
+    lvec::get(2, 1, &xs),
+];
+let results: Vec<_> = goal.query(xs).collect();
+assert_eq!(results, vec![vec![1, 2]]);
+```
+*/
+pub fn length<T>(
+    collection: impl Into<Value<LVec<T>>>,
+    length: impl Into<Value<usize>>,
+) -> Length<T>
+where
+    T: Unify,
+{
+    Length {
+        collection: collection.into(),
+        length: length.into(),
+    }
+}
+
+impl<T: Unify> Goal for Length<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify> Constraint for Length<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        match OneOfTwo::resolve(&self.collection, &self.length, state)? {
+            OneOfTwo::A(collection, length) => {
+                let found = Value::new(collection.len());
+                Ok(Box::new(move |state| state.unify(&length, &found)))
+            }
+            OneOfTwo::B(collection, length) => {
+                let fresh = Value::new(LVec::fresh(*length));
+                Ok(Box::new(move |state| state.unify(&collection, &fresh)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn measures() {
+        let n = LVar::new();
+        let goals = goal_vec![length(lvec![1, 2], n)];
+        goals.assert_permutations_resolve_to(&n, vec![2]);
+        let goal = length(LVec::<i32>::fresh(0), n);
+        assert_eq!(goal.query(n).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn constructs_fresh() {
+        let (xs, x) = (LVar::new(), LVar::new());
+        let goals = goal_vec![length(&xs, 3), unify(&xs, lvec![1, x, 3]), unify(x, 2)];
+        goals.assert_permutations_resolve_to(&xs, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn fails_on_mismatch() {
+        let xs = LVar::new();
+        let goals = goal_vec![length(&xs, 2), unify(&xs, lvec![1, 2, 3])];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+    }
+}
@@ -0,0 +1,121 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{Constraint, OneOfTwo, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::{All, Goal},
+};
+use std::{fmt, rc::Rc};
+
+/**
+A [`Goal`] that relates the items of two [`LVec`]s pairwise. Create with
+[`map`]. */
+#[allow(clippy::type_complexity)]
+pub struct Map<A: Unify, B: Unify> {
+    a: Value<LVec<A>>,
+    b: Value<LVec<B>>,
+    f: Rc<dyn Fn(Value<A>, Value<B>) -> Box<dyn Goal>>,
+}
+
+impl<A: Unify, B: Unify> Clone for Map<A, B> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<A: Unify, B: Unify> fmt::Debug for Map<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Map {:?} {:?}", self.a, self.b)
+    }
+}
+
+/**
+Create a [`Goal`] that relates the items of two [`LVec`]s pairwise, like
+Prolog's `maplist/3`.
+
+Once either vector is resolved, the other is bound to a vector of
+[fresh](LVec::fresh) variables of the same length (if it wasn't already) and
+`func` is called with each pair of items.
+
+# Example:
+```
+use canrun::{LVar, lvec, Query};
+use canrun::ops::add;
+
+let ys = LVar::new();
+let goal = lvec::map(lvec![1, 2, 3], &ys, |x, y| add(x, 10, y));
+let results: Vec<_> = goal.query(ys).collect();
+assert_eq!(results, vec![vec![11, 12, 13]]);
+```
+*/
+pub fn map<A, B, G>(
+    a: impl Into<Value<LVec<A>>>,
+    b: impl Into<Value<LVec<B>>>,
+    func: impl Fn(Value<A>, Value<B>) -> G + 'static,
+) -> Map<A, B>
+where
+    A: Unify,
+    B: Unify,
+    G: Goal,
+{
+    Map {
+        a: a.into(),
+        b: b.into(),
+        f: Rc::new(move |a, b| Box::new(func(a, b))),
+    }
+}
+
+impl<A: Unify, B: Unify> Goal for Map<A, B> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<A: Unify, B: Unify> Constraint for Map<A, B> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let len = match OneOfTwo::resolve(&self.a, &self.b, state)? {
+            OneOfTwo::A(a, _) => a.len(),
+            OneOfTwo::B(_, b) => b.len(),
+        };
+        let (a, b) = (LVec::fresh(len), LVec::fresh(len));
+        let goals = a.vec.iter().zip(b.vec.iter());
+        let goal: All = goals.map(|(a, b)| (self.f)(a.clone(), b.clone())).collect();
+        let (a_var, b_var) = (self.a.clone(), self.b.clone());
+        let (a, b) = (Value::new(a), Value::new(b));
+        Ok(Box::new(move |state| {
+            goal.apply(state.unify(&a_var, &a)?.unify(&b_var, &b)?)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn maps_forward() {
+        let ys = LVar::new();
+        let goals = goal_vec![map(lvec![1, 2], &ys, unify)];
+        goals.assert_permutations_resolve_to(&ys, vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn maps_backward() {
+        let (x, xs) = (LVar::new(), LVar::new());
+        let goals = goal_vec![map(&xs, lvec![1, 2], unify), unify(xs, lvec![1, x]),];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+    }
+
+    #[test]
+    fn fails_on_length_mismatch() {
+        let ys: LVar<LVec<i32>> = LVar::new();
+        let goals = goal_vec![map(lvec![1, 2], &ys, unify), unify(&ys, lvec![1])];
+        goals.assert_permutations_resolve_to(&ys, vec![]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::{
     constraints::{resolve_1, Constraint, ResolveFn},
     LVarList, State, Unify, Value,
 };
+use crate::goals::domain::restrict;
 use crate::goals::unify;
 use crate::goals::Any;
 use crate::goals::Goal;
@@ -84,7 +85,16 @@ impl<T: Unify> Constraint for Member<T> {
             .zip(repeat(self.item.clone()))
             .map(|(a, b)| Rc::new(unify(a, b)) as Rc<dyn Goal>)
             .collect::<Any>();
-        Ok(Box::new(move |state| any.apply(state)))
+        let item = self.item.clone();
+        Ok(Box::new(move |state| {
+            // Let other goals narrow down the candidates before the fork is
+            // expanded.
+            let state = match state.resolve(&item) {
+                Value::Var(var) => restrict(state, &var, &collection.vec)?,
+                Value::Resolved(_) => state,
+            };
+            any.apply(state)
+        }))
     }
 }
 
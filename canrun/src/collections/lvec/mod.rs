@@ -1,18 +1,36 @@
 //! A [`Vec`]-like data structure with [`Value`](crate::Value) values.
 
+mod append;
+mod fold;
+mod for_each;
 mod get;
+mod length;
+mod map;
 mod member;
+mod nth;
+mod permutation;
 mod slice;
+mod sorted;
 mod subset;
+mod zip;
 
 use crate::{
     core::{Reify, State, Unify, Value},
     ReadyState,
 };
+pub use append::{append, Append};
+pub use fold::{fold, Fold};
+pub use for_each::{for_each, ForEach};
 pub use get::{get, Get};
+pub use length::{length, Length};
+pub use map::{map, Map};
 pub use member::{member, Member};
+pub use nth::{nth, Nth};
+pub use permutation::{permutation, Permutation};
 pub use slice::{slice, Slice};
+pub use sorted::{sorted, Sorted};
 pub use subset::{subset, Subset};
+pub use zip::{zip, Zip};
 
 use std::rc::Rc;
 
@@ -35,6 +53,23 @@ impl<T: Unify> LVec<T> {
     pub fn is_empty(&self) -> bool {
         self.vec.is_empty()
     }
+
+    /// The items of the [`LVec`], in order.
+    pub(crate) fn as_slice(&self) -> &[Value<T>] {
+        &self.vec
+    }
+
+    /// Create an [`LVec`] of `len` new, unbound [`LVar`](crate::LVar)s.
+    ///
+    /// ```
+    /// use canrun::lvec::LVec;
+    ///
+    /// let xs: LVec<i32> = LVec::fresh(3);
+    /// assert_eq!(xs.len(), 3);
+    /// ```
+    pub fn fresh(len: usize) -> Self {
+        (0..len).map(|_| Value::var()).collect()
+    }
 }
 
 /** Create an [`LVec<T>`](crate::collections::lvec::LVec) with automatic `Into<Value<T>>` conversion.
@@ -81,6 +116,12 @@ impl<T: Unify> From<Vec<Value<T>>> for LVec<T> {
     }
 }
 
+impl<T: Unify> From<LVec<T>> for Vec<Value<T>> {
+    fn from(lvec: LVec<T>) -> Self {
+        lvec.vec
+    }
+}
+
 impl<T: Unify> From<&[Value<T>]> for LVec<T> {
     fn from(slice: &[Value<T>]) -> Self {
         LVec {
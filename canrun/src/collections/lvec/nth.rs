@@ -0,0 +1,115 @@
+use super::{get, LVec};
+use crate::{
+    core::{
+        constraints::{resolve_1, Constraint, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::{both, unify, Any, Goal},
+};
+use std::{fmt::Debug, rc::Rc};
+
+/**
+Relate an item in an [`LVec`] to its index. Create with [`nth`]. */
+#[derive(Debug)]
+pub struct Nth<T: Unify> {
+    collection: Value<LVec<T>>,
+    index: Value<usize>,
+    item: Value<T>,
+}
+
+impl<T: Unify> Clone for Nth<T> {
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection.clone(),
+            index: self.index.clone(),
+            item: self.item.clone(),
+        }
+    }
+}
+
+/**
+Create a [`Goal`] that relates an item in an [`LVec`] to its index.
+
+This behaves like [`get`] when the index is known. Otherwise it will fork the
+state for each index at which the item could be found.
+
+# Examples:
+```
+use canrun::{LVar, lvec, Query};
+
+let i = LVar::new();
+let goal = lvec::nth(lvec![1, 2, 1], i, 1);
+let results: Vec<_> = goal.query(i).collect();
+assert_eq!(results, vec![0, 2]);
+```
+*/
+pub fn nth<T>(
+    collection: impl Into<Value<LVec<T>>>,
+    index: impl Into<Value<usize>>,
+    item: impl Into<Value<T>>,
+) -> Nth<T>
+where
+    T: Unify,
+{
+    Nth {
+        collection: collection.into(),
+        index: index.into(),
+        item: item.into(),
+    }
+}
+
+impl<T: Unify> Goal for Nth<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify> Constraint for Nth<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let collection = resolve_1(&self.collection, state)?;
+        let get = get(
+            self.item.clone(),
+            self.index.clone(),
+            self.collection.clone(),
+        );
+        match state.resolve(&self.index) {
+            Value::Resolved(_) => Ok(Box::new(move |state| get.apply(state))),
+            Value::Var(_) => {
+                let index = self.index.clone();
+                let goals: Any = (0..collection.len())
+                    .map(|i| Rc::new(both(unify(index.clone(), i), get.clone())) as Rc<dyn Goal>)
+                    .collect();
+                Ok(Box::new(move |state| goals.apply(state)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn known_index() {
+        let x = LVar::new();
+        let goal = nth(lvec![1, 2, 3], 1, x);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn enumerates_indices() {
+        let (i, x) = (LVar::new(), LVar::new());
+        let goal = nth(lvec![1, 2], i, x);
+        assert_eq!(goal.query((i, x)).collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn with_other_goals() {
+        let (i, xs) = (LVar::new(), LVar::new());
+        let goals = goal_vec![nth(&xs, i, 3), unify(&xs, lvec![1, 2, 3]), unify(i, 2)];
+        goals.assert_permutations_resolve_to(&i, vec![2]);
+        let goals = goal_vec![nth(&xs, i, 4), unify(xs, lvec![1, 2, 3])];
+        goals.assert_permutations_resolve_to(&i, vec![]);
+    }
+}
@@ -0,0 +1,198 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{Constraint, OneOfTwo, ResolveFn},
+        Fork, LVarList, State, StateIter, Unify, Value,
+    },
+    goals::Goal,
+};
+use std::{fmt::Debug, iter::once, rc::Rc};
+
+/**
+Relate two [`LVec`]s containing the same items in any order. Create with
+[`permutation`]. */
+#[derive(Debug)]
+pub struct Permutation<T: Unify> {
+    a: Value<LVec<T>>,
+    b: Value<LVec<T>>,
+}
+
+impl<T: Unify> Clone for Permutation<T> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+/**
+Create a [`Goal`] that relates two [`LVec`]s containing the same items in any
+order.
+
+Once either vector is known, the other is bound to a vector of the same
+length and its positions are filled in one at a time. Each position is a
+separate [fork](crate::State::fork()), so permutations are enumerated lazily
+and any branch that conflicts with other goals is abandoned as soon as the
+offending item is placed.
+
+Items that appear more than once will lead to repeated results.
+
+# Example:
+```
+use canrun::{LVar, all, unify, lvec, Query};
+
+let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+let goal = all![
+    lvec::permutation(lvec![x, y, z], lvec![1, 2, 3]),
+    unify(y, 1),
+];
+let results: Vec<_> = goal.query((x, y, z)).collect();
+assert_eq!(results, vec![(2, 1, 3), (3, 1, 2)]);
+```
+*/
+pub fn permutation<T>(a: impl Into<Value<LVec<T>>>, b: impl Into<Value<LVec<T>>>) -> Permutation<T>
+where
+    T: Unify,
+{
+    Permutation {
+        a: a.into(),
+        b: b.into(),
+    }
+}
+
+impl<T: Unify> Goal for Permutation<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<T: Unify> Constraint for Permutation<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (source, target) = match OneOfTwo::resolve(&self.a, &self.b, state)? {
+            OneOfTwo::A(a, b) => (a, b),
+            OneOfTwo::B(a, b) => (b, a),
+        };
+        let fresh = LVec::fresh(source.len());
+        let fork = PermutationFork {
+            source: source.vec.clone(),
+            target: fresh.vec.clone(),
+        };
+        let fresh = Value::new(fresh);
+        Ok(Box::new(move |state| {
+            state.unify(&target, &fresh)?.fork(fork)
+        }))
+    }
+}
+
+/// Places the remaining `source` items into the remaining `target` positions,
+/// one position per fork.
+#[derive(Debug)]
+struct PermutationFork<T: Unify> {
+    source: Vec<Value<T>>,
+    target: Vec<Value<T>>,
+}
+
+impl<T: Unify> PermutationFork<T> {
+    /// Try placing the source item at `index` into the first target position.
+    fn place(&self, state: State, index: usize) -> Option<State> {
+        let state = state.unify(&self.target[0], &self.source[index])?;
+        if self.target.len() == 1 {
+            return Some(state);
+        }
+        let mut source = self.source.clone();
+        source.remove(index);
+        state.fork(PermutationFork {
+            source,
+            target: self.target[1..].to_vec(),
+        })
+    }
+}
+
+impl<T: Unify> Fork for PermutationFork<T> {
+    fn fork(&self, state: &State) -> StateIter {
+        if self.target.is_empty() {
+            return Box::new(once(state.clone()));
+        }
+        let fork = PermutationFork {
+            source: self.source.clone(),
+            target: self.target.clone(),
+        };
+        let state = state.clone();
+        Box::new((0..self.source.len()).filter_map(move |index| fork.place(state.clone(), index)))
+    }
+
+    /// Counts the items that could be placed in the next position.
+    fn branching(&self, state: &State) -> Option<usize> {
+        let Some(next) = self.target.first() else {
+            return Some(1);
+        };
+        let viable = self
+            .source
+            .iter()
+            .filter(|item| state.clone().unify(next, item).is_some());
+        Some(viable.count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn enumerates() {
+        let xs = LVar::new();
+        let goal = permutation(lvec![1, 2, 3], &xs);
+        let results: Vec<_> = goal.query(xs).collect();
+        assert_eq!(
+            results,
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn either_direction() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![permutation(lvec![x, 2, y], lvec![3, 2, 1]), unify(x, 3),];
+        goals.assert_permutations_resolve_to(&y, vec![1]);
+        let goals = goal_vec![permutation(lvec![3, 2, 1], lvec![x, 2, y]), unify(x, 3),];
+        goals.assert_permutations_resolve_to(&y, vec![1]);
+    }
+
+    #[test]
+    fn fails_on_mismatch() {
+        let xs: LVar<LVec<i32>> = LVar::new();
+        let goals = goal_vec![permutation(&xs, lvec![1, 2]), unify(&xs, lvec![1, 3])];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+        let goals = goal_vec![permutation(&xs, lvec![1, 2]), unify(&xs, lvec![1])];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+    }
+
+    #[test]
+    fn empty() {
+        let xs: LVar<LVec<i32>> = LVar::new();
+        let goal = permutation(&xs, lvec![]);
+        assert_eq!(goal.query(xs).collect::<Vec<_>>(), vec![vec![]]);
+    }
+
+    #[test]
+    fn estimates_branching() {
+        let x = Value::var();
+        let fork = PermutationFork {
+            source: vec![Value::new(1), Value::new(2)],
+            target: vec![x.clone(), Value::var()],
+        };
+        let state = State::new();
+        assert_eq!(fork.branching(&state), Some(2));
+        let state = state.unify(&x, &Value::new(2)).unwrap();
+        assert_eq!(fork.branching(&state), Some(1));
+    }
+}
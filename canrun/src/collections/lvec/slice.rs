@@ -43,6 +43,7 @@ where
 /// A [`Goal`] that attempts to unify an `LVec<T>` with
 /// a slice from another `LVec<T>` defined by a [`Range`]. Create with [`slice()`].
 #[derive(Debug)]
+#[allow(clippy::struct_field_names)]
 pub struct Slice<T: Unify> {
     slice: Value<LVec<T>>,
     range: Value<Range<usize>>,
@@ -0,0 +1,223 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{Constraint, ResolveFn},
+        Fork, LVarList, State, StateIter, Unify, Value,
+    },
+    goals::Goal,
+};
+use std::{fmt::Debug, iter::once, rc::Rc};
+
+/**
+Relate an [`LVec`] to a sorted copy of itself. Create with [`sorted`]. */
+#[derive(Debug)]
+pub struct Sorted<T: Unify + Ord> {
+    collection: Value<LVec<T>>,
+    sorted: Value<LVec<T>>,
+}
+
+impl<T: Unify + Ord> Clone for Sorted<T> {
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection.clone(),
+            sorted: self.sorted.clone(),
+        }
+    }
+}
+
+/**
+Create a [`Goal`] that relates an [`LVec`] to a sorted copy of itself.
+
+As soon as every item in `collection` is resolved, it is sorted and the
+result unified with `sorted`. Alternatively, once every item in `sorted` is
+resolved it is checked for order and `collection` is constrained to be a
+[`permutation`](super::permutation) of it. Duplicate items are kept, but each
+distinct arrangement of them is only yielded once.
+
+# Examples:
+```
+use canrun::{LVar, lvec, Query};
+
+let ys = LVar::new();
+let goal = lvec::sorted(lvec![3, 1, 2, 1], &ys);
+let results: Vec<_> = goal.query(ys).collect();
+assert_eq!(results, vec![vec![1, 1, 2, 3]]);
+```
+
+```
+use canrun::{LVar, all, unify, lvec, Query};
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = all![
+    lvec::sorted(lvec![x, 2, y], lvec![1, 2, 3]),
+    unify(x, 3),
+];
+let results: Vec<_> = goal.query(y).collect();
+assert_eq!(results, vec![1]);
+```
+*/
+pub fn sorted<T>(
+    collection: impl Into<Value<LVec<T>>>,
+    sorted: impl Into<Value<LVec<T>>>,
+) -> Sorted<T>
+where
+    T: Unify + Ord,
+{
+    Sorted {
+        collection: collection.into(),
+        sorted: sorted.into(),
+    }
+}
+
+impl<T: Unify + Ord> Goal for Sorted<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+/// Resolve a vector along with every item in it, or list whatever is still
+/// unresolved.
+fn resolve_items<T: Unify>(
+    collection: &Value<LVec<T>>,
+    state: &State,
+) -> Result<Vec<Rc<T>>, LVarList> {
+    let collection = match state.resolve(collection) {
+        Value::Resolved(collection) => collection,
+        Value::Var(var) => return Err(LVarList::one(&var)),
+    };
+    let mut items = Vec::with_capacity(collection.len());
+    let mut unresolved = Vec::new();
+    for item in &collection.vec {
+        match state.resolve(item) {
+            Value::Resolved(item) => items.push(item),
+            Value::Var(var) => unresolved.push(LVarList::one(&var)),
+        }
+    }
+    if unresolved.is_empty() {
+        Ok(items)
+    } else {
+        Err(LVarList::flatten(unresolved.into_iter()))
+    }
+}
+
+impl<T: Unify + Ord> Constraint for Sorted<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let collection_vars = match resolve_items(&self.collection, state) {
+            Ok(mut items) => {
+                items.sort();
+                let items: LVec<T> = items.into_iter().map(Value::Resolved).collect();
+                let (sorted, items) = (self.sorted.clone(), Value::new(items));
+                return Ok(Box::new(move |state| state.unify(&sorted, &items)));
+            }
+            Err(vars) => vars,
+        };
+        match resolve_items(&self.sorted, state) {
+            Ok(items) => {
+                if items.windows(2).any(|pair| pair[0] > pair[1]) {
+                    return Ok(Box::new(|_| None));
+                }
+                let collection = self.collection.clone();
+                let fresh = LVec::fresh(items.len());
+                let fork = DistinctPermutations {
+                    items,
+                    target: fresh.vec.clone(),
+                };
+                let fresh = Value::new(fresh);
+                Ok(Box::new(move |state| {
+                    state.unify(&collection, &fresh)?.fork(fork)
+                }))
+            }
+            Err(sorted_vars) => Err(LVarList::flatten(
+                [collection_vars, sorted_vars].into_iter(),
+            )),
+        }
+    }
+}
+
+/// Fill in `target` with every distinct arrangement of the sorted `items`,
+/// one position at a time. Equal items are adjacent, so only the first of
+/// each run is tried at a given position.
+#[derive(Debug)]
+struct DistinctPermutations<T: Unify> {
+    items: Vec<Rc<T>>,
+    target: Vec<Value<T>>,
+}
+
+impl<T: Unify + Ord> DistinctPermutations<T> {
+    fn place(&self, state: State, index: usize) -> Option<State> {
+        let state = state.unify(&self.target[0], &Value::Resolved(self.items[index].clone()))?;
+        if self.target.len() == 1 {
+            return Some(state);
+        }
+        let mut items = self.items.clone();
+        items.remove(index);
+        state.fork(DistinctPermutations {
+            items,
+            target: self.target[1..].to_vec(),
+        })
+    }
+}
+
+impl<T: Unify + Ord> Fork for DistinctPermutations<T> {
+    fn fork(&self, state: &State) -> StateIter {
+        if self.target.is_empty() {
+            return Box::new(once(state.clone()));
+        }
+        let distinct: Vec<usize> = (0..self.items.len())
+            .filter(|&index| index == 0 || self.items[index - 1] != self.items[index])
+            .collect();
+        let fork = DistinctPermutations {
+            items: self.items.clone(),
+            target: self.target.clone(),
+        };
+        let state = state.clone();
+        Box::new(
+            distinct
+                .into_iter()
+                .filter_map(move |index| fork.place(state.clone(), index)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, core::Query, goal_vec, goals::unify, lvec};
+
+    #[test]
+    fn sorts() {
+        let (x, ys) = (LVar::new(), LVar::new());
+        let goals = goal_vec![sorted(lvec![2, x, 1], &ys), unify(x, 3)];
+        goals.assert_permutations_resolve_to(&ys, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn rejects_unsorted() {
+        let xs: LVar<LVec<i32>> = LVar::new();
+        let goal = sorted(&xs, lvec![2, 1]);
+        assert_eq!(goal.query(xs).count(), 0);
+        let goal = sorted(lvec![2, 1], lvec![2, 1]);
+        assert_eq!(goal.query(1).count(), 0);
+    }
+
+    #[test]
+    fn unsorts() {
+        let xs = LVar::new();
+        let goal = sorted(&xs, lvec![1, 2]);
+        let results: Vec<_> = goal.query(xs).collect();
+        assert_eq!(results, vec![vec![1, 2], vec![2, 1]]);
+    }
+
+    #[test]
+    fn unsorts_duplicates_once() {
+        let xs = LVar::new();
+        let goal = sorted(&xs, lvec![1, 1]);
+        let results: Vec<_> = goal.query(xs).collect();
+        assert_eq!(results, vec![vec![1, 1]]);
+
+        let xs = LVar::new();
+        let goal = sorted(&xs, lvec![1, 1, 2]);
+        let results: Vec<_> = goal.query(xs).collect();
+        assert_eq!(results, vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]]);
+    }
+}
@@ -0,0 +1,131 @@
+use super::LVec;
+use crate::{
+    core::{
+        constraints::{Constraint, ResolveFn},
+        LVarList, State, Unify, Value,
+    },
+    goals::Goal,
+};
+use std::rc::Rc;
+
+/**
+Relate two [`LVec`]s to a vector of pairs. Create with [`zip`]. */
+#[derive(Debug)]
+pub struct Zip<A: Unify, B: Unify> {
+    a: Value<LVec<A>>,
+    b: Value<LVec<B>>,
+    pairs: Value<LVec<(Value<A>, Value<B>)>>,
+}
+
+impl<A: Unify, B: Unify> Clone for Zip<A, B> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            pairs: self.pairs.clone(),
+        }
+    }
+}
+
+/**
+Create a [`Goal`] that relates two [`LVec`]s of the same length to a vector
+of pairs of their items.
+
+This works in any direction, and waits until the length of any one of the
+three vectors is known.
+
+# Example:
+```
+use canrun::{LVar, lvec, ltup, Query};
+
+let (xs, ys) = (LVar::new(), LVar::new());
+let goal = lvec::zip(&xs, &ys, lvec![ltup!(1, "a"), ltup!(2, "b")]);
+let results: Vec<_> = goal.query((xs, ys)).collect();
+assert_eq!(results, vec![(vec![1, 2], vec!["a", "b"])]);
+```
+*/
+pub fn zip<A, B>(
+    a: impl Into<Value<LVec<A>>>,
+    b: impl Into<Value<LVec<B>>>,
+    pairs: impl Into<Value<LVec<(Value<A>, Value<B>)>>>,
+) -> Zip<A, B>
+where
+    A: Unify,
+    B: Unify,
+{
+    Zip {
+        a: a.into(),
+        b: b.into(),
+        pairs: pairs.into(),
+    }
+}
+
+impl<A: Unify, B: Unify> Goal for Zip<A, B> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+}
+
+impl<A: Unify, B: Unify> Constraint for Zip<A, B> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let len = match (
+            state.resolve(&self.a),
+            state.resolve(&self.b),
+            state.resolve(&self.pairs),
+        ) {
+            (Value::Resolved(a), _, _) => a.len(),
+            (_, Value::Resolved(b), _) => b.len(),
+            (_, _, Value::Resolved(pairs)) => pairs.len(),
+            (Value::Var(a), Value::Var(b), Value::Var(pairs)) => {
+                let vars = [LVarList::two(&a, &b), LVarList::one(&pairs)];
+                return Err(LVarList::flatten(vars.into_iter()));
+            }
+        };
+        let (a, b) = (LVec::fresh(len), LVec::fresh(len));
+        let pairs: LVec<_> = a
+            .vec
+            .iter()
+            .cloned()
+            .zip(b.vec.iter().cloned())
+            .map(Value::new)
+            .collect();
+        let zip = self.clone();
+        let (a, b, pairs) = (Value::new(a), Value::new(b), Value::new(pairs));
+        Ok(Box::new(move |state| {
+            state
+                .unify(&zip.a, &a)?
+                .unify(&zip.b, &b)?
+                .unify(&zip.pairs, &pairs)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::LVar, goal_vec, goals::unify, ltup, lvec};
+
+    #[test]
+    fn zips() {
+        let pairs = LVar::new();
+        let goals = goal_vec![zip(lvec![1, 2], lvec![3, 4], &pairs)];
+        goals.assert_permutations_resolve_to(&pairs, vec![vec![(1, 3), (2, 4)]]);
+    }
+
+    #[test]
+    fn partially_known() {
+        let (x, ys) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            zip(lvec![1, x], &ys, lvec![ltup!(1, 3), ltup!(2, 4)]),
+            unify(ys, lvec![3, 4]),
+        ];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+    }
+
+    #[test]
+    fn fails_on_length_mismatch() {
+        let pairs = LVar::new();
+        let goals = goal_vec![zip(lvec![1, 2], lvec![3], &pairs)];
+        goals.assert_permutations_resolve_to(&pairs, vec![]);
+    }
+}
@@ -8,6 +8,12 @@ specifying and implementing these operations correctly means that they could
 be flat out wrong. More testing, benchmarking and refinement is required.
 */
 
+pub mod lbag;
+pub mod lbtreemap;
+pub mod llist;
 pub mod lmap;
+pub mod lset;
+pub mod lstring;
 pub mod ltup;
 pub mod lvec;
+pub mod term;
@@ -0,0 +1,503 @@
+/*! A dynamically shaped [`Term`] for untyped, Prolog-like logic programming.
+
+Everything else in canrun is statically typed: a [`Value<i32>`](Value) can
+only ever be unified with other `i32`s. That is usually what you want, but
+sometimes the shape of the data isn't known until runtime (for example, when
+interpreting rules loaded from a file). A `Term` can hold atoms, numbers,
+strings, lists and arbitrarily nested compound terms, all in one type.
+
+Logic variables are represented the usual way, as a [`Value::Var`] holding an
+[`LVar<Term>`](crate::LVar). This means terms can be freely mixed with all
+of the existing goals.
+
+```
+use canrun::{unify, LVar, Query, Value};
+use canrun::term::Term;
+
+let x = LVar::new();
+// f(a, [1, X]) = f(a, [1, "two"])
+let goal = unify(
+    Term::compound("f", [Term::atom("a"), Term::list([Value::new(Term::Int(1)), (&x).into()])]),
+    Term::compound("f", [Term::atom("a"), Term::list([Term::Int(1), Term::from("two")])]),
+);
+let result: Vec<_> = goal.query(x).collect();
+assert_eq!(result, vec![Term::Str("two".to_string())]);
+```
+*/
+
+use std::{fmt, rc::Rc};
+
+use crate::{
+    core::{ReadyState, Reify, State, Unify, Value},
+    lvec::LVec,
+};
+
+/// A dynamically typed term. See the [module level docs](crate::term) for
+/// more details.
+///
+/// Equality treats a [`Cons`](Term::Cons) chain ending in a resolved
+/// [`List`](Term::List) as the same list as the flattened `List`.
+#[derive(Debug, Clone)]
+pub enum Term {
+    /// A named constant, such as `a` or `'hello world'`.
+    Atom(String),
+    /// An integer.
+    Int(i64),
+    /// A floating point number. Like [`f64`], `NaN` does not unify with
+    /// anything (including itself).
+    Float(f64),
+    /// A string.
+    Str(String),
+    /// A functor applied to one or more arguments, such as `f(a, X)`.
+    ///
+    /// Tuples are converted to and from compound terms with an empty functor.
+    Compound(String, Vec<Value<Term>>),
+    /// A proper list, such as `[1, 2, 3]`.
+    List(Vec<Value<Term>>),
+    /// A list with a known head and a tail that may not be resolved yet,
+    /// such as `[1 | T]`. A `Cons` unifies with a non-empty [`List`](Term::List)
+    /// by matching against its first element and the remaining elements.
+    Cons(Value<Term>, Value<Term>),
+}
+
+impl Term {
+    /// Create an [`Atom`](Term::Atom).
+    pub fn atom(name: impl Into<String>) -> Term {
+        Term::Atom(name.into())
+    }
+
+    /// Create a [`Compound`](Term::Compound) term with automatic
+    /// `Into<Value<Term>>` conversion of the arguments.
+    pub fn compound<A: Into<Value<Term>>>(
+        functor: impl Into<String>,
+        args: impl IntoIterator<Item = A>,
+    ) -> Term {
+        Term::Compound(functor.into(), args.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a proper [`List`](Term::List) with automatic
+    /// `Into<Value<Term>>` conversion of the items.
+    pub fn list<A: Into<Value<Term>>>(items: impl IntoIterator<Item = A>) -> Term {
+        Term::List(items.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a [`Cons`](Term::Cons) cell with automatic `Into<Value<Term>>`
+    /// conversion. If the tail is already a resolved [`List`](Term::List),
+    /// the head is prepended to it instead so that the result stays
+    /// canonical.
+    pub fn cons(head: impl Into<Value<Term>>, tail: impl Into<Value<Term>>) -> Term {
+        let head = head.into();
+        match tail.into() {
+            Value::Resolved(tail) => match tail.as_ref() {
+                Term::List(items) => {
+                    Term::List(std::iter::once(head).chain(items.iter().cloned()).collect())
+                }
+                _ => Term::Cons(head, Value::Resolved(tail)),
+            },
+            tail @ Value::Var(_) => Term::Cons(head, tail),
+        }
+    }
+
+    /// The functor name and arity, if this is an [`Atom`](Term::Atom) or
+    /// [`Compound`](Term::Compound) term.
+    pub fn functor(&self) -> Option<(&str, usize)> {
+        match self {
+            Term::Atom(name) => Some((name, 0)),
+            Term::Compound(name, args) => Some((name, args.len())),
+            _ => None,
+        }
+    }
+
+    /// The items of a [`List`](Term::List), or of a [`Cons`](Term::Cons)
+    /// chain that ends in one.
+    fn list_items(&self) -> Option<Vec<&Value<Term>>> {
+        match self {
+            Term::List(items) => Some(items.iter().collect()),
+            Term::Cons(head, Value::Resolved(tail)) => {
+                let mut items = tail.list_items()?;
+                items.insert(0, head);
+                Some(items)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Term {
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.list_items(), other.list_items()) {
+            return a == b;
+        }
+        match (self, other) {
+            (Term::Atom(a), Term::Atom(b)) | (Term::Str(a), Term::Str(b)) => a == b,
+            (Term::Int(a), Term::Int(b)) => a == b,
+            #[allow(clippy::float_cmp)]
+            (Term::Float(a), Term::Float(b)) => a == b,
+            (Term::Compound(a_name, a_args), Term::Compound(b_name, b_args)) => {
+                a_name == b_name && a_args == b_args
+            }
+            (Term::Cons(a_head, a_tail), Term::Cons(b_head, b_tail)) => {
+                a_head == b_head && a_tail == b_tail
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Unify for Term {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        match (a.as_ref(), b.as_ref()) {
+            (Term::Atom(a), Term::Atom(b)) | (Term::Str(a), Term::Str(b)) if a == b => Some(state),
+            (Term::Int(a), Term::Int(b)) if a == b => Some(state),
+            #[allow(clippy::float_cmp)]
+            (Term::Float(a), Term::Float(b)) if a == b => Some(state),
+            (Term::Compound(a_name, a_args), Term::Compound(b_name, b_args))
+                if a_name == b_name && a_args.len() == b_args.len() =>
+            {
+                unify_all(state, a_args, b_args)
+            }
+            (Term::List(a), Term::List(b)) if a.len() == b.len() => unify_all(state, a, b),
+            (Term::Cons(a_head, a_tail), Term::Cons(b_head, b_tail)) => {
+                state.unify(a_head, b_head)?.unify(a_tail, b_tail)
+            }
+            (Term::Cons(head, tail), Term::List(items))
+            | (Term::List(items), Term::Cons(head, tail)) => {
+                let (first, rest) = items.split_first()?;
+                let rest = Value::new(Term::List(rest.to_vec()));
+                state.unify(head, first)?.unify(tail, &rest)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn unify_all(state: State, a: &[Value<Term>], b: &[Value<Term>]) -> Option<State> {
+    a.iter()
+        .zip(b)
+        .try_fold(state, |state, (a, b)| state.unify(a, b))
+}
+
+/// Reifies into a fully resolved `Term`, in which every nested value is
+/// [`Value::Resolved`]. [`Cons`](Term::Cons) cells with a resolved tail are
+/// flattened into a proper [`List`](Term::List).
+impl Reify for Term {
+    type Reified = Term;
+    fn reify_in(&self, state: &ReadyState) -> Option<Term> {
+        let reify_all = |values: &[Value<Term>]| {
+            values
+                .iter()
+                .map(|v| v.reify_in(state).map(Value::new))
+                .collect::<Option<Vec<_>>>()
+        };
+        Some(match self {
+            Term::Compound(name, args) => Term::Compound(name.clone(), reify_all(args)?),
+            Term::List(items) => Term::List(reify_all(items)?),
+            Term::Cons(head, tail) => match tail.reify_in(state)? {
+                Term::List(mut items) => {
+                    items.insert(0, Value::new(head.reify_in(state)?));
+                    Term::List(items)
+                }
+                tail => Term::cons(head.reify_in(state)?, tail),
+            },
+            other => other.clone(),
+        })
+    }
+}
+
+impl From<i64> for Term {
+    fn from(n: i64) -> Self {
+        Term::Int(n)
+    }
+}
+
+impl From<f64> for Term {
+    fn from(n: f64) -> Self {
+        Term::Float(n)
+    }
+}
+
+impl From<&str> for Term {
+    fn from(s: &str) -> Self {
+        Term::Str(s.to_string())
+    }
+}
+
+impl From<String> for Term {
+    fn from(s: String) -> Self {
+        Term::Str(s)
+    }
+}
+
+impl From<LVec<Term>> for Term {
+    fn from(vec: LVec<Term>) -> Self {
+        Term::List(vec.into())
+    }
+}
+
+impl TryFrom<Term> for LVec<Term> {
+    type Error = Term;
+
+    /// Succeeds for a [`List`](Term::List). Anything else is returned as the
+    /// error.
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        match term {
+            Term::List(items) => Ok(items.into()),
+            other => Err(other),
+        }
+    }
+}
+
+macro_rules! term_of {
+    ($v:ident) => {
+        Value<Term>
+    };
+}
+
+macro_rules! impl_term_tuple {
+    ($len:literal: $($v:ident),+) => {
+        impl From<($(term_of!($v)),+)> for Term {
+            fn from(($($v),+): ($(term_of!($v)),+)) -> Self {
+                Term::Compound(String::new(), vec![$($v),+])
+            }
+        }
+
+        impl TryFrom<Term> for ($(term_of!($v)),+) {
+            type Error = Term;
+
+            fn try_from(term: Term) -> Result<Self, Self::Error> {
+                match term {
+                    Term::Compound(name, args) if name.is_empty() && args.len() == $len => {
+                        let mut args = args.into_iter();
+                        $(let $v = args.next().expect("length was checked");)+
+                        Ok(($($v),+))
+                    }
+                    other => Err(other),
+                }
+            }
+        }
+    };
+}
+
+impl_term_tuple!(2: a, b);
+impl_term_tuple!(3: a, b, c);
+impl_term_tuple!(4: a, b, c, d);
+impl_term_tuple!(5: a, b, c, d, e);
+
+/// Writes terms in a Prolog-like syntax. Unresolved variables are written as
+/// `_` followed by an internal id, which is only useful for telling them
+/// apart.
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Atom(name) => write!(f, "{name}"),
+            Term::Int(n) => write!(f, "{n}"),
+            Term::Float(n) => write!(f, "{n:?}"),
+            Term::Str(s) => write!(f, "{s:?}"),
+            Term::Compound(name, args) => {
+                write!(f, "{name}(")?;
+                write_all(f, args)?;
+                write!(f, ")")
+            }
+            Term::List(items) => {
+                write!(f, "[")?;
+                write_all(f, items)?;
+                write!(f, "]")
+            }
+            Term::Cons(head, tail) => {
+                write!(f, "[")?;
+                write_value(f, head)?;
+                let mut tail = tail;
+                loop {
+                    match tail {
+                        Value::Resolved(term) => match term.as_ref() {
+                            Term::List(items) => {
+                                for item in items {
+                                    write!(f, ", ")?;
+                                    write_value(f, item)?;
+                                }
+                                break;
+                            }
+                            Term::Cons(head, next) => {
+                                write!(f, ", ")?;
+                                write_value(f, head)?;
+                                tail = next;
+                            }
+                            _ => {
+                                write!(f, " | ")?;
+                                write_value(f, tail)?;
+                                break;
+                            }
+                        },
+                        Value::Var(_) => {
+                            write!(f, " | ")?;
+                            write_value(f, tail)?;
+                            break;
+                        }
+                    }
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Value<Term>) -> fmt::Result {
+    match value {
+        Value::Var(var) => write!(f, "_{}", var.id),
+        Value::Resolved(term) => write!(f, "{term}"),
+    }
+}
+
+fn write_all(f: &mut fmt::Formatter<'_>, values: &[Value<Term>]) -> fmt::Result {
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write_value(f, value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Term;
+    use crate::{goals::unify, ltup, lvec, lvec::LVec, LVar, Query, State, Value};
+
+    fn int(n: i64) -> Value<Term> {
+        Value::new(Term::Int(n))
+    }
+
+    #[test]
+    fn unifies_nested_compounds() {
+        let x = LVar::new();
+        let y = LVar::new();
+        let goal = unify(
+            Term::compound("f", [Value::new(Term::atom("a")), (&x).into()]),
+            Term::compound("f", [(&y).into(), Value::new(Term::list([int(1)]))]),
+        );
+        assert_eq!(
+            goal.query((x, y)).collect::<Vec<_>>(),
+            vec![(Term::list([int(1)]), Term::atom("a"))]
+        );
+    }
+
+    #[test]
+    fn mismatched_functors_and_types_fail() {
+        let cases = [
+            (Term::compound("f", [int(1)]), Term::compound("g", [int(1)])),
+            (
+                Term::compound("f", [int(1)]),
+                Term::compound("f", [int(1), int(2)]),
+            ),
+            (Term::Int(1), Term::Float(1.0)),
+            (Term::atom("a"), Term::from("a")),
+            (Term::Float(f64::NAN), Term::Float(f64::NAN)),
+        ];
+        for (a, b) in cases {
+            assert!(State::new().unify(&a.into(), &b.into()).is_none());
+        }
+    }
+
+    #[test]
+    fn cons_unifies_with_list() {
+        let head = LVar::new();
+        let tail = LVar::new();
+        let goal = unify(
+            Term::cons(&head, &tail),
+            Term::list([int(1), int(2), int(3)]),
+        );
+        assert_eq!(
+            goal.query((head.clone(), tail.clone())).collect::<Vec<_>>(),
+            vec![(Term::Int(1), Term::list([int(2), int(3)]))]
+        );
+        let empty = Term::List(Vec::new());
+        assert!(State::new()
+            .unify(&Term::cons(head, tail).into(), &empty.into())
+            .is_none());
+    }
+
+    #[test]
+    fn cons_with_list_tail_is_the_same_list() {
+        let raw = Term::Cons(int(1), Value::new(Term::list([int(2)])));
+        assert_eq!(raw, Term::list([int(1), int(2)]));
+        assert_eq!(Term::list([int(1), int(2)]), raw);
+        assert!(matches!(
+            Term::cons(int(1), Term::list([int(2)])),
+            Term::List(items) if items.len() == 2
+        ));
+        assert_ne!(raw, Term::list([int(1)]));
+
+        let x = LVar::new();
+        let goal = crate::both(
+            unify(&x, Term::Cons(int(1), Value::new(Term::List(Vec::new())))),
+            unify(&x, Term::list([int(1)])),
+        );
+        assert_eq!(
+            goal.query(x).collect::<Vec<_>>(),
+            vec![Term::list([int(1)])]
+        );
+    }
+
+    #[test]
+    fn reifies_cons_into_list() {
+        let tail = LVar::new();
+        let list = LVar::new();
+        let goal = crate::both(
+            unify(&list, Term::cons(int(1), &tail)),
+            unify(
+                tail,
+                Term::cons(int(2), Term::list(Vec::<Value<Term>>::new())),
+            ),
+        );
+        assert_eq!(
+            goal.query(list).collect::<Vec<_>>(),
+            vec![Term::list([int(1), int(2)])]
+        );
+    }
+
+    #[test]
+    fn does_not_reify_unbound_vars() {
+        let x = LVar::new();
+        let y: LVar<Term> = LVar::new();
+        let goal = unify(&x, Term::compound("f", [y]));
+        assert_eq!(goal.query(x).count(), 0);
+    }
+
+    #[test]
+    fn converts_lvecs() {
+        let vec: LVec<Term> = lvec![Term::Int(1), Term::atom("a")];
+        let term = Term::from(vec);
+        assert_eq!(term, Term::list([int(1), Value::new(Term::atom("a"))]));
+        let vec = LVec::try_from(term).unwrap();
+        assert_eq!(vec.len(), 2);
+        assert!(LVec::try_from(Term::Int(1)).is_err());
+    }
+
+    #[test]
+    fn converts_tuples() {
+        let term = Term::from(ltup!(Term::Int(1), Term::atom("b")));
+        assert_eq!(term.to_string(), "(1, b)");
+        let (a, b): (Value<Term>, Value<Term>) = term.clone().try_into().unwrap();
+        assert_eq!((a, b), (int(1), Value::new(Term::atom("b"))));
+        assert!(<(Value<Term>, Value<Term>, Value<Term>)>::try_from(term).is_err());
+    }
+
+    #[test]
+    fn displays_terms() {
+        let x: Value<Term> = Value::var();
+        let term = Term::compound(
+            "f",
+            [
+                Value::new(Term::atom("a")),
+                Value::new(Term::Float(1.0)),
+                Value::new(Term::from("s")),
+                Value::new(Term::cons(int(1), Term::cons(int(2), x.clone()))),
+            ],
+        );
+        let Value::Var(var) = x else { unreachable!() };
+        assert_eq!(
+            term.to_string(),
+            format!("f(a, 1.0, \"s\", [1, 2 | _{}])", var.id)
+        );
+    }
+}
@@ -77,6 +77,16 @@ pub trait Constraint {
     /// Will return a list of unresolved variables required to check the
     /// constraint.
     fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList>;
+
+    /// Returns `true` if the constraint is known to hold for some binding of
+    /// the variables it is still waiting on, so that it doesn't keep the
+    /// state from being [ready](State::ready).
+    ///
+    /// The waiting variables are left unresolved, so this is only useful for
+    /// constraints that relate many variables at once. Defaults to `false`.
+    fn is_satisfiable(&self, _state: &State) -> bool {
+        false
+    }
 }
 
 /// Resolve one [`Value`] or return an [`Err(LVarList)`](LVarList) in a
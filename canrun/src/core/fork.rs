@@ -1,3 +1,5 @@
+use std::{cmp::Ordering, fmt, rc::Rc};
+
 use super::{State, StateIter};
 
 /** Fork a [`State`] into zero or more alternate states.
@@ -34,6 +36,18 @@ pub trait Fork: 'static {
     /// Given a [`State`], return an iterator of states that result from the
     /// fork operation.
     fn fork(&self, state: &State) -> StateIter;
+
+    /// An estimate of how many states [`fork`](Fork::fork()) will yield
+    /// for the given [`State`], or `None` if it isn't cheap to know.
+    ///
+    /// This is used by [`ForkPolicy::FirstFail`] to decide which pending fork
+    /// to expand next: the fork with the smallest estimate is expanded first,
+    /// and a fork reporting `Some(0)` is expanded immediately as a likely
+    /// dead end. The estimate only affects that ordering, so a wrong value
+    /// never changes which states are found, only how much work it takes.
+    fn branching(&self, _state: &State) -> Option<usize> {
+        None
+    }
 }
 
 impl<F: 'static> Fork for F
@@ -44,3 +58,178 @@ where
         self(state)
     }
 }
+
+/** Decides which pending [`Fork`] is expanded next when iterating over the
+states of a [`State`].
+
+The order forks are expanded in does not change which results are found,
+but it can make an enormous difference in how much work is needed to find
+them. Constraint satisfaction problems in particular tend to benefit from
+[`FirstFail`](ForkPolicy::FirstFail), which expands the most constrained
+fork first so that dead ends are found early.
+
+Set with [`State::with_fork_policy()`].
+
+# Example:
+```
+use canrun::{any, unify, ForkPolicy, Goal, LVar, Query, State};
+
+let x = LVar::new();
+let y = LVar::new();
+let goal = canrun::all![
+    any![unify(x, 1), unify(x, 2), unify(x, 3)],
+    any![unify(y, 1), unify(y, 2)],
+];
+let state = State::new().with_fork_policy(ForkPolicy::FirstFail);
+let results: Vec<_> = goal.apply(state).query((x, y)).collect();
+// The smaller `y` fork is expanded first.
+assert_eq!(results, vec![(1, 1), (2, 1), (3, 1), (1, 2), (2, 2), (3, 2)]);
+```
+*/
+#[derive(Clone, Default)]
+pub enum ForkPolicy {
+    /// Expand forks in the order they were added. This is the default.
+    #[default]
+    Fifo,
+    /// Expand the fork with the smallest [`branching`](Fork::branching())
+    /// estimate first. Forks that can't provide an estimate are expanded
+    /// last, and ties are broken by the order the forks were added.
+    ///
+    /// Note that the estimates are not free (an [`Any`](crate::goals::Any)
+    /// goal tries applying each of its sub-goals to count the viable ones)
+    /// and they are recomputed for every pending fork each time one is
+    /// expanded. This only pays off when it prunes enough of the search.
+    FirstFail,
+    /// Expand the smallest fork as ordered by the provided comparison
+    /// function. Ties are broken by the order the forks were added.
+    Custom(ForkComparator),
+}
+
+/// A comparison function used by [`ForkPolicy::Custom`].
+pub type ForkComparator = Rc<dyn Fn(&ForkInfo, &ForkInfo) -> Ordering>;
+
+/// Information about a pending [`Fork`], passed to
+/// [`ForkPolicy::Custom`] comparison functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkInfo {
+    /// The position of the fork in the queue of pending forks, starting with
+    /// the oldest at `0`.
+    pub index: usize,
+    /// The fork's [`branching`](Fork::branching()) estimate.
+    pub branching: Option<usize>,
+}
+
+impl ForkPolicy {
+    /// Returns the index of the pending fork to expand next, if any.
+    pub(crate) fn select(&self, state: &State) -> Option<usize> {
+        if state.forks.is_empty() {
+            return None;
+        }
+        let infos = || {
+            state
+                .forks
+                .iter()
+                .enumerate()
+                .map(|(index, fork)| ForkInfo {
+                    index,
+                    branching: fork.branching(state),
+                })
+        };
+        match self {
+            ForkPolicy::Fifo => Some(0),
+            ForkPolicy::FirstFail => {
+                let mut best: Option<(usize, usize)> = None;
+                for info in infos() {
+                    let branching = info.branching.unwrap_or(usize::MAX);
+                    if branching == 0 {
+                        // A dead end. No point in looking any further.
+                        return Some(info.index);
+                    }
+                    if best.map_or(true, |(_, min)| branching < min) {
+                        best = Some((info.index, branching));
+                    }
+                }
+                best.map(|(index, _)| index)
+            }
+            ForkPolicy::Custom(compare) => {
+                infos().min_by(|a, b| compare(a, b)).map(|info| info.index)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ForkPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForkPolicy::Fifo => write!(f, "Fifo"),
+            ForkPolicy::FirstFail => write!(f, "FirstFail"),
+            ForkPolicy::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{ForkInfo, ForkPolicy};
+    use crate::{any, either, goals::Goal, unify, LVar, Query, State, Succeed};
+
+    fn results(policy: ForkPolicy) -> Vec<(i32, i32)> {
+        let x = LVar::new();
+        let y = LVar::new();
+        let goal = crate::all![
+            any![unify(x, 1), unify(x, 2), unify(x, 3)],
+            either(unify(y, 1), unify(y, 2)),
+        ];
+        goal.apply(State::new().with_fork_policy(policy))
+            .query((x, y))
+            .collect()
+    }
+
+    #[test]
+    fn fifo_expands_oldest_first() {
+        assert_eq!(
+            results(ForkPolicy::Fifo),
+            vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn first_fail_expands_smallest_first() {
+        assert_eq!(
+            results(ForkPolicy::FirstFail),
+            vec![(1, 1), (2, 1), (3, 1), (1, 2), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn custom_policy() {
+        // Expanding the `y` fork first groups the results by `y`.
+        let newest_first = |a: &ForkInfo, b: &ForkInfo| b.index.cmp(&a.index);
+        assert_eq!(
+            results(ForkPolicy::Custom(Rc::new(newest_first))),
+            vec![(1, 1), (2, 1), (3, 1), (1, 2), (2, 2), (3, 2)]
+        );
+        // Expanding the `x` fork first groups the results by `x`.
+        let widest_first = |a: &ForkInfo, b: &ForkInfo| b.branching.cmp(&a.branching);
+        assert_eq!(
+            results(ForkPolicy::Custom(Rc::new(widest_first))),
+            vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn first_fail_prefers_known_estimates() {
+        let x = LVar::new();
+        let opaque = move |s: &State| -> crate::StateIter {
+            Box::new(s.clone().unify(&x.into(), &1.into()).into_iter())
+        };
+        let state = State::new()
+            .fork(opaque)
+            .and_then(|s| any![Succeed, Succeed].apply(s))
+            .unwrap();
+        assert_eq!(ForkPolicy::FirstFail.select(&state), Some(1));
+        assert_eq!(ForkPolicy::Fifo.select(&state), Some(0));
+    }
+}
@@ -81,13 +81,19 @@ impl<K: Eq + Hash + Clone + fmt::Debug, V: Clone> MKMVMap<K, V> {
         Some(values)
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.keys.is_empty()
+    /// Returns true if any values added after `other` (which this map was
+    /// cloned from) are still in the map.
+    pub fn has_values_added_since(&self, other: &Self) -> bool {
+        self.values.keys().any(|id| *id >= other.current_id)
     }
 
     pub fn keys(&self) -> impl Iterator<Item = &K> {
         self.keys.keys()
     }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.values().map(|value| &value.data)
+    }
 }
 
 impl<K: Eq + Hash + Clone + fmt::Debug, V> fmt::Debug for Value<K, V> {
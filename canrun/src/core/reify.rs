@@ -118,6 +118,44 @@ impl_reify_copy!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize, f32, f64);
 impl_reify_copy!(&'static str, bool, char);
 impl_reify_clone!(String);
 
+impl<T: Reify> Reify for Option<T> {
+    type Reified = Option<T::Reified>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        match self {
+            Some(value) => Some(Some(value.reify_in(state)?)),
+            None => Some(None),
+        }
+    }
+}
+
+impl<T: Reify, E: Reify> Reify for Result<T, E> {
+    type Reified = Result<T::Reified, E::Reified>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        match self {
+            Ok(value) => Some(Ok(value.reify_in(state)?)),
+            Err(error) => Some(Err(error.reify_in(state)?)),
+        }
+    }
+}
+
+impl<T: Reify> Reify for Box<T> {
+    type Reified = Box<T::Reified>;
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        Some(Box::new(self.as_ref().reify_in(state)?))
+    }
+}
+
+impl<T: Reify, const N: usize> Reify for [T; N] {
+    type Reified = [T::Reified; N];
+    fn reify_in(&self, state: &ReadyState) -> Option<Self::Reified> {
+        let reified = self
+            .iter()
+            .map(|value| value.reify_in(state))
+            .collect::<Option<Vec<_>>>()?;
+        reified.try_into().ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Reify, State, Value};
@@ -136,4 +174,29 @@ mod tests {
             Some("foo".to_string())
         );
     }
+
+    #[test]
+    fn reify_option_result_box() {
+        let state = State::new().ready().unwrap();
+        let x: Value<i32> = Value::var();
+        assert_eq!(Some(Value::new(1)).reify_in(&state), Some(Some(1)));
+        assert_eq!(None::<Value<i32>>.reify_in(&state), Some(None));
+        assert_eq!(Some(x.clone()).reify_in(&state), None);
+
+        let ok: Result<Value<i32>, Value<&str>> = Ok(Value::new(1));
+        assert_eq!(ok.reify_in(&state), Some(Ok(1)));
+        let err: Result<Value<i32>, Value<&str>> = Err(Value::new("e"));
+        assert_eq!(err.reify_in(&state), Some(Err("e")));
+
+        assert_eq!(Box::new(Value::new(1)).reify_in(&state), Some(Box::new(1)));
+    }
+
+    #[test]
+    fn reify_array() {
+        let state = State::new().ready().unwrap();
+        let array = [Value::new(1), Value::new(2), Value::new(3)];
+        assert_eq!(array.reify_in(&state), Some([1, 2, 3]));
+        let array = [Value::new(1), Value::var()];
+        assert_eq!(array.reify_in(&state), None);
+    }
 }
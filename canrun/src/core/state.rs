@@ -4,9 +4,10 @@ use super::mkmvmap::MKMVMap;
 
 use super::constraints::Constraint;
 use crate::{
-    core::{AnyVal, Fork, Unify, Value, VarId},
+    core::{AnyVal, Fork, ForkPolicy, LVar, Unify, Value, VarId},
     LVarList, ReadyState,
 };
+use std::any::{Any, TypeId};
 use std::rc::Rc;
 
 /** The core struct used to contain and manage [`Value`] bindings.
@@ -42,7 +43,10 @@ assert!(my_fn().is_some());
 pub struct State {
     pub(crate) values: im_rc::HashMap<VarId, AnyVal>,
     pub(crate) forks: im_rc::Vector<Rc<dyn Fork>>,
+    pub(crate) fork_policy: ForkPolicy,
     constraints: MKMVMap<VarId, Rc<dyn Constraint>>,
+    domains: im_rc::HashMap<VarId, Rc<dyn Any>>,
+    stores: im_rc::HashMap<TypeId, Rc<dyn Any>>,
 }
 
 impl State {
@@ -66,10 +70,29 @@ impl State {
         State {
             values: im_rc::HashMap::new(),
             forks: im_rc::Vector::new(),
+            fork_policy: ForkPolicy::default(),
             constraints: MKMVMap::new(),
+            domains: im_rc::HashMap::new(),
+            stores: im_rc::HashMap::new(),
         }
     }
 
+    /**
+    Set the [`ForkPolicy`] used to decide which pending [`Fork`] is expanded
+    next. The policy is inherited by every state derived from this one.
+
+    # Example:
+    ```
+    use canrun::{ForkPolicy, State};
+    let state = State::new().with_fork_policy(ForkPolicy::FirstFail);
+    ```
+    */
+    #[must_use]
+    pub fn with_fork_policy(mut self, policy: ForkPolicy) -> Self {
+        self.fork_policy = policy;
+        self
+    }
+
     /**
     Apply an arbitrary function to a state.
 
@@ -219,13 +242,60 @@ impl State {
     called. [Constraints](State::constrain()) are run as variables are
     resolved. Forking is executed lazily at the end, when
     [`StateIterator::into_states`](super::state_iterator::StateIterator::into_states)
-    or [`.query()`](crate::Query::query()) is called.
+    or [`.query()`](crate::Query::query()) is called. Forks are expanded in
+    the order they were added unless a different [`ForkPolicy`] is
+    [set](State::with_fork_policy()).
     */
     pub fn fork(mut self, fork: impl Fork) -> Option<Self> {
         self.forks.push_back(Rc::new(fork));
         Some(self)
     }
 
+    /// The finite set of values an unresolved [`LVar`] is known to be limited
+    /// to, as recorded by the [`domain`](crate::goals::domain) goals.
+    pub(crate) fn domain<T: Unify>(&self, var: &LVar<T>) -> Option<&[T]> {
+        let values = self.domains.get(&var.id)?;
+        values.downcast_ref::<Vec<T>>().map(Vec::as_slice)
+    }
+
+    /// Record the sorted, finite set of values an [`LVar`] is limited to.
+    pub(crate) fn with_domain<T: Unify>(mut self, var: &LVar<T>, values: Vec<T>) -> Self {
+        self.domains.insert(var.id, Rc::new(values));
+        self
+    }
+
+    /// Limit an unresolved [`LVar`] to `values`, which must be sorted.
+    ///
+    /// This fails if there are no values left and binds the variable if there
+    /// is only one. Otherwise the constraints watching the variable are
+    /// attempted again, so that they can narrow other domains in turn.
+    pub(crate) fn narrow_domain<T: Unify>(self, var: &LVar<T>, mut values: Vec<T>) -> Option<Self> {
+        match values.len() {
+            0 => None,
+            1 => self.unify(&Value::Var(var.clone()), &Value::new(values.pop()?)),
+            _ => {
+                let mut state = self.with_domain(var, values);
+                if let Some(constraints) = state.constraints.extract(&var.id) {
+                    constraints.into_iter().try_fold(state, State::constrain)
+                } else {
+                    Some(state)
+                }
+            }
+        }
+    }
+
+    /// The shared store of type `S` kept by goals that reason about many
+    /// variables at once, such as the [`linear`](crate::linear) constraints.
+    pub(crate) fn store<S: Any>(&self) -> Option<&S> {
+        self.stores.get(&TypeId::of::<S>())?.downcast_ref()
+    }
+
+    /// Replace the shared store of type `S`.
+    pub(crate) fn with_store<S: Any>(mut self, store: S) -> Self {
+        self.stores.insert(TypeId::of::<S>(), Rc::new(store));
+        self
+    }
+
     /**
     Generate a list of [`LVar`](crate::LVar)s in this state. This takes into account bound variables
     and constraint watches.
@@ -237,11 +307,25 @@ impl State {
         LVarList(ids)
     }
 
+    /// Returns `true` if constraints added since this state was derived from
+    /// `earlier` are still waiting on unresolved variables.
+    pub(crate) fn has_new_constraints(&self, earlier: &State) -> bool {
+        self.constraints
+            .has_values_added_since(&earlier.constraints)
+    }
+
     /** Returns `true` if the `State` has no open forks or constraints.
 
+    Constraints that are already [satisfiable](Constraint::is_satisfiable)
+    don't count as open.
+
     If ready, then a [`ReadyState`] can be derived with [`State::ready()`]. */
     pub fn is_ready(&self) -> bool {
-        self.forks.is_empty() && self.constraints.is_empty()
+        self.forks.is_empty()
+            && self
+                .constraints
+                .values()
+                .all(|constraint| constraint.is_satisfiable(self))
     }
     /** Returns a [`ReadyState`] if the `State` has no open forks or constraints. */
     pub fn ready(self) -> Option<ReadyState> {
@@ -26,10 +26,12 @@ pub trait StateIterator: 'static {
 
 impl StateIterator for State {
     fn into_states(mut self) -> StateIter {
-        let fork = self.forks.pop_front();
-        match fork {
+        match self.fork_policy.select(&self) {
             None => Box::new(once(self)),
-            Some(fork) => Box::new(fork.fork(&self).flat_map(StateIterator::into_states)),
+            Some(index) => {
+                let fork = self.forks.remove(index);
+                Box::new(fork.fork(&self).flat_map(StateIterator::into_states))
+            }
         }
     }
 }
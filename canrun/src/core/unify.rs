@@ -2,7 +2,7 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::rc::Rc;
 
-use crate::core::State;
+use crate::core::{State, Value};
 
 /**
 How compatible values are matched with each other.
@@ -89,3 +89,86 @@ macro_rules! impl_unify_eq {
 impl_unify_eq!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize, f32, f64);
 impl_unify_eq!(String, &'static str, bool, char);
 impl_unify_eq!(std::ops::Range<usize>);
+
+impl<T: Unify> Unify for Option<Value<T>> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        match (a.as_ref(), b.as_ref()) {
+            (Some(a), Some(b)) => state.unify(a, b),
+            (None, None) => Some(state),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Unify, E: Unify> Unify for Result<Value<T>, Value<E>> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        match (a.as_ref(), b.as_ref()) {
+            (Ok(a), Ok(b)) => state.unify(a, b),
+            (Err(a), Err(b)) => state.unify(a, b),
+            _ => None,
+        }
+    }
+}
+
+/// Since [`Unify::unify`] works with `Rc<T>`, the boxed values need to be
+/// cloned out of their boxes.
+impl<T: Unify + Clone> Unify for Box<T> {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        T::unify(
+            state,
+            Rc::new(a.as_ref().as_ref().clone()),
+            Rc::new(b.as_ref().as_ref().clone()),
+        )
+    }
+}
+
+impl<T: Unify, const N: usize> Unify for [Value<T>; N] {
+    fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+        a.iter()
+            .zip(b.iter())
+            .try_fold(state, |state, (a, b)| state.unify(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{goals::unify, LVar, Query, Value};
+
+    #[test]
+    fn unify_option() {
+        let x = LVar::new();
+        let goal = unify(Some(Value::Var(x)), Some(Value::new(1)));
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![1]);
+
+        let none: Option<Value<i32>> = None;
+        assert_eq!(unify(none.clone(), none.clone()).query(1).count(), 1);
+        assert_eq!(unify(Some(Value::new(1)), none).query(1).count(), 0);
+    }
+
+    #[test]
+    fn unify_result() {
+        let x = LVar::new();
+        let ok: Result<Value<i32>, Value<String>> = Ok(Value::new(1));
+        let goal = unify(Ok(x.into()), ok.clone());
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![1]);
+
+        let err = Err(Value::new("nope".to_string()));
+        assert_eq!(unify(ok, err).query(1).count(), 0);
+    }
+
+    #[test]
+    fn unify_box() {
+        let x = LVar::new();
+        let goal = unify(Box::new(Some(Value::Var(x))), Box::new(Some(Value::new(1))));
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn unify_array() {
+        let x = LVar::new();
+        let goal = unify([Value::new(1), x.into()], [Value::new(1), Value::new(2)]);
+        assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![2]);
+        let goal = unify([Value::new(1), x.into()], [Value::new(2), Value::new(2)]);
+        assert_eq!(goal.query(x).count(), 0);
+    }
+}
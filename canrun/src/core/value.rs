@@ -37,6 +37,20 @@ impl<T> PartialEq for LVar<T> {
     }
 }
 
+impl<T> PartialOrd for LVar<T> {
+    fn partial_cmp(&self, other: &LVar<T>) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Variables are ordered by creation, which allows [`Value`]s to be used as
+/// keys in ordered collections.
+impl<T> Ord for LVar<T> {
+    fn cmp(&self, other: &LVar<T>) -> std::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
 impl<T> Hash for LVar<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state);
@@ -101,7 +115,7 @@ impl<T: Unify> Default for LVar<T> {
 Contain individual resolved values or variables that can be bound through
 [unification](crate::core::Unify).
 */
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Value<T: Unify> {
     /**
     A [logical variable](LVar).
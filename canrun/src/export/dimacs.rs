@@ -0,0 +1,519 @@
+/*!
+Convert boolean [`Formula`]s to [DIMACS CNF], the input format understood by
+most SAT solvers, and read their solutions back.
+
+Nested formulas are flattened with the Tseitin transformation, which adds an
+auxiliary variable for each subformula so that the output grows linearly
+with the input. [`AtMost`](Formula::AtMost) constraints use Sinz's
+sequential counter encoding. Only the variables listed by [`Cnf::vars()`]
+stand for [`LVar`]s. The rest are auxiliary, and are ignored when reading a
+solution.
+
+[DIMACS CNF]: https://jix.github.io/varisat/manual/0.2.0/formats/dimacs.html
+
+# Example:
+```
+use canrun::{all, LVar, Query};
+use canrun::goals::bool::{and, not_b};
+use canrun::export::dimacs::Cnf;
+
+let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+let goal = all![and(a, b, true), not_b(b, c)];
+let cnf = Cnf::from_goal(&goal).unwrap();
+let text = cnf.to_string();
+assert!(text.starts_with("p cnf "));
+
+// Pretend a SAT solver found this solution.
+let a_ = cnf.var(a).unwrap();
+let b_ = cnf.var(b).unwrap();
+let c_ = cnf.var(c).unwrap();
+let solution = format!("s SATISFIABLE\nv {a_} {b_} -{c_} 0\n");
+let bindings = cnf.bindings(&solution).unwrap();
+let results: Vec<_> = all![goal, bindings].query((a, b, c)).collect();
+assert_eq!(results, vec![(true, true, false)]);
+```
+*/
+
+use super::{describe, Formula, UnsupportedGoal};
+use crate::goals::{unify, All, Fail, Goal};
+use crate::LVar;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// A formula in conjunctive normal form, along with the [`LVar`] each
+/// DIMACS variable stands for.
+#[derive(Clone, Debug)]
+pub struct Cnf {
+    vars: Vec<(i64, LVar<bool>)>,
+    num_vars: i64,
+    clauses: Vec<Vec<i64>>,
+}
+
+impl Cnf {
+    /// Convert the [`Formula`] describing `goal`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnsupportedGoal`] if the goal, or one of its sub-goals,
+    /// can't be described as a [`Formula`] over `bool` variables.
+    pub fn from_goal(goal: &(impl Goal + ?Sized)) -> Result<Cnf, UnsupportedGoal> {
+        Cnf::from_formula(&describe(goal)?).ok_or_else(|| UnsupportedGoal::new(goal))
+    }
+
+    /// Convert a [`Formula`], or return `None` if it has parts that don't
+    /// stand for a `bool`, which CNF can't express.
+    pub fn from_formula(formula: &Formula) -> Option<Cnf> {
+        let mut encoder = Encoder::default();
+        encoder.assert(formula)?;
+        let mut vars: Vec<_> = encoder.vars.into_iter().map(|(var, n)| (n, var)).collect();
+        vars.sort_unstable();
+        Some(Cnf {
+            vars,
+            num_vars: encoder.num_vars,
+            clauses: encoder.clauses,
+        })
+    }
+
+    /// The number of variables, including auxiliary ones.
+    pub fn num_vars(&self) -> i64 {
+        self.num_vars
+    }
+
+    /// The clauses, each a list of non-zero literals. A negative literal is
+    /// the negation of a variable.
+    pub fn clauses(&self) -> &[Vec<i64>] {
+        &self.clauses
+    }
+
+    /// The DIMACS variable that stands for `var`, if it appears in the
+    /// formula.
+    pub fn var(&self, var: LVar<bool>) -> Option<i64> {
+        self.vars.iter().find(|(_, v)| *v == var).map(|(n, _)| *n)
+    }
+
+    /// Each DIMACS variable that stands for an [`LVar`], in order.
+    pub fn vars(&self) -> impl Iterator<Item = (i64, LVar<bool>)> + '_ {
+        self.vars.iter().copied()
+    }
+
+    /** Read a SAT solver's solution into a goal that binds each [`LVar`].
+
+    Both the SAT competition format (with `s` and `v` lines) and the plain
+    format written by `minisat` (`SAT` followed by literals) are accepted. Variables
+    missing from the solution are left unbound. An unsatisfiable result
+    becomes a goal that always fails.
+
+    # Errors
+
+    Returns a [`ParseSolutionError`] for lines that can't be read or refer
+    to variables that don't exist.
+    */
+    pub fn bindings(&self, solution: &str) -> Result<All, ParseSolutionError> {
+        let mut assignment = HashMap::new();
+        for (index, line) in solution.lines().enumerate() {
+            let error = |message: &str| ParseSolutionError {
+                line: index + 1,
+                message: message.to_string(),
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('c') {
+                continue;
+            }
+            let status = line.strip_prefix("s ").unwrap_or(line).trim();
+            match status {
+                "SATISFIABLE" | "SAT" => continue,
+                "UNSATISFIABLE" | "UNSAT" => {
+                    return Ok(All::from(vec![Box::new(Fail) as Box<dyn Goal>]));
+                }
+                _ if line.starts_with("s ") => return Err(error("unknown status")),
+                _ => {}
+            }
+            let literals = line.strip_prefix('v').unwrap_or(line);
+            for literal in literals.split_whitespace() {
+                let literal: i64 = literal.parse().map_err(|_| error("invalid literal"))?;
+                let var = match literal.checked_abs() {
+                    Some(var) if var <= self.num_vars => var,
+                    _ => return Err(error("unknown variable")),
+                };
+                if var != 0 {
+                    assignment.insert(var, literal > 0);
+                }
+            }
+        }
+        Ok(self
+            .vars
+            .iter()
+            .filter_map(|(n, var)| {
+                let value = *assignment.get(n)?;
+                Some(Box::new(unify(*var, value)) as Box<dyn Goal>)
+            })
+            .collect())
+    }
+}
+
+impl fmt::Display for Cnf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "p cnf {} {}", self.num_vars, self.clauses.len())?;
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(f, "{literal} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returned by [`Cnf::bindings()`] when a solution can't be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSolutionError {
+    line: usize,
+    message: String,
+}
+
+impl ParseSolutionError {
+    /// The line the error was found on, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseSolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on line {} of the solution", self.message, self.line)
+    }
+}
+
+impl Error for ParseSolutionError {}
+
+#[derive(Default)]
+struct Encoder {
+    vars: HashMap<LVar<bool>, i64>,
+    num_vars: i64,
+    clauses: Vec<Vec<i64>>,
+    truth: Option<i64>,
+}
+
+impl Encoder {
+    fn fresh(&mut self) -> i64 {
+        self.num_vars += 1;
+        self.num_vars
+    }
+
+    fn var(&mut self, var: LVar<bool>) -> i64 {
+        if let Some(n) = self.vars.get(&var) {
+            return *n;
+        }
+        let n = self.fresh();
+        self.vars.insert(var, n);
+        n
+    }
+
+    /// A variable that is always true, for encoding constants.
+    fn truth(&mut self) -> i64 {
+        if let Some(truth) = self.truth {
+            return truth;
+        }
+        let truth = self.fresh();
+        self.clauses.push(vec![truth]);
+        self.truth = Some(truth);
+        truth
+    }
+
+    /// Add clauses that require `formula` to be true. This avoids
+    /// auxiliary variables for the top level of the formula where it can.
+    fn assert(&mut self, formula: &Formula) -> Option<()> {
+        match formula {
+            Formula::Bool(true) => {}
+            Formula::Bool(false) => self.clauses.push(vec![]),
+            Formula::And(formulas) => {
+                for formula in formulas {
+                    self.assert(formula)?;
+                }
+            }
+            Formula::Or(formulas) => {
+                let clause = self.literals(formulas)?;
+                self.clauses.push(clause);
+            }
+            Formula::AtMost(formulas, k) => {
+                let literals = self.literals(formulas)?;
+                self.sequential_counter(&literals, *k);
+            }
+            formula => {
+                let literal = self.literal(formula)?;
+                self.clauses.push(vec![literal]);
+            }
+        }
+        Some(())
+    }
+
+    fn literals(&mut self, formulas: &[Formula]) -> Option<Vec<i64>> {
+        formulas.iter().map(|f| self.literal(f)).collect()
+    }
+
+    /// A literal that is true exactly when `formula` is, or `None` if it
+    /// compares numeric terms.
+    fn literal(&mut self, formula: &Formula) -> Option<i64> {
+        Some(match formula {
+            Formula::Bool(value) => {
+                let truth = self.truth();
+                if *value {
+                    truth
+                } else {
+                    -truth
+                }
+            }
+            Formula::Var(var) => self.var(*var),
+            Formula::Not(formula) => -self.literal(formula)?,
+            Formula::And(formulas) => {
+                let literals = self.literals(formulas)?;
+                self.and_gate(&literals)
+            }
+            Formula::Or(formulas) => {
+                let literals: Vec<_> = self.literals(formulas)?.iter().map(|l| -l).collect();
+                -self.and_gate(&literals)
+            }
+            Formula::Xor(a, b) => {
+                let (a, b) = (self.literal(a)?, self.literal(b)?);
+                self.xor_gate(a, b)
+            }
+            Formula::Iff(a, b) => {
+                let (a, b) = (self.literal(a)?, self.literal(b)?);
+                -self.xor_gate(a, b)
+            }
+            Formula::AtMost(formulas, k) => {
+                let literals = self.literals(formulas)?;
+                self.counter_gate(&literals, *k)
+            }
+            Formula::Eq(..) | Formula::Lt(..) | Formula::Le(..) => return None,
+        })
+    }
+
+    fn and_gate(&mut self, literals: &[i64]) -> i64 {
+        match literals {
+            [] => return self.truth(),
+            [literal] => return *literal,
+            _ => {}
+        }
+        let gate = self.fresh();
+        let mut clause = vec![gate];
+        for literal in literals {
+            self.clauses.push(vec![-gate, *literal]);
+            clause.push(-literal);
+        }
+        self.clauses.push(clause);
+        gate
+    }
+
+    fn xor_gate(&mut self, a: i64, b: i64) -> i64 {
+        let gate = self.fresh();
+        self.clauses.extend([
+            vec![-gate, a, b],
+            vec![-gate, -a, -b],
+            vec![gate, -a, b],
+            vec![gate, a, -b],
+        ]);
+        gate
+    }
+
+    /// Require at most `k` of `literals` to be true, with Sinz's sequential
+    /// counter. `s[i][j]` is implied when at least `j + 1` of the first
+    /// `i + 1` literals are true.
+    fn sequential_counter(&mut self, literals: &[i64], k: usize) {
+        let n = literals.len();
+        if k >= n {
+            return;
+        }
+        if k == 0 {
+            for literal in literals {
+                self.clauses.push(vec![-literal]);
+            }
+            return;
+        }
+        let s: Vec<Vec<i64>> = (0..n - 1)
+            .map(|_| (0..k).map(|_| self.fresh()).collect())
+            .collect();
+        self.clauses.push(vec![-literals[0], s[0][0]]);
+        for aux in &s[0][1..] {
+            self.clauses.push(vec![-aux]);
+        }
+        for i in 1..n - 1 {
+            let x = literals[i];
+            self.clauses.push(vec![-x, s[i][0]]);
+            self.clauses.push(vec![-s[i - 1][0], s[i][0]]);
+            for j in 1..k {
+                self.clauses.push(vec![-x, -s[i - 1][j - 1], s[i][j]]);
+                self.clauses.push(vec![-s[i - 1][j], s[i][j]]);
+            }
+            self.clauses.push(vec![-x, -s[i - 1][k - 1]]);
+        }
+        self.clauses.push(vec![-literals[n - 1], -s[n - 2][k - 1]]);
+    }
+
+    /// A literal that is true exactly when at most `k` of `literals` are.
+    /// Unlike [`sequential_counter`](Self::sequential_counter), every count
+    /// is fully defined so that the result can be negated.
+    fn counter_gate(&mut self, literals: &[i64], k: usize) -> i64 {
+        if k >= literals.len() {
+            return self.truth();
+        }
+        // `at_least[j]` is true when at least `j + 1` literals are true.
+        let no = -self.truth();
+        let mut at_least = vec![no; k + 1];
+        for &x in literals {
+            let mut next = Vec::with_capacity(k + 1);
+            for j in 0..=k {
+                let carried = if j == 0 {
+                    x
+                } else {
+                    self.and_gate(&[x, at_least[j - 1]])
+                };
+                next.push(-self.and_gate(&[-at_least[j], -carried]));
+            }
+            at_least = next;
+        }
+        -at_least[k]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cnf;
+    use crate::export::Formula;
+    use crate::goals::bool::{at_most_k, xor};
+    use crate::goals::Goal;
+    use crate::{all, any, custom, lvec, unify, LVar, Query};
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    /// A small DPLL solver, so that the encodings can be checked without an
+    /// external one.
+    fn satisfiable(clauses: &[Vec<i64>], mut assignment: HashMap<i64, bool>) -> bool {
+        loop {
+            let mut changed = false;
+            let mut branch = None;
+            for clause in clauses {
+                let value =
+                    |literal: &i64| assignment.get(&literal.abs()).map(|v| *v == (*literal > 0));
+                if clause.iter().any(|l| value(l) == Some(true)) {
+                    continue;
+                }
+                let open: Vec<_> = clause.iter().filter(|l| value(l).is_none()).collect();
+                match open[..] {
+                    [] => return false,
+                    [literal] => {
+                        assignment.insert(literal.abs(), *literal > 0);
+                        changed = true;
+                    }
+                    [literal, ..] => branch = Some(literal.abs()),
+                }
+            }
+            if !changed {
+                let Some(var) = branch else { return true };
+                return [true, false].into_iter().any(|value| {
+                    let mut assignment = assignment.clone();
+                    assignment.insert(var, value);
+                    satisfiable(clauses, assignment)
+                });
+            }
+        }
+    }
+
+    /// Find every assignment of the [`LVar`]s that can be extended to
+    /// satisfy `cnf`.
+    fn solutions(cnf: &Cnf) -> Vec<Vec<bool>> {
+        let vars: Vec<_> = cnf.vars().map(|(n, _)| n).collect();
+        (0..1_u32 << vars.len())
+            .map(|bits| {
+                (0..vars.len())
+                    .map(|i| bits & (1 << i) != 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|values| {
+                let assignment = vars.iter().copied().zip(values.iter().copied()).collect();
+                satisfiable(cnf.clauses(), assignment)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encodes_goals() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goal = any![all![unify(a, true), unify(b, false)], xor(a, b, false)];
+        let cnf = Cnf::from_goal(&goal).unwrap();
+        assert_eq!(cnf.vars().map(|(_, v)| v).collect::<Vec<_>>(), vec![a, b]);
+        assert_eq!(
+            solutions(&cnf),
+            vec![vec![false, false], vec![true, false], vec![true, true]]
+        );
+    }
+
+    #[test]
+    fn encodes_at_most() {
+        let vars: Vec<LVar<bool>> = (0..4).map(|_| LVar::new()).collect();
+        let formulas: Vec<Formula> = vars.iter().map(|v| Formula::Var(*v)).collect();
+        let count = |values: &Vec<bool>| values.iter().filter(|v| **v).count();
+        for k in 0..4 {
+            let cnf = Cnf::from_formula(&Formula::AtMost(formulas.clone(), k)).unwrap();
+            let found = solutions(&cnf);
+            assert_eq!(found.len(), (0..=k).map(|i| [1, 4, 6, 4][i]).sum());
+            assert!(found.iter().all(|values| count(values) <= k));
+
+            let negated = Formula::Not(Box::new(Formula::AtMost(formulas.clone(), k)));
+            let cnf = Cnf::from_formula(&Formula::And(vec![
+                Formula::Var(vars[0]),
+                Formula::Var(vars[1]),
+                Formula::Var(vars[2]),
+                Formula::Var(vars[3]),
+                negated,
+            ]))
+            .unwrap();
+            assert_eq!(solutions(&cnf).len(), 1);
+        }
+        let goal = at_most_k(lvec![vars[0], vars[1]], 1);
+        let cnf = Cnf::from_goal(&goal).unwrap();
+        assert_eq!(solutions(&cnf).len(), 3);
+    }
+
+    #[test]
+    fn reads_solutions() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goal = xor(a, b, true);
+        let cnf = Cnf::from_goal(&goal).unwrap();
+        let (na, nb) = (cnf.var(a).unwrap(), cnf.var(b).unwrap());
+
+        let bindings = cnf.bindings(&format!("SAT\n-{na} {nb} 0\n")).unwrap();
+        let results: Vec<_> = all![goal, bindings].query((a, b)).collect();
+        assert_eq!(results, vec![(false, true)]);
+
+        let bindings = cnf.bindings("c no luck\ns UNSATISFIABLE\n").unwrap();
+        assert_eq!(all![xor(a, b, true), bindings].query((a, b)).count(), 0);
+
+        let error = cnf.bindings("s SATISFIABLE\nv 1 x 0\n").unwrap_err();
+        assert_eq!(error.line(), 2);
+        assert!(cnf.bindings("v 99 0").is_err());
+        assert!(cnf.bindings("v -9223372036854775808 0").is_err());
+        assert!(cnf.bindings("v 9223372036854775807 0").is_err());
+    }
+
+    #[test]
+    fn describes_wrapped_goals() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goal = all![
+            Rc::new(unify(a, true)),
+            Box::new(unify(b, false)),
+            Rc::new(xor(a, b, true)) as Rc<dyn Goal>,
+        ];
+        let cnf = Cnf::from_goal(&goal).unwrap();
+        assert_eq!(solutions(&cnf), vec![vec![true, false]]);
+    }
+
+    #[test]
+    fn rejects_opaque_goals() {
+        let x = LVar::new();
+        let goal = all![unify(x, true), custom(Some)];
+        assert!(Cnf::from_goal(&goal).is_err());
+        assert!(Cnf::from_goal(&unify(LVar::new(), 1)).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+/*!
+Describe goals as formulas that can be handed off to external solvers.
+
+Goals are usually opaque, but the ones built from
+[`unify`](crate::unify()), [`both`](crate::both()),
+[`either`](crate::either()), [`all!`](crate::all), [`any!`](crate::any),
+[domains](crate::domain), the [comparisons](crate::cmp), the
+[boolean relations](crate::goals::bool) and most of the
+[operators](crate::ops) implement [`Describe`], which turns them into a
+[`Formula`]. Anything else, such as a [`custom`](crate::custom) goal or a
+[`project`](crate::project) closure, makes [`Describe::formula()`] return an
+[`UnsupportedGoal`] error instead. So do [`rem`](crate::ops::rem) and
+[`pow`](crate::ops::pow), which are built on [`project`](crate::project),
+and [`sum`](crate::ops::sum) and [`product`](crate::ops::product), which are
+built on [`fold`](crate::lvec::fold).
+
+Values other than `bool` are described as [`NumExpr`]s, which are limited to
+the primitive integer types and [`Rational`]. Floats aren't supported.
+
+- [`dimacs`] converts formulas over `bool` variables to DIMACS CNF for SAT
+  solvers, and reads their solutions back into goals.
+- [`smtlib`] converts any formula to SMT-LIB v2 for SMT solvers.
+
+# Example:
+```
+use canrun::{any, unify, LVar};
+use canrun::export::{Describe, Formula};
+
+let x = LVar::new();
+let goal = any![unify(x, true), unify(x, false)];
+let formula = goal.formula().unwrap();
+assert_eq!(
+    formula,
+    Formula::Or(vec![
+        Formula::iff(x.into(), Formula::Bool(true)),
+        Formula::iff(x.into(), Formula::Bool(false)),
+    ])
+);
+```
+*/
+
+pub mod dimacs;
+pub mod smtlib;
+
+use crate::core::VarId;
+use crate::goals::Goal;
+use crate::linear::Rational;
+use crate::{LVar, Unify, Value};
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+/** A [`Goal`] that can be described as a [`Formula`].
+
+Goals that hold other goals find out whether those can be described through
+[`Goal::describe()`], so implementors should also return `Some(self)` from
+it.
+
+# Example:
+```
+use canrun::{unify, LVar};
+use canrun::export::{Describe, Formula};
+
+let x = LVar::new();
+assert_eq!(
+    unify(x, true).formula(),
+    Ok(Formula::iff(x.into(), Formula::Bool(true)))
+);
+assert!(unify(LVar::new(), 1.5).formula().is_err());
+```
+*/
+pub trait Describe: Goal {
+    /// Describe the goal as a [`Formula`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnsupportedGoal`] if the goal, or one of its sub-goals,
+    /// can't be described.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal>;
+}
+
+/// Describe any goal, failing if it doesn't implement [`Describe`].
+pub(crate) fn describe(goal: &(impl Goal + ?Sized)) -> Result<Formula, UnsupportedGoal> {
+    goal.describe()
+        .ok_or_else(|| UnsupportedGoal::new(goal))?
+        .formula()
+}
+
+/// A logical formula, as described by [`Describe::formula()`].
+///
+/// More kinds of formulas may be added, so matches on it need a wildcard
+/// arm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Formula {
+    /// A constant.
+    Bool(bool),
+    /// A variable.
+    Var(LVar<bool>),
+    /// True if the inner formula is false.
+    Not(Box<Formula>),
+    /// True if every formula is true. An empty list is true.
+    And(Vec<Formula>),
+    /// True if any formula is true. An empty list is false.
+    Or(Vec<Formula>),
+    /// True if exactly one of the formulas is true.
+    Xor(Box<Formula>, Box<Formula>),
+    /// True if both formulas have the same value.
+    Iff(Box<Formula>, Box<Formula>),
+    /// True if at most this many of the formulas are true.
+    AtMost(Vec<Formula>, usize),
+    /// True if the expressions are equal.
+    Eq(NumExpr, NumExpr),
+    /// True if the first expression is less than the second.
+    Lt(NumExpr, NumExpr),
+    /// True if the first expression is less than or equal to the
+    /// second.
+    Le(NumExpr, NumExpr),
+}
+
+impl Formula {
+    /// Shorthand for [`Formula::Xor`].
+    pub fn xor(a: Formula, b: Formula) -> Formula {
+        Formula::Xor(Box::new(a), Box::new(b))
+    }
+
+    /// Shorthand for [`Formula::Iff`].
+    pub fn iff(a: Formula, b: Formula) -> Formula {
+        Formula::Iff(Box::new(a), Box::new(b))
+    }
+}
+
+impl From<LVar<bool>> for Formula {
+    fn from(var: LVar<bool>) -> Formula {
+        Formula::Var(var)
+    }
+}
+
+impl From<&Value<bool>> for Formula {
+    fn from(value: &Value<bool>) -> Formula {
+        match value {
+            Value::Var(var) => Formula::Var(*var),
+            Value::Resolved(value) => Formula::Bool(**value),
+        }
+    }
+}
+
+/// A numeric expression in a [`Formula`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NumExpr {
+    /// An integer constant.
+    Int(i128),
+    /// A rational constant.
+    Real(Rational),
+    /// A variable.
+    Var(NumVar),
+    /// The sum of two expressions.
+    Add(Box<NumExpr>, Box<NumExpr>),
+    /// The first expression minus the second.
+    Sub(Box<NumExpr>, Box<NumExpr>),
+    /// The product of two expressions.
+    Mul(Box<NumExpr>, Box<NumExpr>),
+    /// The negation of an expression.
+    Neg(Box<NumExpr>),
+}
+
+/// A numeric [`LVar`] in a [`NumExpr`], with the type information erased.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NumVar {
+    id: VarId,
+    sort: Sort,
+}
+
+impl NumVar {
+    /// The kind of number the variable stands for.
+    pub fn sort(&self) -> Sort {
+        self.sort
+    }
+
+    /// Returns true if this stands for `var`.
+    pub fn is<T>(&self, var: &LVar<T>) -> bool {
+        self.id == var.id
+    }
+}
+
+/// The kind of number a [`NumVar`] stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sort {
+    /// An integer within the range of its primitive type.
+    Int {
+        /// The smallest possible value.
+        min: i128,
+        /// The largest possible value.
+        max: i128,
+    },
+    /// A [`Rational`].
+    Real,
+}
+
+macro_rules! num_expr_of_ints {
+    ($value:expr, $($type:ty),+) => {
+        $(
+            if let Some(value) = $value.downcast_ref::<Value<$type>>() {
+                return match value {
+                    Value::Var(var) => Some(NumExpr::Var(NumVar {
+                        id: var.id,
+                        sort: Sort::Int {
+                            min: i128::try_from(<$type>::MIN).ok()?,
+                            max: i128::try_from(<$type>::MAX).ok()?,
+                        },
+                    })),
+                    Value::Resolved(value) => Some(NumExpr::Int(i128::try_from(**value).ok()?)),
+                };
+            }
+        )+
+    };
+}
+
+impl NumExpr {
+    /// Zero, as an expression of the same sort as this one.
+    #[must_use]
+    pub fn zero_like(&self) -> NumExpr {
+        match self {
+            NumExpr::Int(_) => NumExpr::Int(0),
+            NumExpr::Real(_) => NumExpr::Real(Rational::ZERO),
+            NumExpr::Var(var) => match var.sort {
+                Sort::Int { .. } => NumExpr::Int(0),
+                Sort::Real => NumExpr::Real(Rational::ZERO),
+            },
+            NumExpr::Add(expr, _)
+            | NumExpr::Sub(expr, _)
+            | NumExpr::Mul(expr, _)
+            | NumExpr::Neg(expr) => expr.zero_like(),
+        }
+    }
+
+    /// Describe `value` as an expression, if it is a primitive integer or a
+    /// [`Rational`].
+    pub fn of<T: Unify>(value: &Value<T>) -> Option<NumExpr> {
+        let value: &dyn Any = value;
+        num_expr_of_ints!(value, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+        match value.downcast_ref::<Value<Rational>>()? {
+            Value::Var(var) => Some(NumExpr::Var(NumVar {
+                id: var.id,
+                sort: Sort::Real,
+            })),
+            Value::Resolved(value) => Some(NumExpr::Real(**value)),
+        }
+    }
+}
+
+macro_rules! impl_num_expr_ops {
+    ($($trait:ident $method:ident $variant:ident),+) => {
+        $(
+            impl std::ops::$trait for NumExpr {
+                type Output = NumExpr;
+                fn $method(self, other: NumExpr) -> NumExpr {
+                    NumExpr::$variant(Box::new(self), Box::new(other))
+                }
+            }
+        )+
+    };
+}
+
+impl_num_expr_ops!(Add add Add, Sub sub Sub, Mul mul Mul);
+
+impl std::ops::Neg for NumExpr {
+    type Output = NumExpr;
+    fn neg(self) -> NumExpr {
+        NumExpr::Neg(Box::new(self))
+    }
+}
+
+/// Returned by [`Describe::formula()`] for goals that can't be described as a
+/// [`Formula`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedGoal {
+    goal: String,
+}
+
+impl UnsupportedGoal {
+    /// Create an error for `goal`, which is described with its `Debug`
+    /// output.
+    pub fn new(goal: &(impl Goal + ?Sized)) -> Self {
+        UnsupportedGoal {
+            goal: format!("{goal:?}"),
+        }
+    }
+
+    /// The `Debug` output of the unsupported goal.
+    pub fn goal(&self) -> &str {
+        &self.goal
+    }
+}
+
+impl fmt::Display for UnsupportedGoal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "goal can't be exported: {}", self.goal)
+    }
+}
+
+impl Error for UnsupportedGoal {}
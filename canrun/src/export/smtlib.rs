@@ -0,0 +1,276 @@
+/*!
+Convert [`Formula`]s to [SMT-LIB v2] scripts, which can be checked by SMT
+solvers such as Z3 or cvc5.
+
+Each variable becomes a constant declared with `declare-const`, named after
+the order it first appears in (`v1`, `v2` and so on). Use [`SmtLib::name()`]
+to find the name of an [`LVar`]. Integer variables are declared as `Int`,
+with their range limited to that of their primitive type. Note that the
+solver's arithmetic doesn't overflow, so intermediate results are not
+limited in the same way as they are in canrun. [`Rational`]s are declared
+as `Real`.
+
+[SMT-LIB v2]: https://smt-lib.org/language.shtml
+[`Rational`]: crate::linear::Rational
+
+# Example:
+```
+use canrun::{all, unify, LVar};
+use canrun::cmp::lt;
+use canrun::ops::add;
+use canrun::export::smtlib::SmtLib;
+
+let (x, y) = (LVar::<i32>::new(), LVar::new());
+let goal = all![add(x, 1, y), lt(y, 10), unify(x, 3)];
+let smt = SmtLib::from_goal(&goal).unwrap();
+assert_eq!(
+    smt.to_string(),
+    "\
+(declare-const v1 Int)
+(assert (and (<= (- 2147483648) v1) (<= v1 2147483647)))
+(declare-const v2 Int)
+(assert (and (<= (- 2147483648) v2) (<= v2 2147483647)))
+(assert (= (+ v1 1) v2))
+(assert (< v2 10))
+(assert (= v1 3))
+(check-sat)
+"
+);
+assert_eq!(smt.name(&y), Some("v2"));
+```
+*/
+
+use super::{describe, Formula, NumExpr, NumVar, Sort, UnsupportedGoal};
+use crate::core::VarId;
+use crate::goals::Goal;
+use crate::LVar;
+use std::fmt::{self, Write};
+
+/// An SMT-LIB v2 script that checks whether a [`Formula`] is satisfiable.
+#[derive(Clone, Debug)]
+pub struct SmtLib {
+    names: Vec<(VarId, String)>,
+    script: String,
+}
+
+impl SmtLib {
+    /// Convert the [`Formula`] describing `goal`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnsupportedGoal`] if the goal, or one of its sub-goals,
+    /// can't be described as a [`Formula`].
+    pub fn from_goal(goal: &(impl Goal + ?Sized)) -> Result<SmtLib, UnsupportedGoal> {
+        Ok(SmtLib::from_formula(&describe(goal)?))
+    }
+
+    /// Convert a [`Formula`].
+    pub fn from_formula(formula: &Formula) -> SmtLib {
+        let mut writer = Writer::default();
+        for formula in conjuncts(formula) {
+            let expr = writer.formula(formula);
+            writer.declarations.push(format!("(assert {expr})"));
+        }
+        writer.declarations.push("(check-sat)".to_string());
+        let mut script = String::new();
+        for line in &writer.declarations {
+            writeln!(script, "{line}").expect("writing to a string can't fail");
+        }
+        SmtLib {
+            names: writer.names,
+            script,
+        }
+    }
+
+    /// The name of the constant declared for `var`, if it appears in the
+    /// formula.
+    pub fn name<T>(&self, var: &LVar<T>) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|(id, _)| *id == var.id)
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl fmt::Display for SmtLib {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.script)
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    names: Vec<(VarId, String)>,
+    declarations: Vec<String>,
+}
+
+/// The parts of a conjunction, with nested ones flattened and constant
+/// `true`s (such as the ones added by [`ops`](crate::ops) goals) left out.
+fn conjuncts(formula: &Formula) -> Vec<&Formula> {
+    match formula {
+        Formula::And(formulas) => formulas.iter().flat_map(conjuncts).collect(),
+        Formula::Bool(true) => vec![],
+        formula => vec![formula],
+    }
+}
+
+/// Apply `op` to `args`, which SMT-LIB requires at least two of for the
+/// variadic operators.
+fn apply(op: &str, args: &[String], empty: &str) -> String {
+    match args {
+        [] => empty.to_string(),
+        [arg] => arg.clone(),
+        args => format!("({op} {})", args.join(" ")),
+    }
+}
+
+fn int(value: i128) -> String {
+    if value < 0 {
+        format!("(- {})", value.unsigned_abs())
+    } else {
+        value.to_string()
+    }
+}
+
+impl Writer {
+    /// The name for the variable with `id`, which is declared with `sort`
+    /// the first time it is seen.
+    fn var(&mut self, id: VarId, sort: &str, bounds: Option<(i128, i128)>) -> String {
+        if let Some((_, name)) = self.names.iter().find(|(v, _)| *v == id) {
+            return name.clone();
+        }
+        let name = format!("v{}", self.names.len() + 1);
+        self.declarations
+            .push(format!("(declare-const {name} {sort})"));
+        if let Some((min, max)) = bounds {
+            self.declarations.push(format!(
+                "(assert (and (<= {} {name}) (<= {name} {})))",
+                int(min),
+                int(max)
+            ));
+        }
+        self.names.push((id, name.clone()));
+        name
+    }
+
+    fn formula(&mut self, formula: &Formula) -> String {
+        match formula {
+            Formula::Bool(value) => value.to_string(),
+            Formula::Var(var) => self.var(var.id, "Bool", None),
+            Formula::Not(f) => format!("(not {})", self.formula(f)),
+            Formula::And(_) => {
+                let args: Vec<_> = conjuncts(formula)
+                    .into_iter()
+                    .map(|f| self.formula(f))
+                    .collect();
+                apply("and", &args, "true")
+            }
+            Formula::Or(fs) => {
+                let args: Vec<_> = fs.iter().map(|f| self.formula(f)).collect();
+                apply("or", &args, "false")
+            }
+            Formula::Xor(a, b) => format!("(xor {} {})", self.formula(a), self.formula(b)),
+            Formula::Iff(a, b) => format!("(= {} {})", self.formula(a), self.formula(b)),
+            Formula::AtMost(fs, k) => {
+                let args: Vec<_> = fs
+                    .iter()
+                    .map(|f| format!("(ite {} 1 0)", self.formula(f)))
+                    .collect();
+                format!("(<= {} {k})", apply("+", &args, "0"))
+            }
+            Formula::Eq(a, b) => format!("(= {} {})", self.expr(a), self.expr(b)),
+            Formula::Lt(a, b) => format!("(< {} {})", self.expr(a), self.expr(b)),
+            Formula::Le(a, b) => format!("(<= {} {})", self.expr(a), self.expr(b)),
+        }
+    }
+
+    fn expr(&mut self, expr: &NumExpr) -> String {
+        match expr {
+            NumExpr::Int(value) => int(*value),
+            NumExpr::Real(value) => {
+                let abs = if value.is_integer() {
+                    format!("{}.0", value.numer().unsigned_abs())
+                } else {
+                    format!("(/ {}.0 {}.0)", value.numer().unsigned_abs(), value.denom())
+                };
+                if value.numer() < 0 {
+                    format!("(- {abs})")
+                } else {
+                    abs
+                }
+            }
+            NumExpr::Var(NumVar { id, sort }) => match sort {
+                Sort::Int { min, max } => self.var(*id, "Int", Some((*min, *max))),
+                Sort::Real => self.var(*id, "Real", None),
+            },
+            NumExpr::Add(a, b) => format!("(+ {} {})", self.expr(a), self.expr(b)),
+            NumExpr::Sub(a, b) => format!("(- {} {})", self.expr(a), self.expr(b)),
+            NumExpr::Mul(a, b) => format!("(* {} {})", self.expr(a), self.expr(b)),
+            NumExpr::Neg(a) => format!("(- {})", self.expr(a)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmtLib;
+    use crate::cmp::{gte, max, max_of};
+    use crate::goals::bool::{at_most_k, or};
+    use crate::linear::Rational;
+    use crate::ops::{div, rem, sum};
+    use crate::{any, custom, lvec, unify, LVar};
+
+    #[test]
+    fn writes_booleans() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goal = any![or(a, b, true), at_most_k(lvec![a, b], 1)];
+        let smt = SmtLib::from_goal(&goal).unwrap();
+        assert_eq!(
+            smt.to_string(),
+            "\
+(declare-const v1 Bool)
+(declare-const v2 Bool)
+(assert (or (= true (or v1 v2)) (<= (+ (ite v1 1 0) (ite v2 1 0)) 1)))
+(check-sat)
+"
+        );
+    }
+
+    #[test]
+    fn writes_arithmetic() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let goal = crate::all![div(x, Rational::new(-1, 2), y), gte(y, Rational::ZERO)];
+        let smt = SmtLib::from_goal(&goal).unwrap();
+        assert_eq!(
+            smt.to_string(),
+            "\
+(assert (not (= (- (/ 1.0 2.0)) 0.0)))
+(declare-const v1 Real)
+(declare-const v2 Real)
+(assert (= v1 (* (- (/ 1.0 2.0)) v2)))
+(assert (<= 0.0 v2))
+(check-sat)
+"
+        );
+    }
+
+    #[test]
+    fn unrolls_resolved_collections() {
+        let (x, m) = (LVar::<u8>::new(), LVar::new());
+        let goal = crate::all![max_of(lvec![x, 2], m), max(x, 4, m)];
+        let smt = SmtLib::from_goal(&goal).unwrap().to_string();
+        assert!(smt.contains("(assert (and (<= 0 v1) (<= v1 255)))"));
+        assert_eq!(smt.matches("declare-const").count(), 3);
+        assert!(SmtLib::from_goal(&sum(lvec![x, 2], 5)).is_err());
+    }
+
+    #[test]
+    fn rejects_opaque_goals() {
+        let x = LVar::<i32>::new();
+        let error = SmtLib::from_goal(&any![unify(x, 1), custom(Some)]).unwrap_err();
+        assert!(error.goal().starts_with("Custom"));
+        assert!(SmtLib::from_goal(&rem(x, 2, 1)).is_err());
+        let xs = LVar::new();
+        assert!(SmtLib::from_goal(&sum(xs, x)).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+/*!
+Wrappers that make floating point numbers easier to reason about.
+
+The primitive `f32` and `f64` types [unify](crate::Unify) only when they
+are exactly equal. That makes the results of float arithmetic fail to
+unify with the constants you'd expect (`0.1 + 0.2` is not `0.3`), and NaN
+never unifies with anything, not even itself.
+
+- [`Approx`] unifies values that are within a [`Tolerance`] of each other,
+  and works with the [`ops`](crate::ops) goals.
+- [`Total`] orders and compares floats by their bits with
+  [`total_cmp`](f64::total_cmp), so it can be used where `Eq`, `Ord` and
+  `Hash` are needed, such as [`LMap`](crate::lmap::LMap) keys.
+
+Both treat NaN as equal to itself.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::float::Approx;
+use canrun::ops::add;
+
+let x = LVar::new();
+let goal = add(Approx::new(0.1), Approx::new(0.2), x);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![Approx::new(0.3)]);
+```
+*/
+
+use crate::core::{ReadyState, Reify, State, Unify};
+use crate::ops::CheckedArith;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::Rc;
+
+/// How close two floats have to be for an [`Approx`] to consider them equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tolerance<T> {
+    /// The absolute difference between the values is at most this much.
+    Epsilon(T),
+    /// There are at most this many representable floats between the values.
+    Ulps(u32),
+}
+
+/** A float that unifies with any value within a [`Tolerance`] of it.
+
+Values created with [`Approx::new`] allow a difference of four
+[ULPs](Tolerance::Ulps), which absorbs the rounding of a few arithmetic
+operations. Two values are equal if either of their tolerances allows it,
+and the result of arithmetic keeps the tolerance of the left operand.
+
+Note that approximate equality is not transitive, so `a == b` and `b == c`
+don't imply `a == c`.
+
+# Example:
+```
+use canrun::float::{Approx, Tolerance};
+
+assert_eq!(Approx::new(0.1 + 0.2), Approx::new(0.3));
+assert_ne!(Approx::new(1.0), Approx::new(1.001));
+assert_eq!(Approx::with_epsilon(1.0, 0.01), Approx::new(1.001));
+assert_eq!(Approx::new(f64::NAN), Approx::new(f64::NAN));
+```
+*/
+#[derive(Clone, Copy)]
+pub struct Approx<T> {
+    value: T,
+    tolerance: Tolerance<T>,
+}
+
+/** A float with a total order, so that it can be hashed and used as a map
+key.
+
+Values are compared with [`total_cmp`](f64::total_cmp), which means that
+NaN is equal to itself and `-0.0` is less than `0.0`.
+
+# Example:
+```
+use canrun::{lmap, LVar, Query};
+use canrun::float::Total;
+
+let x = LVar::new();
+let goal = canrun::unify(
+    lmap! {Total(0.5) => x},
+    lmap! {Total(0.5) => 1},
+);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1]);
+```
+*/
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Total<T>(pub T);
+
+/// The float types that can be wrapped in an [`Approx`].
+///
+/// This trait is sealed, and only implemented for `f32` and `f64`.
+pub trait Float: Copy + PartialEq + sealed::Sealed {
+    #[doc(hidden)]
+    fn is_nan(self) -> bool;
+    #[doc(hidden)]
+    fn within(self, other: Self, epsilon: Self) -> bool;
+    #[doc(hidden)]
+    fn ulps_between(self, other: Self) -> u64;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+impl<T: Float> Approx<T> {
+    /// Wrap a value with the default tolerance of four ULPs.
+    pub fn new(value: T) -> Self {
+        Approx::with_tolerance(value, Tolerance::Ulps(4))
+    }
+
+    /// Wrap a value that is equal to anything within `epsilon`.
+    pub fn with_epsilon(value: T, epsilon: T) -> Self {
+        Approx::with_tolerance(value, Tolerance::Epsilon(epsilon))
+    }
+
+    /// Wrap a value that is equal to anything within `ulps` representable
+    /// floats of it.
+    pub fn with_ulps(value: T, ulps: u32) -> Self {
+        Approx::with_tolerance(value, Tolerance::Ulps(ulps))
+    }
+
+    /// Wrap a value with the given [`Tolerance`].
+    pub fn with_tolerance(value: T, tolerance: Tolerance<T>) -> Self {
+        Approx { value, tolerance }
+    }
+
+    /// The wrapped value.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// The [`Tolerance`] used when comparing this value.
+    pub fn tolerance(&self) -> Tolerance<T> {
+        self.tolerance
+    }
+
+    fn allows(&self, other: T) -> bool {
+        let value = self.value;
+        if value.is_nan() || other.is_nan() {
+            return value.is_nan() && other.is_nan();
+        }
+        value == other
+            || match self.tolerance {
+                Tolerance::Epsilon(epsilon) => value.within(other, epsilon),
+                Tolerance::Ulps(ulps) => value.ulps_between(other) <= ulps.into(),
+            }
+    }
+
+    fn map(self, value: T) -> Self {
+        Approx { value, ..self }
+    }
+}
+
+macro_rules! impl_float_wrappers {
+    ($($float:ty, $bits:ty),+) => {
+        $(
+            impl Float for $float {
+                fn is_nan(self) -> bool {
+                    <$float>::is_nan(self)
+                }
+                fn within(self, other: Self, epsilon: Self) -> bool {
+                    (self - other).abs() <= epsilon
+                }
+                fn ulps_between(self, other: Self) -> u64 {
+                    // Map the bits onto integers that are ordered like the
+                    // floats, as in `total_cmp`.
+                    let key = |f: $float| {
+                        let bits = <$bits>::from_ne_bytes(f.to_bits().to_ne_bytes());
+                        if bits < 0 {
+                            bits ^ <$bits>::MAX
+                        } else {
+                            bits
+                        }
+                    };
+                    key(self).abs_diff(key(other)).into()
+                }
+            }
+
+            impl From<$float> for Approx<$float> {
+                fn from(value: $float) -> Self {
+                    Approx::new(value)
+                }
+            }
+
+            impl PartialEq for Approx<$float> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.allows(other.value) || other.allows(self.value)
+                }
+            }
+
+            impl PartialOrd for Approx<$float> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    if self == other {
+                        Some(Ordering::Equal)
+                    } else {
+                        self.value.partial_cmp(&other.value)
+                    }
+                }
+            }
+
+            impl fmt::Debug for Approx<$float> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "Approx({:?})", self.value)
+                }
+            }
+
+            impl fmt::Display for Approx<$float> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "~{}", self.value)
+                }
+            }
+
+            impl Add for Approx<$float> {
+                type Output = Self;
+                fn add(self, other: Self) -> Self {
+                    self.map(self.value + other.value)
+                }
+            }
+
+            impl Sub for Approx<$float> {
+                type Output = Self;
+                fn sub(self, other: Self) -> Self {
+                    self.map(self.value - other.value)
+                }
+            }
+
+            impl Mul for Approx<$float> {
+                type Output = Self;
+                fn mul(self, other: Self) -> Self {
+                    self.map(self.value * other.value)
+                }
+            }
+
+            impl Div for Approx<$float> {
+                type Output = Self;
+                fn div(self, other: Self) -> Self {
+                    self.map(self.value / other.value)
+                }
+            }
+
+            impl Neg for Approx<$float> {
+                type Output = Self;
+                fn neg(self) -> Self {
+                    self.map(-self.value)
+                }
+            }
+
+            impl CheckedArith for Approx<$float> {
+                const ZERO: Self = Approx {
+                    value: 0.0,
+                    tolerance: Tolerance::Ulps(4),
+                };
+                const ONE: Self = Approx {
+                    value: 1.0,
+                    tolerance: Tolerance::Ulps(4),
+                };
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    Some(self * other)
+                }
+                /// Fails if `other` is approximately zero.
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    if other == Self::ZERO {
+                        None
+                    } else {
+                        Some(self / other)
+                    }
+                }
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    self.value.checked_rem(other.value).map(|value| self.map(value))
+                }
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    self.value.checked_pow(exp).map(|value| self.map(value))
+                }
+            }
+
+            impl Unify for Approx<$float> {
+                fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+                    (a == b).then_some(state)
+                }
+            }
+
+            impl Reify for Approx<$float> {
+                type Reified = Self;
+                fn reify_in(&self, _: &ReadyState) -> Option<Self> {
+                    Some(*self)
+                }
+            }
+
+            impl PartialEq for Total<$float> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for Total<$float> {}
+
+            impl PartialOrd for Total<$float> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for Total<$float> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+
+            impl Hash for Total<$float> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.to_bits().hash(state);
+                }
+            }
+
+            impl Unify for Total<$float> {
+                fn unify(state: State, a: Rc<Self>, b: Rc<Self>) -> Option<State> {
+                    (a == b).then_some(state)
+                }
+            }
+
+            impl Reify for Total<$float> {
+                type Reified = Self;
+                fn reify_in(&self, _: &ReadyState) -> Option<Self> {
+                    Some(*self)
+                }
+            }
+        )+
+    };
+}
+
+impl_float_wrappers!(f32, i32, f64, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::{Approx, Tolerance, Total};
+    use crate::ops::{div, mul};
+    use crate::{goal_vec, LVar, Query};
+
+    #[test]
+    fn compares_with_tolerance() {
+        assert_eq!(Approx::new(0.1_f32 + 0.2), Approx::new(0.3));
+        assert_ne!(Approx::new(1.0), Approx::with_ulps(1.0 + 1e-12, 0));
+        assert_eq!(Approx::with_ulps(1.0 + 1e-12, 10_000), Approx::new(1.0));
+        assert_eq!(
+            Approx::with_tolerance(100.0, Tolerance::Epsilon(0.5)),
+            Approx::new(100.4)
+        );
+        assert_eq!(Approx::new(-0.0), Approx::new(0.0));
+        assert_ne!(Approx::new(f64::NAN), Approx::new(0.0));
+        assert!(Approx::new(1.0) < Approx::new(2.0));
+    }
+
+    #[test]
+    fn unifies_results_of_ops() {
+        let x = LVar::new();
+        let goals = goal_vec![mul(Approx::new(0.1), Approx::new(3.0), x)];
+        goals.assert_permutations_resolve_to(&x, vec![Approx::new(0.3)]);
+        let goal = div(Approx::new(0.3), Approx::new(0.1), Approx::new(3.0));
+        assert_eq!(goal.query(1).count(), 1);
+        let goals = goal_vec![div(Approx::new(1.0), Approx::new(0.0), x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn orders_totally() {
+        assert_eq!(Total(f64::NAN), Total(f64::NAN));
+        assert!(Total(-0.0) < Total(0.0));
+        let mut values = [Total(2.0), Total(f64::NAN), Total(-1.0)];
+        values.sort();
+        assert_eq!(values[..2], [Total(-1.0), Total(2.0)]);
+    }
+}
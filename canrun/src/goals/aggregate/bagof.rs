@@ -0,0 +1,55 @@
+use super::Aggregate;
+use crate::{goals::Goal, lvec::LVec, Reify, Unify, Value};
+
+/** Like [`findall`](super::findall), but fails if the goal has no solutions.
+
+Unlike Prolog's `bagof/3`, this does not group the solutions by the other
+free variables in the goal.
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::bagof;
+
+let (x, xs) = (LVar::new(), LVar::new());
+let goal = bagof(x, any![unify(x, 2), unify(x, 1)], &xs);
+let results: Vec<_> = goal.query(xs).collect();
+assert_eq!(results, vec![vec![2, 1]]);
+```
+*/
+pub fn bagof<Q>(
+    template: Q,
+    goal: impl Goal,
+    result: impl Into<Value<LVec<Q::Reified>>>,
+) -> Aggregate<Q::Reified, LVec<Q::Reified>>
+where
+    Q: Reify + 'static,
+    Q::Reified: Unify,
+{
+    Aggregate::of_template(
+        template,
+        goal,
+        |found| (!found.is_empty()).then(|| found.into_iter().collect()),
+        result.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bagof;
+    use crate::{any, goal_vec, unify, Fail, LVar};
+
+    #[test]
+    fn collects_solutions() {
+        let (x, xs) = (LVar::new(), LVar::new());
+        let goals = goal_vec![bagof(x, any![unify(x, 1), unify(x, 1)], &xs)];
+        goals.assert_permutations_resolve_to(&xs, vec![vec![1, 1]]);
+    }
+
+    #[test]
+    fn fails_when_no_solutions() {
+        let (x, xs) = (LVar::<i32>::new(), LVar::new());
+        let goals = goal_vec![bagof(x, Fail, &xs)];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+    }
+}
@@ -0,0 +1,48 @@
+use super::Aggregate;
+use crate::{goals::Goal, Value};
+
+/** Count the solutions of a goal.
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::count;
+
+let (x, n) = (LVar::new(), LVar::new());
+let goal = count(any![unify(x, 1), unify(x, 2)], n);
+let results: Vec<_> = goal.query(n).collect();
+assert_eq!(results, vec![2]);
+```
+*/
+pub fn count(goal: impl Goal, n: impl Into<Value<usize>>) -> Aggregate<(), usize> {
+    Aggregate::new(goal, |_| Some(()), |found| Some(found.len()), n.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count;
+    use crate::{any, goal_vec, unify, Fail, LVar, Succeed};
+
+    #[test]
+    fn counts_solutions() {
+        let (x, y, n) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(y, 1),
+            count(any![unify(x, y), unify(x, 2), unify(x, 3), unify(y, 4)], n),
+        ];
+        goals.assert_permutations_resolve_to(&n, vec![3]);
+    }
+
+    #[test]
+    fn counts_zero() {
+        let n = LVar::new();
+        let goals = goal_vec![count(Fail, n)];
+        goals.assert_permutations_resolve_to(&n, vec![0]);
+    }
+
+    #[test]
+    fn checks_known_count() {
+        let goals = goal_vec![count(any![Succeed, Succeed], 1)];
+        goals.assert_permutations_resolve_to(&1, vec![]);
+    }
+}
@@ -0,0 +1,65 @@
+use super::Aggregate;
+use crate::{goals::Goal, lvec::LVec, Reify, Unify, Value};
+
+/** Collect the template from every solution of a goal into an [`LVec`].
+
+The results are in the order the solutions are found. If there are no
+solutions, the result is an empty `LVec`.
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::findall;
+
+let (x, xs) = (LVar::new(), LVar::new());
+let goal = findall(x, any![unify(x, 1), unify(x, 2), unify(x, 1)], &xs);
+let results: Vec<_> = goal.query(xs).collect();
+assert_eq!(results, vec![vec![1, 2, 1]]);
+```
+*/
+pub fn findall<Q>(
+    template: Q,
+    goal: impl Goal,
+    result: impl Into<Value<LVec<Q::Reified>>>,
+) -> Aggregate<Q::Reified, LVec<Q::Reified>>
+where
+    Q: Reify + 'static,
+    Q::Reified: Unify,
+{
+    Aggregate::of_template(
+        template,
+        goal,
+        |found| Some(found.into_iter().collect()),
+        result.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::findall;
+    use crate::{any, goal_vec, lvec::LVec, unify, Fail, LVar};
+
+    #[test]
+    fn collects_solutions() {
+        let (x, y, xs) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(y, 2),
+            findall(x, any![unify(x, 1), unify(x, y), unify(x, 3)], &xs),
+        ];
+        goals.assert_permutations_resolve_to(&xs, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn empty_when_no_solutions() {
+        let (x, xs) = (LVar::<i32>::new(), LVar::<LVec<i32>>::new());
+        let goals = goal_vec![findall(x, Fail, &xs)];
+        goals.assert_permutations_resolve_to(&xs, vec![vec![]]);
+    }
+
+    #[test]
+    fn skips_unresolved_templates() {
+        let (x, y, xs) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![findall(x, any![unify(x, 1), unify(y, 2)], &xs)];
+        goals.assert_permutations_resolve_to(&xs, vec![vec![1]]);
+    }
+}
@@ -0,0 +1,56 @@
+use super::Aggregate;
+use crate::{goals::Goal, Reify, Unify, Value};
+
+/** Find the largest template among the solutions of a goal. Fails if there
+are no solutions.
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::max;
+
+let (x, result) = (LVar::new(), LVar::new());
+let goal = max(x, any![unify(x, 2), unify(x, 1), unify(x, 3)], result);
+let results: Vec<_> = goal.query(result).collect();
+assert_eq!(results, vec![3]);
+```
+*/
+pub fn max<Q>(
+    template: Q,
+    goal: impl Goal,
+    result: impl Into<Value<Q::Reified>>,
+) -> Aggregate<Q::Reified, Q::Reified>
+where
+    Q: Reify + 'static,
+    Q::Reified: Unify + Ord,
+{
+    Aggregate::of_template(
+        template,
+        goal,
+        |found| found.into_iter().max(),
+        result.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::max;
+    use crate::{any, goal_vec, unify, Fail, LVar};
+
+    #[test]
+    fn finds_largest() {
+        let (x, y, result) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(y, 5),
+            max(x, any![unify(x, 1), unify(x, y), unify(x, 0)], result),
+        ];
+        goals.assert_permutations_resolve_to(&result, vec![5]);
+    }
+
+    #[test]
+    fn fails_when_no_solutions() {
+        let (x, result) = (LVar::<i32>::new(), LVar::new());
+        let goals = goal_vec![max(x, Fail, result)];
+        goals.assert_permutations_resolve_to(&result, vec![]);
+    }
+}
@@ -0,0 +1,194 @@
+/*! Goals that collect every solution of a sub-goal into a single value,
+including [`findall`](findall::findall), [`bagof`](bagof::bagof),
+[`setof`](setof::setof), [`count`](count::count), [`sum`](sum::sum),
+[`min`](min::min) and [`max`](max::max).
+
+The sub-goal is run against the surrounding state, so it sees any bindings
+made by the other goals. To make sure those bindings are all in place, the
+aggregation is deferred until the rest of the state has been settled: any
+other pending forks are expanded first, and the sub-goal is run once for each
+resulting branch.
+
+Solutions in which the template can not be fully [reified](crate::Reify)
+are skipped, as are solutions in which the sub-goal left behind
+[constraints](crate::State::constrain()) that are still waiting on
+unresolved variables.
+
+# Caveats
+The sub-goal is run to exhaustion, so it must have a finite number of
+solutions. Also note that variables bound only inside the sub-goal do not
+leak out. There is no equivalent to Prolog's `^` operator or to grouping by
+free variables in `bagof` and `setof`.
+*/
+
+mod bagof;
+mod count;
+mod findall;
+mod max;
+mod min;
+mod setof;
+mod sum;
+
+pub use bagof::bagof;
+pub use count::count;
+pub use findall::findall;
+pub use max::max;
+pub use min::min;
+pub use setof::setof;
+pub use sum::sum;
+
+use std::{fmt, rc::Rc};
+
+use crate::{
+    core::{Fork, ReadyState, Reify, State, StateIter, StateIterator, Unify, Value},
+    goals::Goal,
+};
+
+type ReifyFn<X> = Rc<dyn Fn(&ReadyState) -> Option<X>>;
+type CombineFn<X, R> = Rc<dyn Fn(Vec<X>) -> Option<R>>;
+
+/** A [`Goal`] that collects the solutions of a sub-goal and unifies the
+combined result with a value. Create with one of the functions in the
+[`aggregate`](crate::goals::aggregate) module.
+*/
+pub struct Aggregate<X, R: Unify> {
+    goal: Rc<dyn Goal>,
+    reify: ReifyFn<X>,
+    combine: CombineFn<X, R>,
+    result: Value<R>,
+}
+
+impl<X: 'static, R: Unify> Aggregate<X, R> {
+    fn new(
+        goal: impl Goal,
+        reify: impl Fn(&ReadyState) -> Option<X> + 'static,
+        combine: impl Fn(Vec<X>) -> Option<R> + 'static,
+        result: Value<R>,
+    ) -> Self {
+        Aggregate {
+            goal: Rc::new(goal),
+            reify: Rc::new(reify),
+            combine: Rc::new(combine),
+            result,
+        }
+    }
+
+    /// Collect the reified template from every solution of `goal` and
+    /// combine them.
+    fn of_template<Q>(
+        template: Q,
+        goal: impl Goal,
+        combine: impl Fn(Vec<X>) -> Option<R> + 'static,
+        result: Value<R>,
+    ) -> Self
+    where
+        Q: Reify<Reified = X> + 'static,
+    {
+        Aggregate::new(goal, move |state| template.reify_in(state), combine, result)
+    }
+}
+
+impl<X, R: Unify> fmt::Debug for Aggregate<X, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Aggregate {:?} {:?}", self.goal, self.result)
+    }
+}
+
+impl<X, R: Unify> Clone for Aggregate<X, R> {
+    fn clone(&self) -> Self {
+        Self {
+            goal: self.goal.clone(),
+            reify: self.reify.clone(),
+            combine: self.combine.clone(),
+            result: self.result.clone(),
+        }
+    }
+}
+
+impl<X: 'static, R: Unify> Goal for Aggregate<X, R> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.fork(self.clone())
+    }
+}
+
+impl<X: 'static, R: Unify> Fork for Aggregate<X, R> {
+    fn fork(&self, state: &State) -> StateIter {
+        let aggregate = self.clone();
+        // Settle any other pending forks so that the sub-goal sees every
+        // binding it possibly can.
+        Box::new(state.clone().into_states().filter_map(move |outer| {
+            let found = aggregate
+                .goal
+                .apply(outer.clone())
+                .into_states()
+                // Any constraints still open in the outer state will also be
+                // open here, so we can't insist on a fully ready state. We can
+                // at least make sure the sub-goal didn't leave anything open.
+                .filter(|inner| !inner.has_new_constraints(&outer))
+                .filter_map(|inner| (aggregate.reify)(&ReadyState::new(inner.values)))
+                .collect();
+            let result = (aggregate.combine)(found)?;
+            outer.unify(&aggregate.result, &Value::new(result))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count, findall};
+    use crate::{any, both, cmp::lt, goal_vec, lvec, lvec::LVec, unify, LVar, Query, Value};
+
+    #[test]
+    fn sees_bindings_from_outer_forks() {
+        let (x, y, n) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            any![unify(x, 1), unify(x, 2)],
+            count(
+                both(any![unify(y, 1), unify(y, 2), unify(y, 3)], lt(x, y)),
+                n
+            ),
+        ];
+        goals.assert_permutations_resolve_to(&(x, n), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn skips_solutions_with_open_constraints() {
+        let (x, y, n) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 1), count(any![lt(x, y), unify(y, 2)], n),];
+        goals.assert_permutations_resolve_to(&n, vec![1]);
+    }
+
+    #[test]
+    fn tolerates_outer_open_constraints() {
+        let (x, y, n) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lt(y, n),
+            count(any![unify(x, 1), unify(x, 2)], n),
+            unify(y, 1),
+        ];
+        goals.assert_permutations_resolve_to(&n, vec![2]);
+    }
+
+    #[test]
+    fn nested_aggregates() {
+        let (x, y, xs, n) = (LVar::new(), LVar::new(), LVar::new(), LVar::new());
+        let goal = count(
+            both(
+                any![unify(x, 1), unify(x, 2), unify(x, 3)],
+                findall(y, any![unify(y, x), unify(y, 4)], &xs),
+            ),
+            n,
+        );
+        assert_eq!(goal.query(n).collect::<Vec<_>>(), vec![3]);
+        let goal = both(unify(x, 1), findall(y, any![unify(y, x), unify(y, 4)], &xs));
+        assert_eq!(goal.query(xs).collect::<Vec<_>>(), vec![vec![1, 4]]);
+    }
+
+    #[test]
+    fn result_can_be_partially_known() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let result: Value<LVec<i32>> = Value::new(lvec![1, y]);
+        let goal = findall(x, any![unify(x, 1), unify(x, 2)], result);
+        assert_eq!(goal.query(y).collect::<Vec<_>>(), vec![2]);
+    }
+}
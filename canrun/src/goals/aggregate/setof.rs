@@ -0,0 +1,64 @@
+use super::Aggregate;
+use crate::{goals::Goal, lvec::LVec, Reify, Unify, Value};
+
+/** Like [`bagof`](super::bagof), but the solutions are sorted and
+deduplicated.
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::setof;
+
+let (x, xs) = (LVar::new(), LVar::new());
+let goal = setof(x, any![unify(x, 2), unify(x, 1), unify(x, 2)], &xs);
+let results: Vec<_> = goal.query(xs).collect();
+assert_eq!(results, vec![vec![1, 2]]);
+```
+*/
+pub fn setof<Q>(
+    template: Q,
+    goal: impl Goal,
+    result: impl Into<Value<LVec<Q::Reified>>>,
+) -> Aggregate<Q::Reified, LVec<Q::Reified>>
+where
+    Q: Reify + 'static,
+    Q::Reified: Unify + Ord,
+{
+    Aggregate::of_template(
+        template,
+        goal,
+        |mut found| {
+            found.sort();
+            found.dedup();
+            (!found.is_empty()).then(|| found.into_iter().collect())
+        },
+        result.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::setof;
+    use crate::{any, goal_vec, unify, Fail, LVar};
+
+    #[test]
+    fn sorts_and_deduplicates() {
+        let (x, y, xs) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(y, "b"),
+            setof(
+                x,
+                any![unify(x, "c"), unify(x, y), unify(x, "a"), unify(x, "b")],
+                &xs
+            ),
+        ];
+        goals.assert_permutations_resolve_to(&xs, vec![vec!["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn fails_when_no_solutions() {
+        let (x, xs) = (LVar::<i32>::new(), LVar::new());
+        let goals = goal_vec![setof(x, Fail, &xs)];
+        goals.assert_permutations_resolve_to(&xs, vec![]);
+    }
+}
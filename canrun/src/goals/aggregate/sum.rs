@@ -0,0 +1,60 @@
+use std::iter::Sum;
+
+use super::Aggregate;
+use crate::{goals::Goal, Reify, Unify, Value};
+
+/** Add up the template from every solution of a goal.
+
+If there are no solutions, the result is the [`Sum`] of an empty iterator
+(usually zero).
+
+# Example:
+```
+use canrun::{any, unify, LVar, Query};
+use canrun::aggregate::sum;
+
+let (x, total) = (LVar::new(), LVar::new());
+let goal = sum(x, any![unify(x, 1), unify(x, 2), unify(x, 3)], total);
+let results: Vec<_> = goal.query(total).collect();
+assert_eq!(results, vec![6]);
+```
+*/
+pub fn sum<Q>(
+    template: Q,
+    goal: impl Goal,
+    total: impl Into<Value<Q::Reified>>,
+) -> Aggregate<Q::Reified, Q::Reified>
+where
+    Q: Reify + 'static,
+    Q::Reified: Unify + Sum,
+{
+    Aggregate::of_template(
+        template,
+        goal,
+        |found| Some(found.into_iter().sum()),
+        total.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sum;
+    use crate::{any, goal_vec, unify, Fail, LVar};
+
+    #[test]
+    fn sums_solutions() {
+        let (x, y, total) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            unify(y, 2.5),
+            sum(x, any![unify(x, 1.0), unify(x, y)], total),
+        ];
+        goals.assert_permutations_resolve_to(&total, vec![3.5]);
+    }
+
+    #[test]
+    fn sums_nothing() {
+        let (x, total) = (LVar::<i32>::new(), LVar::new());
+        let goals = goal_vec![sum(x, Fail, total)];
+        goals.assert_permutations_resolve_to(&total, vec![0]);
+    }
+}
@@ -1,5 +1,6 @@
 use super::Goal;
 use crate::core::State;
+use crate::export::{describe, Describe, Formula, UnsupportedGoal};
 
 /**
 A [`Goal`] that only succeeds if all sub-goals succeed.
@@ -48,6 +49,17 @@ impl Goal for All {
     fn apply(&self, state: State) -> Option<State> {
         self.goals.iter().try_fold(state, |s, g| g.apply(s))
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for All {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        let formulas = self.goals.iter().map(|g| describe(g.as_ref()));
+        Ok(Formula::And(formulas.collect::<Result<_, _>>()?))
+    }
 }
 
 /**
@@ -85,8 +97,6 @@ pub use all;
 mod tests {
     use crate::{core::LVar, core::Query, goals::unify};
 
-    use super::all;
-
     #[test]
     fn succeeds() {
         let x = LVar::new();
@@ -2,6 +2,7 @@ use std::{iter::repeat, rc::Rc};
 
 use super::Goal;
 use crate::core::{Fork, State, StateIter};
+use crate::export::{describe, Describe, Formula, UnsupportedGoal};
 
 /**
 A [`Goal`] that yields a state for every successful
@@ -52,6 +53,17 @@ impl Goal for Any {
     fn apply(&self, state: State) -> Option<State> {
         state.fork(self.clone())
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for Any {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        let formulas = self.goals.iter().map(|g| describe(g.as_ref()));
+        Ok(Formula::Or(formulas.collect::<Result<_, _>>()?))
+    }
 }
 
 impl Fork for Any {
@@ -60,6 +72,15 @@ impl Fork for Any {
         let states = repeat(state.clone());
         Box::new(goals.zip(states).flat_map(|(g, s)| g.apply(s).into_iter()))
     }
+
+    /// Counts the sub-goals that do not fail as soon as they are applied.
+    fn branching(&self, state: &State) -> Option<usize> {
+        let viable = self
+            .goals
+            .iter()
+            .filter(|g| g.apply(state.clone()).is_some());
+        Some(viable.count())
+    }
 }
 
 /**
@@ -119,8 +140,6 @@ mod tests {
         goals::{both::both, fail::Fail, unify},
     };
 
-    use super::any;
-
     #[test]
     fn both_succeed() {
         let x = LVar::new();
@@ -0,0 +1,53 @@
+use super::relation::binary;
+use crate::export::Formula;
+use crate::goals::Goal;
+use crate::Value;
+
+/** Relate `c` to the logical AND of `a` and `b`.
+
+If `c` is `true`, both `a` and `b` are bound to `true`. If either input is
+`false`, so is `c`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::bool::and;
+
+let (a, b) = (LVar::new(), LVar::new());
+let goal = and(a, b, true);
+let results: Vec<_> = goal.query((a, b)).collect();
+assert_eq!(results, vec![(true, true)]);
+```
+*/
+pub fn and(
+    a: impl Into<Value<bool>>,
+    b: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+) -> impl Goal {
+    binary(a, b, c, |a, b| a && b, |a, b| Formula::And(vec![a, b]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::and;
+    use crate::goals::bool::or;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn propagates() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![and(a, b, c), unify(a, false)];
+        goals.assert_permutations_resolve_to(&c, vec![false]);
+        let goals = goal_vec![and(a, b, false), unify(a, true)];
+        goals.assert_permutations_resolve_to(&b, vec![false]);
+    }
+
+    #[test]
+    fn forks_when_necessary() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![and(a, b, false), or(a, b, true)];
+        goals.assert_permutations_resolve_to(&(a, b), vec![(false, true), (true, false)]);
+        let goals = goal_vec![and(a, b, true), unify(b, false)];
+        goals.assert_permutations_resolve_to(&a, vec![]);
+    }
+}
@@ -0,0 +1,224 @@
+use super::relation::candidates;
+use crate::constraints::{resolve_1, Constraint, ResolveFn};
+use crate::core::{Fork, StateIter};
+use crate::export::{Describe, Formula, UnsupportedGoal};
+use crate::goals::Goal;
+use crate::lvec::LVec;
+use crate::{LVarList, State, Value};
+use std::fmt::{self, Debug};
+use std::iter::once;
+use std::rc::Rc;
+
+/** Ensure that at most `k` of the items in an [`LVec`] are `true`.
+
+Once `k` items are known to be `true`, the rest are bound to `false`. The
+constraint is dropped as soon as too few items are left unknown to go over
+`k`, and only forks over the unknown items otherwise.
+
+# Example:
+```
+use canrun::{all, lvec, unify, LVar, Query};
+use canrun::goals::bool::at_most_k;
+
+let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+let goal = all![at_most_k(lvec![a, b, c], 1), unify(b, true)];
+let results: Vec<_> = goal.query((a, c)).collect();
+assert_eq!(results, vec![(false, false)]);
+```
+*/
+pub fn at_most_k(items: impl Into<Value<LVec<bool>>>, k: usize) -> AtMostK {
+    AtMostK {
+        items: items.into(),
+        k,
+    }
+}
+
+/** A [goal](crate::goals::Goal) that limits how many items in an [`LVec`]
+are `true`. Create with [`at_most_k`]. */
+#[derive(Clone)]
+pub struct AtMostK {
+    items: Value<LVec<bool>>,
+    k: usize,
+}
+
+impl Debug for AtMostK {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AtMostK {:?} {}", self.items, self.k)
+    }
+}
+
+impl Goal for AtMostK {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for AtMostK {
+    /// Can only be described once the [`LVec`] itself is resolved.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        match &self.items {
+            Value::Resolved(items) => Ok(Formula::AtMost(
+                items.as_slice().iter().map(Formula::from).collect(),
+                self.k,
+            )),
+            Value::Var(_) => Err(UnsupportedGoal::new(self)),
+        }
+    }
+}
+
+impl Constraint for AtMostK {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let items = resolve_1(&self.items, state)?;
+        let goal = Count {
+            values: items.as_slice().into(),
+            k: self.k,
+        };
+        Ok(Box::new(move |state| goal.apply(state)))
+    }
+}
+
+/// Counts the `true` items once the [`LVec`] is known, rather than checking
+/// every assignment like the other boolean relations.
+#[derive(Clone)]
+struct Count {
+    values: Rc<[Value<bool>]>,
+    k: usize,
+}
+
+impl Debug for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Count {:?} {}", self.values, self.k)
+    }
+}
+
+impl Count {
+    /// The number of items known to be `true`, and the items still unknown.
+    fn count(&self, state: &State) -> (usize, Vec<Value<bool>>) {
+        let mut trues = 0;
+        let mut unknown = Vec::new();
+        for value in self.values.iter() {
+            match state.resolve(value) {
+                Value::Resolved(value) => trues += usize::from(*value),
+                var @ Value::Var(_) => unknown.push(var),
+            }
+        }
+        (trues, unknown)
+    }
+
+    /// The first item still worth forking over, if the limit could still be
+    /// exceeded.
+    fn unknown(&self, state: &State) -> Option<Value<bool>> {
+        let (trues, unknown) = self.count(state);
+        if trues + unknown.len() <= self.k {
+            return None;
+        }
+        unknown.into_iter().next()
+    }
+}
+
+impl Goal for Count {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))?.fork(self.clone())
+    }
+}
+
+impl Constraint for Count {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (trues, unknown) = self.count(state);
+        if trues > self.k {
+            return Ok(Box::new(|_| None));
+        }
+        if trues + unknown.len() <= self.k {
+            return Ok(Box::new(Some));
+        }
+        if trues == self.k {
+            return Ok(Box::new(move |state| {
+                unknown
+                    .iter()
+                    .try_fold(state, |state, value| state.unify(value, &Value::new(false)))
+            }));
+        }
+        let vars = unknown.iter().filter_map(|value| match value {
+            Value::Var(var) => Some(LVarList::one(var)),
+            Value::Resolved(_) => None,
+        });
+        Err(LVarList::flatten(vars))
+    }
+}
+
+impl Fork for Count {
+    fn fork(&self, state: &State) -> StateIter {
+        let Some(value) = self.unknown(state) else {
+            return Box::new(once(state.clone()));
+        };
+        let (state, this) = (state.clone(), self.clone());
+        Box::new(candidates(None).filter_map(move |candidate| {
+            state
+                .clone()
+                .unify(&value, &Value::new(candidate))?
+                .fork(this.clone())
+        }))
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        Some(if self.unknown(state).is_some() { 2 } else { 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::at_most_k;
+    use crate::goals::bool::xor;
+    use crate::lvec::LVec;
+    use crate::{all, goal_vec, lvec, unify, LVar, Query, StateIterator, Value};
+
+    #[test]
+    fn propagates() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![at_most_k(lvec![a, b, c], 2), unify(a, true), unify(c, true)];
+        goals.assert_permutations_resolve_to(&b, vec![false]);
+    }
+
+    #[test]
+    fn forks_when_necessary() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![at_most_k(lvec![a, b, c], 1), xor(a, b, true)];
+        goals.assert_permutations_resolve_to(
+            &(a, b, c),
+            vec![(false, true, false), (true, false, false)],
+        );
+    }
+
+    #[test]
+    fn counts_many_items() {
+        let vars: Vec<LVar<bool>> = (0..200).map(|_| LVar::new()).collect();
+        let items: LVec<bool> = vars.iter().map(Value::from).collect();
+
+        let goal = at_most_k(items.clone(), 200);
+        assert_eq!(goal.into_states().count(), 1);
+
+        let goal = all![at_most_k(items.clone(), 1), unify(vars[150], true)];
+        let results: Vec<_> = goal.query(items.clone()).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].iter().filter(|v| **v).count(), 1);
+
+        let goal = all![
+            at_most_k(items.clone(), 2),
+            unify(vars[3], true),
+            unify(vars[199], true),
+            unify(vars[100], true),
+        ];
+        assert_eq!(goal.into_states().count(), 0);
+    }
+
+    #[test]
+    fn fails() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![at_most_k(lvec![a, b], 0), unify(b, true)];
+        goals.assert_permutations_resolve_to(&a, vec![]);
+    }
+}
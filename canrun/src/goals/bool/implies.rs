@@ -0,0 +1,56 @@
+use super::relation::binary;
+use crate::export::Formula;
+use crate::goals::Goal;
+use crate::Value;
+
+/** Relate `c` to whether `a` implies `b`.
+
+`c` is `false` only when `a` is `true` and `b` is `false`, so requiring
+`implies(a, b, true)` binds `b` as soon as `a` is `true`.
+
+# Example:
+```
+use canrun::{all, unify, LVar, Query};
+use canrun::goals::bool::implies;
+
+let (a, b) = (LVar::new(), LVar::new());
+let goal = all![implies(a, b, true), unify(a, true)];
+let results: Vec<_> = goal.query(b).collect();
+assert_eq!(results, vec![true]);
+```
+*/
+pub fn implies(
+    a: impl Into<Value<bool>>,
+    b: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+) -> impl Goal {
+    binary(
+        a,
+        b,
+        c,
+        |a, b| !a || b,
+        |a, b| Formula::Or(vec![Formula::Not(Box::new(a)), b]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::implies;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn propagates() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![implies(a, b, c), unify(a, false)];
+        goals.assert_permutations_resolve_to(&c, vec![true]);
+        let goals = goal_vec![implies(a, b, false)];
+        goals.assert_permutations_resolve_to(&(a, b), vec![(true, false)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![implies(a, b, true), unify(a, true), unify(b, false)];
+        goals.assert_permutations_resolve_to(&(a, b), vec![]);
+    }
+}
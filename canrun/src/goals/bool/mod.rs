@@ -0,0 +1,31 @@
+//! Boolean logic goals including [`and`](and::and), [`or`](or::or),
+//! [`xor`](xor::xor), [`not_b`](not_b::not_b) and
+//! [`implies`](implies::implies), plus [`at_most_k`](at_most_k::at_most_k)
+//! over an [`LVec`](crate::lvec::LVec).
+//!
+//! These goals don't wait for all of their values to be resolved. As soon as
+//! the known values leave only one possibility for another value, it is
+//! bound. For example, `and(a, b, true)` binds both `a` and `b` to `true`
+//! right away. Any values that are still unknown when the results are
+//! collected are forked over, one at a time, until the goal holds no matter
+//! what the rest turn out to be. Values that don't matter by then are left
+//! unresolved, so the same answer isn't repeated once for each of them.
+//!
+//! This module isn't re-exported at the crate root, since `canrun::bool`
+//! would be easy to confuse with the primitive type. Use it as
+//! `canrun::goals::bool`.
+
+mod and;
+mod at_most_k;
+mod implies;
+mod not_b;
+mod or;
+mod relation;
+mod xor;
+
+pub use and::and;
+pub use at_most_k::{at_most_k, AtMostK};
+pub use implies::implies;
+pub use not_b::not_b;
+pub use or::or;
+pub use xor::xor;
@@ -0,0 +1,53 @@
+use super::relation::{candidates, relation};
+use crate::export::Formula;
+use crate::goals::Goal;
+use crate::Value;
+use std::rc::Rc;
+
+/** Relate `b` to the logical NOT of `a`.
+
+Named to avoid clashing with the [`not`](crate::goals::not()) goal, which
+negates a goal rather than a value.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::bool::not_b;
+
+let a = LVar::new();
+let goal = not_b(a, true);
+let results: Vec<_> = goal.query(a).collect();
+assert_eq!(results, vec![false]);
+```
+*/
+pub fn not_b(a: impl Into<Value<bool>>, b: impl Into<Value<bool>>) -> impl Goal {
+    let (a, b) = (a.into(), b.into());
+    let formula = Formula::iff((&b).into(), Formula::Not(Box::new((&a).into())));
+    relation(
+        vec![a, b],
+        Rc::new(|known| candidates(known[0]).any(|a| candidates(known[1]).any(|b| a != b))),
+        formula,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::not_b;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn propagates() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![not_b(a, b), unify(b, false)];
+        goals.assert_permutations_resolve_to(&a, vec![true]);
+        let goals = goal_vec![not_b(a, b)];
+        goals.assert_permutations_resolve_to(&(a, b), vec![(false, true), (true, false)]);
+    }
+
+    #[test]
+    fn fails() {
+        let a = LVar::new();
+        let goals = goal_vec![not_b(a, a)];
+        goals.assert_permutations_resolve_to(&a, vec![]);
+    }
+}
@@ -0,0 +1,50 @@
+use super::relation::binary;
+use crate::export::Formula;
+use crate::goals::Goal;
+use crate::Value;
+
+/** Relate `c` to the logical OR of `a` and `b`.
+
+If `c` is `false`, both `a` and `b` are bound to `false`. If either input
+is `true`, so is `c`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::bool::or;
+
+let (a, c) = (LVar::new(), LVar::new());
+let goal = or(a, false, c);
+let results: Vec<_> = goal.query((a, c)).collect();
+assert_eq!(results, vec![(false, false), (true, true)]);
+```
+*/
+pub fn or(
+    a: impl Into<Value<bool>>,
+    b: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+) -> impl Goal {
+    binary(a, b, c, |a, b| a || b, |a, b| Formula::Or(vec![a, b]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::or;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn propagates() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![or(a, b, c), unify(b, true)];
+        goals.assert_permutations_resolve_to(&c, vec![true]);
+        let goals = goal_vec![or(a, b, true), unify(a, false)];
+        goals.assert_permutations_resolve_to(&b, vec![true]);
+    }
+
+    #[test]
+    fn fails() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![or(a, b, false), unify(a, true)];
+        goals.assert_permutations_resolve_to(&b, vec![]);
+    }
+}
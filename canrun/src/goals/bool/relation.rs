@@ -0,0 +1,176 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::core::{Fork, StateIter};
+use crate::export::{Describe, Formula, UnsupportedGoal};
+use crate::goals::Goal;
+use crate::{LVarList, State, Value};
+use std::fmt::{self, Debug};
+use std::iter::once;
+use std::rc::Rc;
+
+/// Returns true if the known values (`None` for a variable) can still be
+/// completed in a way that satisfies the relation.
+type PossibleFn = Rc<dyn Fn(&[Option<bool>]) -> bool>;
+
+/// The possible values of a [`bool`] that may not be known yet.
+pub(super) fn candidates(known: Option<bool>) -> impl Iterator<Item = bool> {
+    [false, true]
+        .into_iter()
+        .filter(move |value| known.map_or(true, |known| known == *value))
+}
+
+/// Relate `a`, `b` and `c` so that `c` is `holds(a, b)`, which is
+/// described by `describe` when [exported](crate::export).
+pub(super) fn binary(
+    a: impl Into<Value<bool>>,
+    b: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+    holds: fn(bool, bool) -> bool,
+    describe: fn(Formula, Formula) -> Formula,
+) -> Relation {
+    let (a, b, c) = (a.into(), b.into(), c.into());
+    let formula = Formula::iff((&c).into(), describe((&a).into(), (&b).into()));
+    relation(
+        vec![a, b, c],
+        Rc::new(move |known| {
+            candidates(known[0]).any(|a| {
+                candidates(known[1]).any(|b| candidates(known[2]).any(|c| holds(a, b) == c))
+            })
+        }),
+        formula,
+    )
+}
+
+/// Constrain `values` to the assignments allowed by `possible`.
+///
+/// Whenever one of the values is bound, any value that can only be `true` or
+/// only be `false` in the remaining assignments is bound as well. Once every
+/// remaining assignment is allowed the relation is entailed and dropped.
+/// Otherwise, values that are still unknown once the other goals have been
+/// applied are forked over one at a time, so that each branch gets a chance
+/// to propagate before the next value is picked.
+pub(super) fn relation(
+    values: Vec<Value<bool>>,
+    possible: PossibleFn,
+    formula: Formula,
+) -> Relation {
+    Relation {
+        values: values.into(),
+        possible,
+        formula: Rc::new(formula),
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Relation {
+    values: Rc<[Value<bool>]>,
+    possible: PossibleFn,
+    formula: Rc<Formula>,
+}
+
+impl Debug for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Relation {:?}", self.values)
+    }
+}
+
+impl Goal for Relation {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))?.fork(self.clone())
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for Relation {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(self.formula.as_ref().clone())
+    }
+}
+
+impl Constraint for Relation {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let resolved: Vec<_> = self.values.iter().map(|v| state.resolve(v)).collect();
+        let mut known: Vec<_> = resolved.iter().map(|v| v.resolved().copied()).collect();
+        if !(self.possible)(&known) {
+            return Ok(Box::new(|_| None));
+        }
+        if self.entailed(&mut known) {
+            return Ok(Box::new(Some));
+        }
+        let mut forced = Vec::new();
+        let mut vars = Vec::new();
+        for (i, value) in resolved.iter().enumerate() {
+            let Value::Var(var) = value else { continue };
+            let mut allowed = candidates(None).filter(|&candidate| {
+                known[i] = Some(candidate);
+                (self.possible)(&known)
+            });
+            if let (Some(only), None) = (allowed.next(), allowed.next()) {
+                forced.push((value.clone(), only));
+            }
+            known[i] = None;
+            vars.push(var.id);
+        }
+        if forced.is_empty() {
+            return Err(LVarList(vars));
+        }
+        let this = self.clone();
+        Ok(Box::new(move |state| {
+            forced
+                .into_iter()
+                .try_fold(state, |state, (value, only)| {
+                    state.unify(&value, &Value::new(only))
+                })?
+                .constrain(Rc::new(this))
+        }))
+    }
+}
+
+impl Relation {
+    /// Returns true if every completion of the unknown values is allowed.
+    fn entailed(&self, known: &mut [Option<bool>]) -> bool {
+        let Some(i) = known.iter().position(Option::is_none) else {
+            return (self.possible)(known);
+        };
+        let entailed = candidates(None).all(|candidate| {
+            known[i] = Some(candidate);
+            self.entailed(known)
+        });
+        known[i] = None;
+        entailed
+    }
+
+    /// The first value still worth forking over, if the relation is not
+    /// already entailed.
+    fn unknown(&self, state: &State) -> Option<Value<bool>> {
+        let resolved: Vec<_> = self.values.iter().map(|v| state.resolve(v)).collect();
+        let mut known: Vec<_> = resolved.iter().map(|v| v.resolved().copied()).collect();
+        if self.entailed(&mut known) {
+            return None;
+        }
+        resolved
+            .into_iter()
+            .find(|value| matches!(value, Value::Var(_)))
+    }
+}
+
+impl Fork for Relation {
+    fn fork(&self, state: &State) -> StateIter {
+        let Some(value) = self.unknown(state) else {
+            return Box::new(once(state.clone()));
+        };
+        let (state, this) = (state.clone(), self.clone());
+        Box::new(candidates(None).filter_map(move |candidate| {
+            state
+                .clone()
+                .unify(&value, &Value::new(candidate))?
+                .fork(this.clone())
+        }))
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        Some(if self.unknown(state).is_some() { 2 } else { 1 })
+    }
+}
@@ -0,0 +1,47 @@
+use super::relation::binary;
+use crate::export::Formula;
+use crate::goals::Goal;
+use crate::Value;
+
+/** Relate `c` to the logical XOR of `a` and `b`.
+
+Any two of the values determine the third.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::bool::xor;
+
+let a = LVar::new();
+let goal = xor(a, true, true);
+let results: Vec<_> = goal.query(a).collect();
+assert_eq!(results, vec![false]);
+```
+*/
+pub fn xor(
+    a: impl Into<Value<bool>>,
+    b: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+) -> impl Goal {
+    binary(a, b, c, |a, b| a != b, Formula::xor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xor;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn propagates() {
+        let (a, b, c) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![xor(a, b, c), unify(a, true), unify(c, true)];
+        goals.assert_permutations_resolve_to(&b, vec![false]);
+    }
+
+    #[test]
+    fn forks_when_necessary() {
+        let (a, b) = (LVar::new(), LVar::new());
+        let goals = goal_vec![xor(a, b, true)];
+        goals.assert_permutations_resolve_to(&(a, b), vec![(false, true), (true, false)]);
+    }
+}
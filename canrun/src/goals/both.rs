@@ -1,5 +1,6 @@
 use super::Goal;
 use crate::core::State;
+use crate::export::{describe, Describe, Formula, UnsupportedGoal};
 
 /**
 A [Goal](crate::goals::Goal) that only succeeds if both sub-goals succeed. Create with [`both`].
@@ -49,6 +50,16 @@ impl<A: Goal, B: Goal> Goal for Both<A, B> {
     fn apply(&self, state: State) -> Option<State> {
         self.a.apply(state).and_then(|s| self.b.apply(s))
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<A: Goal, B: Goal> Describe for Both<A, B> {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(Formula::And(vec![describe(&self.a)?, describe(&self.b)?]))
+    }
 }
 
 #[cfg(test)]
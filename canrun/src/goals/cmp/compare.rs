@@ -0,0 +1,190 @@
+use crate::constraints::{Constraint, ResolveFn};
+use crate::export::{Describe, Formula, NumExpr, UnsupportedGoal};
+use crate::goals::Goal;
+use crate::{LVar, LVarList, State, Unify, Value};
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+type CompareFn<A, B> = fn(&A, &B) -> bool;
+type DescribeFn = fn(NumExpr, NumExpr) -> Formula;
+
+/// Check `holds(a, b)` once both values are resolved. Until then, drop any
+/// values from the [domain](crate::domain) of one side that can't satisfy
+/// `holds` with any of the possible values of the other side. When
+/// [exported](crate::export), the comparison is described by `describe`.
+pub(super) fn compare<A, B>(
+    a: impl Into<Value<A>>,
+    b: impl Into<Value<B>>,
+    holds: CompareFn<A, B>,
+    describe: DescribeFn,
+) -> Compare<A, B>
+where
+    A: Unify + Clone,
+    B: Unify + Clone,
+{
+    Compare {
+        a: a.into(),
+        b: b.into(),
+        holds,
+        describe,
+    }
+}
+
+pub(super) struct Compare<A: Unify, B: Unify> {
+    a: Value<A>,
+    b: Value<B>,
+    holds: CompareFn<A, B>,
+    describe: DescribeFn,
+}
+
+impl<A: Unify, B: Unify> Clone for Compare<A, B> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            holds: self.holds,
+            describe: self.describe,
+        }
+    }
+}
+
+impl<A: Unify, B: Unify> Debug for Compare<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Compare {:?} {:?}", self.a, self.b)
+    }
+}
+
+impl<A: Unify + Clone, B: Unify + Clone> Goal for Compare<A, B> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<A: Unify + Clone, B: Unify + Clone> Describe for Compare<A, B> {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        match (NumExpr::of(&self.a), NumExpr::of(&self.b)) {
+            (Some(a), Some(b)) => Ok((self.describe)(a, b)),
+            _ => Err(UnsupportedGoal::new(self)),
+        }
+    }
+}
+
+fn var_of<T: Unify>(value: &Value<T>) -> Option<LVar<T>> {
+    match value {
+        Value::Var(var) => Some(var.clone()),
+        Value::Resolved(_) => None,
+    }
+}
+
+/// The values `value` could still take, if there are finitely many.
+fn candidates<'a, T: Unify>(state: &'a State, value: &'a Value<T>) -> Option<Vec<&'a T>> {
+    match value {
+        Value::Resolved(value) => Some(vec![value.as_ref()]),
+        Value::Var(var) => state.domain(var).map(|values| values.iter().collect()),
+    }
+}
+
+/// The domain of `value` with the unsupported values removed, if that leaves
+/// anything out.
+fn narrow<T: Unify + Clone>(
+    state: &State,
+    value: &Value<T>,
+    supported: impl Fn(&T) -> bool,
+) -> Option<(LVar<T>, Vec<T>)> {
+    let var = var_of(value)?;
+    let current = state.domain(&var)?;
+    let narrowed: Vec<T> = current.iter().filter(|v| supported(v)).cloned().collect();
+    (narrowed.len() < current.len()).then_some((var, narrowed))
+}
+
+impl<A: Unify + Clone, B: Unify + Clone> Constraint for Compare<A, B> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (a, b) = (state.resolve(&self.a), state.resolve(&self.b));
+        let holds = self.holds;
+        if let (Value::Resolved(a), Value::Resolved(b)) = (&a, &b) {
+            let found = holds(a, b);
+            return Ok(Box::new(move |state| found.then_some(state)));
+        }
+        // Narrow one side at a time, since narrowing can bind the other side.
+        if let Some((var, values)) = candidates(state, &b)
+            .and_then(|bs| narrow(state, &a, |a| bs.iter().any(|b| holds(a, b))))
+        {
+            let this = self.clone();
+            return Ok(Box::new(move |state| {
+                state.narrow_domain(&var, values)?.constrain(Rc::new(this))
+            }));
+        }
+        if let Some((var, values)) = candidates(state, &a)
+            .and_then(|as_| narrow(state, &b, |b| as_.iter().any(|a| holds(a, b))))
+        {
+            let this = self.clone();
+            return Ok(Box::new(move |state| {
+                state.narrow_domain(&var, values)?.constrain(Rc::new(this))
+            }));
+        }
+        Err(match (var_of(&a), var_of(&b)) {
+            (Some(a), Some(b)) => LVarList::two(&a, &b),
+            (Some(a), None) => LVarList::one(&a),
+            (None, Some(b)) => LVarList::one(&b),
+            (None, None) => unreachable!("one side is unresolved"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmp::{gt, lt};
+    use crate::domain::in_range;
+    use crate::{all, goal_vec, lvec, unify, Goal, LVar, State};
+
+    #[test]
+    fn narrows_domains() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![in_range(x, 1..=3), in_range(y, 1..=3), lt(x, y), gt(x, 1)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn fails_on_empty_domains() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            in_range(x, 1..=3),
+            in_range(y, 1..=3),
+            lt(x, y),
+            unify(x, 3)
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![]);
+    }
+
+    #[test]
+    fn propagates_through_chains() {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            in_range(x, 1..=3),
+            in_range(y, 1..=3),
+            in_range(z, 1..=3),
+            lt(x, y),
+            lt(y, z),
+        ];
+        goals.assert_permutations_resolve_to(&(x, y, z), vec![(1, 2, 3)]);
+    }
+
+    #[test]
+    fn narrows_member_sets() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goal = all![
+            lvec::member(x, lvec![1, 2, 3, 4, 5]),
+            lvec::member(y, lvec![1, 2, 3, 4, 5]),
+            lt(x, y),
+            gt(x, 1),
+        ];
+        // The domains are narrowed before the `member` forks are expanded.
+        let state = goal.apply(State::new()).unwrap();
+        assert_eq!(state.domain(&x), Some(&[2, 3, 4][..]));
+        assert_eq!(state.domain(&y), Some(&[3, 4, 5][..]));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::constraints::{resolve_1, Constraint, ResolveFn};
+use crate::export::{describe, Describe, Formula, UnsupportedGoal};
+use crate::goals::{unify, Goal};
+use crate::lvec::{fold, LVec};
+use crate::{LVarList, State, Unify, Value};
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+type PickFn<T> = fn(Value<T>, Value<T>, Value<T>) -> Rc<dyn Goal>;
+
+/// Fold `pick` over the items once the [`LVec`] is resolved, starting with
+/// the first item. Fails if the [`LVec`] is empty.
+pub(super) fn extremum<T: Unify>(
+    items: impl Into<Value<LVec<T>>>,
+    result: impl Into<Value<T>>,
+    pick: PickFn<T>,
+) -> Extremum<T> {
+    Extremum {
+        items: items.into(),
+        result: result.into(),
+        pick,
+    }
+}
+
+pub(super) struct Extremum<T: Unify> {
+    items: Value<LVec<T>>,
+    result: Value<T>,
+    pick: PickFn<T>,
+}
+
+impl<T: Unify> Clone for Extremum<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            result: self.result.clone(),
+            pick: self.pick,
+        }
+    }
+}
+
+impl<T: Unify> Debug for Extremum<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Extremum {:?} {:?}", self.items, self.result)
+    }
+}
+
+impl<T: Unify> Goal for Extremum<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<T: Unify> Describe for Extremum<T> {
+    /// Can only be described once the [`LVec`] itself is resolved, as the
+    /// same chain of `pick` goals that the constraint would apply.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        let Value::Resolved(items) = &self.items else {
+            return Err(UnsupportedGoal::new(self));
+        };
+        let Some((first, rest)) = items.as_slice().split_first() else {
+            return Ok(Formula::Bool(false));
+        };
+        let mut formulas = Vec::with_capacity(items.len());
+        let mut acc = first.clone();
+        for item in rest {
+            let next = Value::var();
+            formulas.push(describe(
+                (self.pick)(acc, item.clone(), next.clone()).as_ref(),
+            )?);
+            acc = next;
+        }
+        formulas.push(describe(&unify(acc, self.result.clone()))?);
+        Ok(Formula::And(formulas))
+    }
+}
+
+impl<T: Unify> Constraint for Extremum<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let items = resolve_1(&self.items, state)?;
+        let Some((first, rest)) = items.as_slice().split_first() else {
+            return Ok(Box::new(|_| None));
+        };
+        let goal = fold(
+            LVec::from(rest.to_vec()),
+            first.clone(),
+            self.result.clone(),
+            self.pick,
+        );
+        Ok(Box::new(move |state| goal.apply(state)))
+    }
+}
@@ -1,4 +1,5 @@
-use crate::goals::assert_2;
+use super::compare::compare;
+use crate::export::Formula;
 use crate::goals::Goal;
 use crate::Unify;
 use crate::Value;
@@ -22,10 +23,10 @@ assert_eq!(results, vec![(2, 1)]);
 */
 pub fn gt<A, B>(a: impl Into<Value<A>>, b: impl Into<Value<B>>) -> impl Goal
 where
-    A: Unify + PartialOrd<B>,
-    B: Unify,
+    A: Unify + PartialOrd<B> + Clone,
+    B: Unify + Clone,
 {
-    assert_2(a, b, |a, b| a > b)
+    compare(a, b, |a, b| a > b, |a, b| Formula::Lt(b, a))
 }
 
 #[cfg(test)]
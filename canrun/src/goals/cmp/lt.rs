@@ -1,7 +1,6 @@
-use crate::{
-    goals::{assert_2, Goal},
-    Unify, Value,
-};
+use super::compare::compare;
+use crate::export::Formula;
+use crate::{goals::Goal, Unify, Value};
 
 /** Ensure that one value is less than another.
 
@@ -22,10 +21,10 @@ assert_eq!(results, vec![(1, 2)]);
 */
 pub fn lt<A, B>(a: impl Into<Value<A>>, b: impl Into<Value<B>>) -> impl Goal
 where
-    A: Unify + PartialOrd<B>,
-    B: Unify,
+    A: Unify + PartialOrd<B> + Clone,
+    B: Unify + Clone,
 {
-    assert_2(a, b, |a, b| a < b)
+    compare(a, b, |a, b| a < b, Formula::Lt)
 }
 
 #[cfg(test)]
@@ -22,7 +22,7 @@ assert_eq!(results, vec![(1, 2, 2)]);
 */
 pub fn max<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
 where
-    T: Unify + PartialOrd,
+    T: Unify + PartialOrd + Clone,
 {
     let a = a.into();
     let b = b.into();
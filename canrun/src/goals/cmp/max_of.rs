@@ -0,0 +1,53 @@
+use super::extremum::extremum;
+use super::max::max;
+use crate::goals::Goal;
+use crate::lvec::LVec;
+use crate::{Unify, Value};
+use std::rc::Rc;
+
+/** Get the greatest item in an [`LVec`], using [`max`](super::max) for each
+item.
+
+Fails if the [`LVec`] is empty.
+
+# Example:
+```
+use canrun::{lvec, LVar, Query};
+use canrun::cmp::max_of;
+
+let x = LVar::new();
+let goal = max_of(lvec![3, 1, 4, 1, 5], x);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![5]);
+```
+*/
+pub fn max_of<T>(items: impl Into<Value<LVec<T>>>, m: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + PartialOrd + Clone,
+{
+    extremum(items, m, |a, b, c| Rc::new(max(a, b, c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::max_of;
+    use crate::{goal_vec, lvec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, m) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 7), max_of(lvec![3, x, 5], m)];
+        goals.assert_permutations_resolve_to(&m, vec![7]);
+        let goals = goal_vec![max_of(lvec![2, 2], m)];
+        goals.assert_permutations_resolve_to(&m, vec![2]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, m) = (LVar::new(), LVar::<i32>::new());
+        let goals = goal_vec![unify(x, 7), max_of(lvec![3, x, 5], 5)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![max_of(lvec![], m)];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+    }
+}
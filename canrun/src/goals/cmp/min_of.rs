@@ -0,0 +1,53 @@
+use super::extremum::extremum;
+use super::min::min;
+use crate::goals::Goal;
+use crate::lvec::LVec;
+use crate::{Unify, Value};
+use std::rc::Rc;
+
+/** Get the least item in an [`LVec`], using [`min`](super::min) for each
+item.
+
+Fails if the [`LVec`] is empty.
+
+# Example:
+```
+use canrun::{lvec, LVar, Query};
+use canrun::cmp::min_of;
+
+let x = LVar::new();
+let goal = min_of(lvec![3, 1, 4, 1, 5], x);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1]);
+```
+*/
+pub fn min_of<T>(items: impl Into<Value<LVec<T>>>, m: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + PartialOrd + Clone,
+{
+    extremum(items, m, |a, b, c| Rc::new(min(a, b, c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_of;
+    use crate::{goal_vec, lvec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, m) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 1), min_of(lvec![3, x, 5], m)];
+        goals.assert_permutations_resolve_to(&m, vec![1]);
+        let goals = goal_vec![min_of(lvec![2, 2], m)];
+        goals.assert_permutations_resolve_to(&m, vec![2]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, m) = (LVar::new(), LVar::<i32>::new());
+        let goals = goal_vec![unify(x, 1), min_of(lvec![3, x, 5], 3)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![min_of(lvec![], m)];
+        goals.assert_permutations_resolve_to(&m, vec![]);
+    }
+}
@@ -1,16 +1,30 @@
 //! Comparison goals including [`lt`](lt::lt), [`lte`](lte::lte),
-//! [`gt`](gt::gt), [`gte`](gte::gte), [`min`](min::min) and [`max`](max::max).
+//! [`gt`](gt::gt), [`gte`](gte::gte), [`min`](min::min) and [`max`](max::max),
+//! plus [`min_of`](min_of::min_of) and [`max_of`](max_of::max_of) over an
+//! [`LVec`](crate::lvec::LVec).
+//!
+//! The comparisons don't have to wait for both sides to be resolved. If one
+//! side has a [domain](crate::domain), any values that can't satisfy the
+//! comparison with the other side are dropped from it. This can narrow other
+//! domains in turn, and fails as soon as a domain is left empty, before any
+//! forks that depend on it are expanded.
 
+mod compare;
+mod extremum;
 mod gt;
 mod gte;
 mod lt;
 mod lte;
 mod max;
+mod max_of;
 mod min;
+mod min_of;
 
 pub use gt::gt;
 pub use gte::gte;
 pub use lt::lt;
 pub use lte::lte;
 pub use max::max;
+pub use max_of::max_of;
 pub use min::min;
+pub use min_of::min_of;
@@ -0,0 +1,239 @@
+//! Limit variables to a finite set of values.
+//!
+//! A domain does not enumerate its values by itself, so a variable with only
+//! a domain is left unresolved. Instead, other goals can use the domain to
+//! narrow down their search. For example, [`ops::add`](crate::ops::add) will
+//! fork over the possible values of its inputs when only the result is known.
+//!
+//! Adding more than one domain to the same variable limits it to the values
+//! they have in common. A [`member`](crate::lvec::member) goal over a known
+//! collection of integers or `char`s also adds a domain for its item.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use crate::constraints::{Constraint, ResolveFn};
+use crate::export::{Describe, Formula, NumExpr, UnsupportedGoal};
+use crate::goals::Goal;
+use crate::{LVar, LVarList, State, Unify, Value};
+
+/** Create a [goal](crate::goals::Goal) that limits `x` to one of `values`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::domain::domain;
+use canrun::ops::add;
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = canrun::all![
+    domain(x, [1, 2, 3]),
+    domain(y, [3, 4]),
+    add(x, y, 5),
+];
+let mut results: Vec<_> = goal.query((x, y)).collect();
+results.sort_unstable();
+assert_eq!(results, vec![(1, 4), (2, 3)]);
+```
+*/
+pub fn domain<T>(x: impl Into<Value<T>>, values: impl IntoIterator<Item = T>) -> Domain<T>
+where
+    T: Unify + Ord + Clone,
+{
+    let mut values: Vec<T> = values.into_iter().collect();
+    values.sort_unstable();
+    values.dedup();
+    Domain {
+        x: x.into(),
+        values: values.into(),
+    }
+}
+
+/** Create a [goal](crate::goals::Goal) that limits `x` to the values in
+`range`.
+
+Every value in the range is stored, so this is meant for reasonably small
+ranges.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::domain::in_range;
+use canrun::ops::add;
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = canrun::all![in_range(x, 1..=3), in_range(y, 1..=3), add(x, y, 5)];
+let mut results: Vec<_> = goal.query((x, y)).collect();
+results.sort_unstable();
+assert_eq!(results, vec![(2, 3), (3, 2)]);
+```
+*/
+pub fn in_range<T>(x: impl Into<Value<T>>, range: RangeInclusive<T>) -> Domain<T>
+where
+    T: Unify + Ord + Clone,
+    RangeInclusive<T>: Iterator<Item = T>,
+{
+    domain(x, range)
+}
+
+/** A [goal](crate::goals::Goal) that limits a value to a finite set. Create
+with [`domain`] or [`in_range`]. */
+pub struct Domain<T: Unify> {
+    x: Value<T>,
+    values: Rc<[T]>,
+}
+
+impl<T: Unify> Clone for Domain<T> {
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T: Unify> Debug for Domain<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Domain")
+            .field("x", &self.x)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl<T: Unify + Ord + Clone> Goal for Domain<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<T: Unify + Ord + Clone> Describe for Domain<T> {
+    /// Described as `x` being equal to one of the values, which are only
+    /// supported if they can be described as [`NumExpr`]s.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        let x = NumExpr::of(&self.x).ok_or_else(|| UnsupportedGoal::new(self))?;
+        let options = self.values.iter().map(|value| {
+            let value = NumExpr::of(&Value::new(value.clone()))
+                .ok_or_else(|| UnsupportedGoal::new(self))?;
+            Ok(Formula::Eq(x.clone(), value))
+        });
+        Ok(Formula::Or(options.collect::<Result<_, _>>()?))
+    }
+}
+
+impl<T: Unify + Ord + Clone> Constraint for Domain<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let contains = |value: &T| self.values.binary_search(value).is_ok();
+        let var = match state.resolve(&self.x) {
+            Value::Resolved(value) => {
+                let found = contains(&value);
+                return Ok(Box::new(move |state| found.then_some(state)));
+            }
+            Value::Var(var) => var,
+        };
+        // Once the stored domain is no wider than this one, just wait for the
+        // variable to be bound.
+        let narrowed: Vec<T> = match state.domain(&var) {
+            Some(current) if current.iter().all(contains) => {
+                return Err(LVarList::one(&var));
+            }
+            Some(current) => current.iter().filter(|v| contains(v)).cloned().collect(),
+            None => self.values.to_vec(),
+        };
+        let this = self.clone();
+        Ok(Box::new(move |state| {
+            state
+                .narrow_domain(&var, narrowed)?
+                .constrain(Rc::new(this))
+        }))
+    }
+}
+
+/// Limit `var` to the resolved `values`, as if by [`domain`].
+///
+/// This lets goals that can't require `T: Ord`, such as
+/// [`member`](crate::lvec::member), feed the same narrowing. Only the built-in
+/// integer types and `char` are supported. For other types, or if any of the
+/// values are unresolved, the state is passed through untouched.
+pub(crate) fn restrict<T: Unify>(
+    state: State,
+    var: &LVar<T>,
+    values: &[Value<T>],
+) -> Option<State> {
+    macro_rules! restrict_as {
+        ($($type:ty),+) => {
+            $(if let Some(var) = (var as &dyn Any).downcast_ref::<LVar<$type>>() {
+                return restrict_as(state, *var, values);
+            })+
+        };
+    }
+    restrict_as!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, char);
+    Some(state)
+}
+
+fn restrict_as<D, T>(state: State, var: LVar<D>, values: &[Value<T>]) -> Option<State>
+where
+    D: Unify + Ord + Clone,
+    T: Unify,
+{
+    let mut found = Vec::with_capacity(values.len());
+    for value in values {
+        let value = state.resolve(value);
+        match value
+            .resolved()
+            .and_then(|v| (v as &dyn Any).downcast_ref::<D>())
+        {
+            Some(value) => found.push(value.clone()),
+            None => return Some(state),
+        }
+    }
+    domain(var, found).apply(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{domain, in_range};
+    use crate::{goal_vec, lvec, unify, Goal, LVar, State};
+
+    #[test]
+    fn checks_resolved_values() {
+        let x = LVar::new();
+        let goals = goal_vec![domain(x, [1, 2, 3]), unify(x, 2)];
+        goals.assert_permutations_resolve_to(&x, vec![2]);
+        let goals = goal_vec![in_range(x, 1..=3), unify(x, 4)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn intersects_domains() {
+        let x = LVar::new();
+        let goals = goal_vec![domain(x, [1, 2, 3]), domain(x, [3, 4])];
+        goals.assert_permutations_resolve_to(&x, vec![3]);
+        let goals = goal_vec![domain(x, [1, 2]), domain(x, [3, 4])];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn follows_bound_vars() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![domain(x, [1, 2, 3]), domain(y, [3, 4]), unify(x, y)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(3, 3)]);
+    }
+
+    #[test]
+    fn member_adds_domain() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goal = lvec::member(x, lvec![3, 1, 2, 1]);
+        let state = goal.apply(State::new()).unwrap();
+        assert_eq!(state.domain(&x), Some(&[1, 2, 3][..]));
+        let goal = lvec::member(y, lvec![1, y]);
+        let state = goal.apply(State::new()).unwrap();
+        assert_eq!(state.domain(&y), None);
+    }
+}
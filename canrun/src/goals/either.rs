@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::core::{Fork, State, StateIter};
+use crate::export::{describe, Describe, Formula, UnsupportedGoal};
 
 use super::Goal;
 
@@ -63,6 +64,19 @@ impl Goal for Either {
     fn apply(&self, state: State) -> Option<State> {
         state.fork(self.clone())
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for Either {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(Formula::Or(vec![
+            describe(self.a.as_ref())?,
+            describe(self.b.as_ref())?,
+        ]))
+    }
 }
 
 impl Fork for Either {
@@ -71,6 +85,12 @@ impl Fork for Either {
         let b = self.b.apply(state.clone()).into_iter();
         Box::new(a.chain(b))
     }
+
+    /// Counts the sub-goals that do not fail as soon as they are applied.
+    fn branching(&self, state: &State) -> Option<usize> {
+        let viable = [&self.a, &self.b].into_iter();
+        Some(viable.filter(|g| g.apply(state.clone()).is_some()).count())
+    }
 }
 
 #[cfg(test)]
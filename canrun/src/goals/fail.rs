@@ -1,4 +1,5 @@
 use crate::core::State;
+use crate::export::{Describe, Formula, UnsupportedGoal};
 use crate::goals::Goal;
 
 /** A [`Goal`] that always fails.
@@ -20,6 +21,16 @@ impl Goal for Fail {
     fn apply(&self, _: State) -> Option<State> {
         None
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for Fail {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(Formula::Bool(false))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,279 @@
+use super::Rational;
+use crate::core::{LVar, State, Value};
+use crate::ops::CheckedArith;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/** A sum of [`Rational`] multiples of variables, plus a constant.
+
+Expressions are usually built with the arithmetic operators, starting from
+an [`LVar<Rational>`](LVar), and then passed to the [`linear`](super) goals.
+Anything that converts into a [`Rational`] can be used as a constant. Like
+those of [`Rational`], the operators panic if a coefficient overflows.
+
+# Example:
+```
+use canrun::LVar;
+use canrun::linear::{LinExpr, Rational};
+
+let (x, y) = (LVar::<Rational>::new(), LVar::new());
+let expr: LinExpr = 2 * x - y / 3 + 1;
+assert_eq!(expr.constant(), Rational::ONE);
+```
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinExpr {
+    terms: Vec<(LVar<Rational>, Rational)>,
+    constant: Rational,
+}
+
+impl LinExpr {
+    /// The constant part of the expression.
+    pub fn constant(&self) -> Rational {
+        self.constant
+    }
+
+    /// The coefficient of each variable, in the order they were created.
+    /// Variables with a coefficient of zero are left out.
+    pub fn terms(&self) -> &[(LVar<Rational>, Rational)] {
+        &self.terms
+    }
+
+    /// Combine terms for the same variable and drop any that cancel out.
+    fn normalized(mut self) -> Option<LinExpr> {
+        self.terms.sort_by_key(|(var, _)| *var);
+        let mut terms: Vec<(LVar<Rational>, Rational)> = Vec::with_capacity(self.terms.len());
+        for (var, coef) in self.terms {
+            match terms.last_mut() {
+                Some((last, sum)) if *last == var => *sum = sum.checked_add(coef)?,
+                _ => terms.push((var, coef)),
+            }
+        }
+        terms.retain(|(_, coef)| *coef != Rational::ZERO);
+        Some(LinExpr {
+            terms,
+            constant: self.constant,
+        })
+    }
+
+    /// Add two expressions, or return `None` if a coefficient overflows.
+    pub(super) fn checked_add(mut self, other: LinExpr) -> Option<LinExpr> {
+        self.terms.extend(other.terms);
+        self.constant = self.constant.checked_add(other.constant)?;
+        self.normalized()
+    }
+
+    /// Scale an expression, or return `None` if a coefficient overflows.
+    pub(super) fn checked_mul(self, factor: Rational) -> Option<LinExpr> {
+        let terms = self
+            .terms
+            .into_iter()
+            .map(|(var, coef)| Some((var, coef.checked_mul(factor)?)))
+            .collect::<Option<_>>()?;
+        LinExpr {
+            terms,
+            constant: self.constant.checked_mul(factor)?,
+        }
+        .normalized()
+    }
+
+    /// The expression with the term for `var` left out.
+    pub(super) fn without(&self, var: LVar<Rational>) -> LinExpr {
+        LinExpr {
+            terms: self
+                .terms
+                .iter()
+                .filter(|(v, _)| *v != var)
+                .copied()
+                .collect(),
+            constant: self.constant,
+        }
+    }
+
+    /// Replace `var` with `expr`, or return `None` if a coefficient
+    /// overflows.
+    pub(super) fn substitute(&self, var: LVar<Rational>, expr: &LinExpr) -> Option<LinExpr> {
+        let Some(&(_, coef)) = self.terms.iter().find(|(v, _)| *v == var) else {
+            return Some(self.clone());
+        };
+        self.without(var)
+            .checked_add(expr.clone().checked_mul(coef)?)
+    }
+
+    /// Replace every variable that is bound in `state` with what it is bound
+    /// to, or return `None` if a coefficient overflows.
+    pub(super) fn resolve_in(&self, state: &State) -> Option<LinExpr> {
+        let mut resolved = LinExpr::from(self.constant);
+        for &(var, coef) in &self.terms {
+            let term = match state.resolve(&Value::Var(var)) {
+                Value::Resolved(value) => LinExpr::from(value.checked_mul(coef)?),
+                Value::Var(var) => LinExpr::from(var).checked_mul(coef)?,
+            };
+            resolved = resolved.checked_add(term)?;
+        }
+        Some(resolved)
+    }
+
+    /// Returns true if any of the variables is bound in `state`.
+    pub(super) fn has_bound_vars(&self, state: &State) -> bool {
+        self.terms
+            .iter()
+            .any(|&(var, _)| state.resolve(&Value::Var(var)) != Value::Var(var))
+    }
+}
+
+impl From<LVar<Rational>> for LinExpr {
+    fn from(var: LVar<Rational>) -> LinExpr {
+        LinExpr {
+            terms: vec![(var, Rational::ONE)],
+            constant: Rational::ZERO,
+        }
+    }
+}
+
+macro_rules! impl_lin_expr_from {
+    ($($type:ty),+) => {
+        $(
+            impl From<$type> for LinExpr {
+                fn from(constant: $type) -> LinExpr {
+                    LinExpr {
+                        terms: Vec::new(),
+                        constant: constant.into(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_lin_expr_from!(Rational, i8, i16, i32, i64, u8, u16, u32, u64);
+
+// Only one integer type gets the operators with a constant on the left, so
+// that integer literals like the `2` in `2 * x` can be inferred.
+macro_rules! impl_lin_expr_ops {
+    ($($type:ty),+) => {
+        $(
+            impl Mul<LVar<Rational>> for $type {
+                type Output = LinExpr;
+                fn mul(self, var: LVar<Rational>) -> LinExpr {
+                    LinExpr::from(var) * self
+                }
+            }
+
+            impl_lin_expr_ops!(@ops $type, LVar<Rational>);
+            impl_lin_expr_ops!(@ops $type, LinExpr);
+        )+
+    };
+    (@ops $type:ty, $other:ty) => {
+        impl Add<$other> for $type {
+            type Output = LinExpr;
+            fn add(self, other: $other) -> LinExpr {
+                LinExpr::from(self) + other
+            }
+        }
+
+        impl Sub<$other> for $type {
+            type Output = LinExpr;
+            fn sub(self, other: $other) -> LinExpr {
+                LinExpr::from(self) - other
+            }
+        }
+    };
+}
+
+impl_lin_expr_ops!(Rational, i32);
+
+impl<E: Into<LinExpr>> Add<E> for LinExpr {
+    type Output = LinExpr;
+    fn add(self, other: E) -> LinExpr {
+        self.checked_add(other.into())
+            .expect("linear expression add overflowed")
+    }
+}
+
+impl<E: Into<LinExpr>> Sub<E> for LinExpr {
+    type Output = LinExpr;
+    fn sub(self, other: E) -> LinExpr {
+        self + -other.into()
+    }
+}
+
+impl<R: Into<Rational>> Mul<R> for LinExpr {
+    type Output = LinExpr;
+    fn mul(self, factor: R) -> LinExpr {
+        self.checked_mul(factor.into())
+            .expect("linear expression mul overflowed")
+    }
+}
+
+impl<R: Into<Rational>> std::ops::Div<R> for LinExpr {
+    type Output = LinExpr;
+    fn div(self, divisor: R) -> LinExpr {
+        self * (Rational::ONE / divisor)
+    }
+}
+
+impl Neg for LinExpr {
+    type Output = LinExpr;
+    fn neg(self) -> LinExpr {
+        self * -1
+    }
+}
+
+impl<E: Into<LinExpr>> Add<E> for LVar<Rational> {
+    type Output = LinExpr;
+    fn add(self, other: E) -> LinExpr {
+        LinExpr::from(self) + other
+    }
+}
+
+impl<E: Into<LinExpr>> Sub<E> for LVar<Rational> {
+    type Output = LinExpr;
+    fn sub(self, other: E) -> LinExpr {
+        LinExpr::from(self) - other
+    }
+}
+
+impl<R: Into<Rational>> Mul<R> for LVar<Rational> {
+    type Output = LinExpr;
+    fn mul(self, factor: R) -> LinExpr {
+        LinExpr::from(self) * factor
+    }
+}
+
+impl<R: Into<Rational>> std::ops::Div<R> for LVar<Rational> {
+    type Output = LinExpr;
+    fn div(self, divisor: R) -> LinExpr {
+        LinExpr::from(self) / divisor
+    }
+}
+
+impl Neg for LVar<Rational> {
+    type Output = LinExpr;
+    fn neg(self) -> LinExpr {
+        -LinExpr::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinExpr;
+    use crate::linear::Rational;
+    use crate::LVar;
+
+    #[test]
+    fn combines_terms() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let expr = x + 2 * y - x + 3;
+        assert_eq!(expr.terms(), &[(y, Rational::from(2))]);
+        assert_eq!(expr.constant(), Rational::from(3));
+        let expr: LinExpr = x - x;
+        assert!(expr.terms().is_empty());
+    }
+
+    #[test]
+    fn substitutes() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let expr = (2 * x + y).substitute(x, &(y + 1));
+        assert_eq!(expr, Some(3 * y + 2));
+    }
+}
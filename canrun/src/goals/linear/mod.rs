@@ -0,0 +1,287 @@
+/*!
+Linear arithmetic constraints over [`Rational`] numbers.
+
+The [`eq`], [`le`] and [`ge`] goals relate [`LinExpr`]s over
+[`LVar<Rational>`](crate::LVar)s, such as `2x + 3y <= 12`. Unlike the
+[`ops`](crate::ops) goals they don't wait for their variables to be
+resolved. Every linear constraint in a state is kept in one shared system,
+and each time a constraint is added or one of its variables is bound, the
+system is checked for a solution. A state fails as soon as the constraints
+contradict each other.
+
+Equalities are solved by Gaussian elimination, and any variable they leave
+with only one possible value is bound to it so that it can be reified. The
+remaining inequalities are checked with the simplex method. A variable that
+is only pinned down by inequalities (such as `x <= 1` and `x >= 1`) is not
+bound, and so won't be reified. The system doesn't keep a state from being
+reified as long as it has a solution, so other variables can still be
+queried when some of the linear ones are left free.
+
+# Example:
+```
+use canrun::{all, LVar, Query};
+use canrun::linear::{eq, le, Rational};
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = all![
+    le(2 * x + 3 * y, 12),
+    eq(x - y, 1),
+    eq(x + y, 4),
+];
+let results: Vec<_> = goal.query((x, y)).collect();
+assert_eq!(results, vec![(Rational::new(5, 2), Rational::new(3, 2))]);
+```
+*/
+
+mod expr;
+mod rational;
+mod solver;
+
+pub use expr::LinExpr;
+pub use rational::Rational;
+
+use crate::constraints::{Constraint, ResolveFn};
+use crate::goals::Goal;
+use crate::{LVarList, State};
+use solver::{solve, Relation};
+use std::fmt;
+use std::rc::Rc;
+
+/** Create a [goal](crate::goals::Goal) that requires `lhs = rhs`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::linear::{eq, Rational};
+
+let x = LVar::new();
+let goal = eq(3 * x + 1, 2);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![Rational::new(1, 3)]);
+```
+*/
+pub fn eq(lhs: impl Into<LinExpr>, rhs: impl Into<LinExpr>) -> Linear {
+    Linear {
+        expr: lhs.into() - rhs.into(),
+        relation: Relation::Eq,
+    }
+}
+
+/** Create a [goal](crate::goals::Goal) that requires `lhs <= rhs`.
+
+# Example:
+```
+use canrun::{all, LVar, Goal, State};
+use canrun::linear::{le, Rational};
+
+let (x, y) = (LVar::<Rational>::new(), LVar::new());
+let goal = all![le(x + y, 1), le(1, x), le(1, y)];
+assert!(goal.apply(State::new()).is_none());
+```
+*/
+pub fn le(lhs: impl Into<LinExpr>, rhs: impl Into<LinExpr>) -> Linear {
+    Linear {
+        expr: lhs.into() - rhs.into(),
+        relation: Relation::Le,
+    }
+}
+
+/** Create a [goal](crate::goals::Goal) that requires `lhs >= rhs`.
+
+# Example:
+```
+use canrun::{all, LVar, Query};
+use canrun::linear::{eq, ge, Rational};
+
+let x = LVar::new();
+let goal = all![ge(x, 2), eq(2 * x, 6)];
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![Rational::from(3)]);
+```
+*/
+pub fn ge(lhs: impl Into<LinExpr>, rhs: impl Into<LinExpr>) -> Linear {
+    le(rhs, lhs)
+}
+
+/** A [goal](crate::goals::Goal) that adds a linear constraint to the shared
+system. Create with [`eq`], [`le`] or [`ge`]. */
+#[derive(Clone)]
+pub struct Linear {
+    expr: LinExpr,
+    relation: Relation,
+}
+
+impl fmt::Debug for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relation = match self.relation {
+            Relation::Eq => "=",
+            Relation::Le => "<=",
+        };
+        write!(f, "Linear {:?} {relation} 0", self.expr)
+    }
+}
+
+impl Goal for Linear {
+    fn apply(&self, state: State) -> Option<State> {
+        let (mut rows, watch) = match state.store::<System>() {
+            Some(system) => (system.rows.to_vec(), system.watch + 1),
+            None => (Vec::new(), 0),
+        };
+        rows.push((self.expr.clone(), self.relation));
+        let system = System {
+            rows: Rc::new(rows),
+            watch,
+        };
+        // Replace the `Watch` so that it covers the new variables as well.
+        // The earlier one is retired the next time it is attempted.
+        check(state.with_store(system))?.constrain(Rc::new(Watch(watch)))
+    }
+}
+
+/// Every linear constraint in a [`State`], along with the id of the [`Watch`]
+/// that looks after them.
+struct System {
+    rows: Rc<Vec<(LinExpr, Relation)>>,
+    watch: usize,
+}
+
+/// Substitute any newly bound variables into the system, make sure it still
+/// has a solution and bind the variables it determines.
+fn check(state: State) -> Option<State> {
+    let system = state.store::<System>()?;
+    let rows: Vec<_> = system
+        .rows
+        .iter()
+        .map(|(expr, relation)| Some((expr.resolve_in(&state)?, *relation)))
+        .collect::<Option<_>>()?;
+    let watch = system.watch;
+    let determined = solve(&rows)?;
+    // Constant rows were just checked, so there is no need to keep them.
+    let rows = rows
+        .into_iter()
+        .filter(|(expr, _)| !expr.terms().is_empty())
+        .collect();
+    let state = state.with_store(System {
+        rows: Rc::new(rows),
+        watch,
+    });
+    determined
+        .into_iter()
+        .try_fold(state, |state, (var, value)| {
+            state.unify(&var.into(), &value.into())
+        })
+}
+
+/// Rechecks the [`System`] whenever one of its variables is bound. Only the
+/// most recently added `Watch` does anything, so the system is checked once
+/// per binding no matter how many constraints it has.
+struct Watch(usize);
+
+impl fmt::Debug for Watch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Watch({})", self.0)
+    }
+}
+
+impl Constraint for Watch {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let Some(system) = state.store::<System>() else {
+            return Ok(Box::new(Some));
+        };
+        if system.watch != self.0 || system.rows.is_empty() {
+            return Ok(Box::new(Some));
+        }
+        if system
+            .rows
+            .iter()
+            .any(|(expr, _)| expr.has_bound_vars(state))
+        {
+            let watch = self.0;
+            return Ok(Box::new(move |state| {
+                check(state)?.constrain(Rc::new(Watch(watch)))
+            }));
+        }
+        let vars = system
+            .rows
+            .iter()
+            .flat_map(|(expr, _)| expr.terms().iter().map(|(var, _)| var.id))
+            .collect();
+        Err(LVarList(vars))
+    }
+
+    /// The system is checked every time one of its variables is bound, so it
+    /// still has a solution whenever a `Watch` is waiting.
+    fn is_satisfiable(&self, _state: &State) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eq, ge, le, Rational};
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn determines_vars() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![eq(x + y, 10), eq(x - y, 2)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(6.into(), 4.into())]);
+    }
+
+    #[test]
+    fn checks_bound_vars() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            le(x + y, 4),
+            unify(x, Rational::from(3)),
+            unify(y, Rational::from(1))
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(3.into(), 1.into())]);
+        let goals = goal_vec![
+            le(x + y, 4),
+            unify(x, Rational::from(3)),
+            unify(y, Rational::from(2))
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![]);
+    }
+
+    #[test]
+    fn derives_from_bound_vars() {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![
+            eq(x + y, z),
+            ge(z, 1),
+            unify(x, Rational::from(2)),
+            unify(z, Rational::from(5))
+        ];
+        goals.assert_permutations_resolve_to(&y, vec![3.into()]);
+    }
+
+    #[test]
+    fn leaves_free_vars_unresolved() {
+        let (x, y, z) = (LVar::<Rational>::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![le(x, 10), unify(y, 1)];
+        goals.assert_permutations_resolve_to(&y, vec![1]);
+        let goals = goal_vec![eq(x + z, 10), le(x, 3), unify(y, 1)];
+        goals.assert_permutations_resolve_to(&y, vec![1]);
+        let goals = goal_vec![eq(x + z, 10), le(x, 3), unify(z, Rational::from(8))];
+        goals.assert_permutations_resolve_to(&(x, z), vec![(2.into(), 8.into())]);
+    }
+
+    #[test]
+    fn checks_vars_bound_later() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![le(x, 10), unify(y, 1), unify(x, Rational::from(11))];
+        goals.assert_permutations_resolve_to(&y, vec![]);
+    }
+
+    #[test]
+    fn fails_early() {
+        use crate::{Goal, State};
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let goal = crate::all![eq(x - y, 1), le(2 * x + 3 * y, 1), ge(y, 0)];
+        assert!(goal.apply(State::new()).is_none());
+        let goal = crate::all![eq(x - y, 1), le(2 * x + 3 * y, 12), ge(y, 0)];
+        assert!(goal.apply(State::new()).is_some());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::core::{ReadyState, Reify, State, Unify};
+use crate::ops::CheckedArith;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::Rc;
+
+/** An exact fraction, used as the value of [`linear`](super) variables.
+
+Rationals are always kept in lowest terms with a positive denominator, so
+two equal rationals are also structurally equal.
+
+# Panics
+
+The arithmetic operators panic if the numerator or denominator overflows an
+`i128`. The [`CheckedArith`] methods return `None` instead.
+
+# Example:
+```
+use canrun::linear::Rational;
+
+let half = Rational::new(1, 2);
+assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+assert_eq!(half * 4, Rational::from(2));
+assert_eq!(Rational::new(2, -4).to_string(), "-1/2");
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+/// The greatest common divisor, or `None` if it is `2^127` and so doesn't
+/// fit in an `i128`.
+fn gcd(mut a: i128, mut b: i128) -> Option<i128> {
+    while b != 0 {
+        // Only `i128::MIN % -1` wraps, and its remainder is zero anyway.
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_abs()
+}
+
+impl Rational {
+    /// Zero, as a constant.
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    /// One, as a constant.
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Create the rational `num / den`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero, or if the fraction can't be reduced to lowest
+    /// terms with a positive denominator (as with `i128::MIN / i128::MIN`).
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "rational with a zero denominator");
+        Rational::checked_new(num, den).expect("rational new overflowed")
+    }
+
+    fn checked_new(num: i128, den: i128) -> Option<Rational> {
+        if den == 0 {
+            return None;
+        }
+        let divisor = gcd(num, den)?.checked_mul(den.signum())?;
+        Some(Rational {
+            num: num.checked_div(divisor)?,
+            den: den.checked_div(divisor)?,
+        })
+    }
+
+    /// The numerator, which carries the sign.
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    /// Returns true if this rational is a whole number.
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Compare two rationals, or return `None` if the cross products
+    /// overflow.
+    pub(super) fn cmp_checked(&self, other: &Rational) -> Option<Ordering> {
+        let left = self.num.checked_mul(other.den)?;
+        let right = other.num.checked_mul(self.den)?;
+        Some(left.cmp(&right))
+    }
+}
+
+impl CheckedArith for Rational {
+    const ZERO: Self = Rational::ZERO;
+    const ONE: Self = Rational::ONE;
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let den = self.den.checked_mul(other.den)?;
+        let num =
+            (self.num.checked_mul(other.den)?).checked_add(other.num.checked_mul(self.den)?)?;
+        Rational::checked_new(num, den)
+    }
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(Rational {
+            num: other.num.checked_neg()?,
+            den: other.den,
+        })
+    }
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        let num = self.num.checked_mul(other.num)?;
+        let den = self.den.checked_mul(other.den)?;
+        Rational::checked_new(num, den)
+    }
+    fn checked_div(self, other: Self) -> Option<Self> {
+        let num = self.num.checked_mul(other.den)?;
+        let den = self.den.checked_mul(other.num)?;
+        Rational::checked_new(num, den)
+    }
+    /// The remainder after truncating division, with the sign of `self`.
+    fn checked_rem(self, other: Self) -> Option<Self> {
+        let quotient = self.checked_div(other)?;
+        let whole = Rational {
+            num: quotient.num / quotient.den,
+            den: 1,
+        };
+        self.checked_sub(whole.checked_mul(other)?)
+    }
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        Some(Rational {
+            num: self.num.checked_pow(exp)?,
+            den: self.den.checked_pow(exp)?,
+        })
+    }
+}
+
+macro_rules! impl_rational_op {
+    ($($trait:ident $method:ident $checked:ident),+) => {
+        $(
+            impl<R: Into<Rational>> $trait<R> for Rational {
+                type Output = Rational;
+                fn $method(self, other: R) -> Rational {
+                    CheckedArith::$checked(self, other.into())
+                        .expect(concat!("rational ", stringify!($method), " overflowed"))
+                }
+            }
+        )+
+    };
+}
+
+impl_rational_op!(Add add checked_add, Sub sub checked_sub, Mul mul checked_mul);
+
+impl<R: Into<Rational>> Div<R> for Rational {
+    type Output = Rational;
+    fn div(self, other: R) -> Rational {
+        let other = other.into();
+        assert!(other != Rational::ZERO, "rational division by zero");
+        self.checked_div(other).expect("rational div overflowed")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational::ZERO - self
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_checked(other)
+            .expect("rational comparison overflowed")
+    }
+}
+
+macro_rules! impl_rational_from {
+    ($($type:ty),+) => {
+        $(
+            impl From<$type> for Rational {
+                fn from(value: $type) -> Rational {
+                    Rational {
+                        num: value.into(),
+                        den: 1,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_rational_from!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Unify for Rational {
+    fn unify(state: State, a: Rc<Rational>, b: Rc<Rational>) -> Option<State> {
+        (a == b).then_some(state)
+    }
+}
+
+impl Reify for Rational {
+    type Reified = Rational;
+    fn reify_in(&self, _: &ReadyState) -> Option<Rational> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rational;
+    use crate::ops::CheckedArith;
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(Rational::new(4, -6), Rational::new(-2, 3));
+        assert_eq!(Rational::new(0, -6), Rational::ZERO);
+        assert_eq!(Rational::new(-4, -2).to_string(), "2");
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let (a, b) = (Rational::new(1, 2), Rational::new(-2, 3));
+        assert_eq!(a + b, Rational::new(-1, 6));
+        assert_eq!(a - b, Rational::new(7, 6));
+        assert_eq!(a * b, Rational::new(-1, 3));
+        assert_eq!(a / b, Rational::new(-3, 4));
+        assert_eq!(-a, Rational::new(-1, 2));
+        assert!(b < a);
+    }
+
+    #[test]
+    fn checks_arithmetic() {
+        let big = Rational::from(i64::MAX) * Rational::from(i64::MAX);
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(Rational::ONE.checked_div(Rational::ZERO), None);
+        let rem = Rational::new(7, 2).checked_rem(Rational::ONE);
+        assert_eq!(rem, Some(Rational::new(1, 2)));
+    }
+
+    #[test]
+    fn checks_extreme_values() {
+        let min = Rational::new(i128::MIN, 1);
+        assert_eq!(Rational::new(i128::MIN, 2), Rational::new(i128::MIN / 2, 1));
+        assert_eq!(min.checked_div(min), None);
+        assert_eq!(min.checked_mul(Rational::new(-1, 1)), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Decide whether a set of linear constraints has a solution.
+//!
+//! Equalities are removed by Gaussian elimination, which also tells us which
+//! variables have only one possible value. The inequalities that are left are
+//! then checked with the first phase of the simplex method.
+
+use std::cmp::Ordering;
+
+use super::{LinExpr, Rational};
+use crate::ops::CheckedArith;
+use crate::LVar;
+
+/// Whether an expression must be equal to zero or at most zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Relation {
+    Eq,
+    Le,
+}
+
+/// Returns the variables with only one possible value, or `None` if the
+/// constraints contradict each other. Constraints whose coefficients overflow
+/// along the way are treated as contradictory.
+pub(super) fn solve(rows: &[(LinExpr, Relation)]) -> Option<Vec<(LVar<Rational>, Rational)>> {
+    let select = |relation| -> Vec<LinExpr> {
+        rows.iter()
+            .filter(|(_, rel)| *rel == relation)
+            .map(|(expr, _)| expr.clone())
+            .collect()
+    };
+    let (mut eqs, mut les) = (select(Relation::Eq), select(Relation::Le));
+
+    let mut solved: Vec<(LVar<Rational>, LinExpr)> = Vec::new();
+    while let Some(eq) = eqs.pop() {
+        let Some(&(var, coef)) = eq.terms().first() else {
+            if eq.constant() == Rational::ZERO {
+                continue;
+            }
+            return None;
+        };
+        // Solve `coef * var + rest = 0` for `var`.
+        let value = eq
+            .without(var)
+            .checked_mul(negate(Rational::ONE.checked_div(coef)?)?)?;
+        let others = eqs.iter_mut().chain(&mut les);
+        for expr in others.chain(solved.iter_mut().map(|(_, expr)| expr)) {
+            *expr = expr.substitute(var, &value)?;
+        }
+        solved.push((var, value));
+    }
+
+    let mut bounded = Vec::new();
+    for le in les {
+        if le.terms().is_empty() {
+            if le.constant() > Rational::ZERO {
+                return None;
+            }
+        } else {
+            bounded.push(le);
+        }
+    }
+    if !feasible(&bounded)? {
+        return None;
+    }
+
+    Some(
+        solved
+            .into_iter()
+            .filter(|(_, expr)| expr.terms().is_empty())
+            .map(|(var, expr)| (var, expr.constant()))
+            .collect(),
+    )
+}
+
+/// Check that some assignment makes every expression at most zero, using
+/// phase one of the simplex method with Bland's rule.
+///
+/// Each variable `x` is split into `p - q` with `p, q >= 0`, each
+/// `expr <= 0` gets a slack variable to become an equality, and rows that
+/// aren't satisfied with everything at zero get an artificial variable. The
+/// constraints are feasible if the artificial variables can all be driven to
+/// zero. Returns `None` if the arithmetic overflows.
+fn feasible(les: &[LinExpr]) -> Option<bool> {
+    let mut vars: Vec<LVar<Rational>> = les
+        .iter()
+        .flat_map(|le| le.terms().iter().map(|(var, _)| *var))
+        .collect();
+    vars.sort_unstable();
+    vars.dedup();
+    let n = vars.len();
+    let m = les.len();
+    let slack = 2 * n;
+    let artificial = slack + m;
+
+    let mut rows: Vec<Vec<Rational>> = Vec::with_capacity(m);
+    let mut rhs: Vec<Rational> = Vec::with_capacity(m);
+    let mut basis: Vec<usize> = Vec::with_capacity(m);
+    let mut artificial_rows = Vec::new();
+    for (i, le) in les.iter().enumerate() {
+        // `terms + constant <= 0` becomes `terms + slack = -constant`.
+        let mut row = vec![Rational::ZERO; artificial + m];
+        for (var, coef) in le.terms() {
+            let j = vars.binary_search(var).expect("collected above");
+            row[j] = *coef;
+            row[n + j] = negate(*coef)?;
+        }
+        row[slack + i] = Rational::ONE;
+        let mut b = negate(le.constant())?;
+        if b < Rational::ZERO {
+            for v in &mut row {
+                *v = negate(*v)?;
+            }
+            b = negate(b)?;
+            row[artificial + i] = Rational::ONE;
+            basis.push(artificial + i);
+            artificial_rows.push(i);
+        } else {
+            basis.push(slack + i);
+        }
+        rows.push(row);
+        rhs.push(b);
+    }
+    if artificial_rows.is_empty() {
+        return Some(true);
+    }
+
+    // The sum of the artificial variables in terms of the non-basic ones.
+    let width = artificial + m;
+    let mut costs = vec![Rational::ZERO; width];
+    let mut objective = Rational::ZERO;
+    for &i in &artificial_rows {
+        for j in 0..artificial {
+            costs[j] = costs[j].checked_sub(rows[i][j])?;
+        }
+        objective = objective.checked_add(rhs[i])?;
+    }
+
+    while objective > Rational::ZERO {
+        let Some(entering) = (0..width).find(|&j| costs[j] < Rational::ZERO) else {
+            return Some(false);
+        };
+        let mut leaving: Option<(usize, Rational)> = None;
+        for i in (0..m).filter(|&i| rows[i][entering] > Rational::ZERO) {
+            let ratio = rhs[i].checked_div(rows[i][entering])?;
+            let smaller = match leaving {
+                None => true,
+                Some((best, best_ratio)) => {
+                    let order = ratio.cmp_checked(&best_ratio)?;
+                    order.then(basis[i].cmp(&basis[best])) == Ordering::Less
+                }
+            };
+            if smaller {
+                leaving = Some((i, ratio));
+            }
+        }
+        let Some((leaving, _)) = leaving else {
+            // The objective can't go below zero, so this shouldn't happen.
+            return Some(true);
+        };
+
+        let pivot = rows[leaving][entering];
+        let pivot_row = rows[leaving]
+            .iter()
+            .map(|v| v.checked_div(pivot))
+            .collect::<Option<Vec<_>>>()?;
+        let pivot_rhs = rhs[leaving].checked_div(pivot)?;
+        // Subtract `factor` times the pivot row from `row`.
+        let eliminate = |row: &mut [Rational], factor: Rational| -> Option<()> {
+            for (v, p) in row.iter_mut().zip(&pivot_row) {
+                *v = v.checked_sub(factor.checked_mul(*p)?)?;
+            }
+            Some(())
+        };
+        for (i, row) in rows.iter_mut().enumerate() {
+            let factor = row[entering];
+            if i != leaving && factor != Rational::ZERO {
+                eliminate(row, factor)?;
+                rhs[i] = rhs[i].checked_sub(factor.checked_mul(pivot_rhs)?)?;
+            }
+        }
+        let factor = costs[entering];
+        eliminate(&mut costs, factor)?;
+        objective = objective.checked_add(factor.checked_mul(pivot_rhs)?)?;
+        rows[leaving] = pivot_row;
+        rhs[leaving] = pivot_rhs;
+        basis[leaving] = entering;
+    }
+    Some(true)
+}
+
+fn negate(value: Rational) -> Option<Rational> {
+    Rational::ZERO.checked_sub(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, Relation};
+    use crate::linear::Rational;
+    use crate::LVar;
+
+    #[test]
+    fn eliminates_equalities() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let rows = [(x + y - 10, Relation::Eq), (x - y - 2, Relation::Eq)];
+        let mut solved = solve(&rows).unwrap();
+        solved.sort_unstable();
+        assert_eq!(solved, vec![(x, Rational::from(6)), (y, Rational::from(4))]);
+    }
+
+    #[test]
+    fn checks_inequalities() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let rows = [
+            (x + y - 1, Relation::Le),
+            (1 - x, Relation::Le),
+            (Rational::new(1, 2) - y, Relation::Le),
+        ];
+        assert_eq!(solve(&rows), None);
+        let rows = [
+            (x + y - 2, Relation::Le),
+            (1 - x, Relation::Le),
+            (Rational::new(1, 2) - y, Relation::Le),
+        ];
+        assert_eq!(solve(&rows), Some(vec![]));
+    }
+
+    #[test]
+    fn combines_equalities_and_inequalities() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let rows = [
+            (x - y - 1, Relation::Eq),
+            (2 * x + 3 * y - 1, Relation::Le),
+            (-y, Relation::Le),
+        ];
+        assert_eq!(solve(&rows), None);
+        let rows = [
+            (x - y - 1, Relation::Eq),
+            (2 * x + 3 * y - 12, Relation::Le),
+            (-y, Relation::Le),
+        ];
+        assert_eq!(solve(&rows), Some(vec![]));
+    }
+
+    #[test]
+    fn fails_on_overflow() {
+        let (x, y) = (LVar::<Rational>::new(), LVar::new());
+        let (a, b) = (Rational::new(1, i128::MAX), Rational::new(1, i128::MAX - 1));
+        let rows = [(x + y * a, Relation::Eq), (x + y * b - 1, Relation::Eq)];
+        assert_eq!(solve(&rows), None);
+        let rows = [(x + y * a, Relation::Le), (1 - x - y * b, Relation::Le)];
+        assert_eq!(solve(&rows), None);
+    }
+}
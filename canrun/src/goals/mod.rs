@@ -20,15 +20,21 @@ and other niceties.
 use std::{fmt::Debug, rc::Rc};
 
 use crate::core::State;
+use crate::export::Describe;
 
+pub mod aggregate;
 mod all;
 mod any;
+pub mod bool;
 mod both;
 pub mod cmp;
 mod custom;
+pub mod domain;
 mod either;
 mod fail;
 mod lazy;
+pub mod linear;
+pub mod nat;
 mod not;
 pub mod ops;
 pub mod project;
@@ -75,22 +81,43 @@ pub trait Goal: Debug + 'static {
     ```
     */
     fn apply(&self, state: State) -> Option<State>;
+
+    /**
+    Returns the goal as a [`Describe`] trait object if it can be
+    [exported](crate::export) to an external solver. Goals that combine
+    other goals, such as [`All`], use this to describe their sub-goals.
+    */
+    fn describe(&self) -> Option<&dyn Describe> {
+        None
+    }
 }
 
 impl Goal for Rc<dyn Goal> {
     fn apply(&self, state: State) -> Option<State> {
         self.as_ref().apply(state)
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        self.as_ref().describe()
+    }
 }
 
 impl<G: Goal> Goal for Rc<G> {
     fn apply(&self, state: State) -> Option<State> {
         self.as_ref().apply(state)
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        self.as_ref().describe()
+    }
 }
 
 impl<G: Goal> Goal for Box<G> {
     fn apply(&self, state: State) -> Option<State> {
         self.as_ref().apply(state)
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        self.as_ref().describe()
+    }
 }
@@ -0,0 +1,159 @@
+use super::lto::{eqlo, ltlo};
+use super::{lto, minuso, pluso, poso, timeso, Nat};
+use crate::core::{LVar, Value};
+use crate::goals::{lazy, unify, Goal};
+use crate::{all, llist};
+
+/**
+Assert that `n = m * q + r` where `r < m`, for [`Nat`] numbers.
+
+In other words, `q` is the quotient and `r` the remainder of dividing `n` by
+`m`. This works with any combination of resolved and unresolved values, and
+will enumerate every solution.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{divo, from_u64, to_u64};
+
+let (q, r) = (LVar::new(), LVar::new());
+let goal = divo(from_u64(17), from_u64(5), &q, &r);
+let results: Vec<_> = goal
+    .query((q, r))
+    .map(|(q, r)| (to_u64(&q), to_u64(&r)))
+    .collect();
+assert_eq!(results, vec![(Some(3), Some(2))]);
+```
+*/
+pub fn divo(
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    q: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, m, q, r) = (n.into(), m.into(), q.into(), r.into());
+    lazy(move || {
+        let (nh, nl, qh, ql) = (LVar::new(), LVar::new(), LVar::new(), LVar::new());
+        let (qlm, qlmr, rr, rh) = (LVar::new(), LVar::new(), LVar::new(), LVar::new());
+        conde![
+            all![unify(&r, &n), unify(&q, llist![]), lto(&n, &m)],
+            all![
+                unify(&q, llist![true]),
+                eqlo(&n, &m),
+                pluso(&r, &m, &n),
+                lto(&r, &m),
+            ],
+            all![
+                ltlo(&m, &n),
+                lto(&r, &m),
+                poso(&q),
+                splito(&n, &r, &nl, &nh),
+                splito(&q, &r, &ql, &qh),
+                conde![
+                    all![
+                        unify(&nh, llist![]),
+                        unify(&qh, llist![]),
+                        minuso(&nl, &r, &qlm),
+                        timeso(&ql, &m, &qlm),
+                    ],
+                    all![
+                        poso(&nh),
+                        timeso(ql, &m, &qlm),
+                        pluso(qlm, &r, &qlmr),
+                        minuso(qlmr, nl, &rr),
+                        splito(rr, &r, llist![], &rh),
+                        divo(nh, &m, qh, rh),
+                    ],
+                ],
+            ],
+        ]
+    })
+}
+
+/// Split `n` into the number `l` made of its lowest `len(r) + 1` bits, and
+/// the number `h` made of the rest.
+pub(super) fn splito(
+    n: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+    l: impl Into<Value<Nat>>,
+    h: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, r, l, h) = (n.into(), r.into(), l.into(), h.into());
+    lazy(move || {
+        let (b, rest_n, rest_r, rest_l) = (
+            LVar::<bool>::new(),
+            LVar::<Nat>::new(),
+            LVar::<Nat>::new(),
+            LVar::<Nat>::new(),
+        );
+        conde![
+            all![
+                unify(&n, llist![]),
+                unify(&h, llist![]),
+                unify(&l, llist![]),
+            ],
+            all![
+                unify(&n, llist![false, b | &rest_n]),
+                unify(&r, llist![]),
+                unify(&h, llist![b | &rest_n]),
+                unify(&l, llist![]),
+            ],
+            all![
+                unify(&n, llist![true | &rest_n]),
+                unify(&r, llist![]),
+                unify(&h, &rest_n),
+                unify(&l, llist![true]),
+            ],
+            all![
+                unify(&n, llist![false, b | &rest_n]),
+                unify(&r, llist![LVar::<bool>::new() | &rest_r]),
+                unify(&l, llist![]),
+                splito(llist![b | &rest_n], &rest_r, llist![], &h),
+            ],
+            all![
+                unify(&n, llist![true | &rest_n]),
+                unify(&r, llist![LVar::<bool>::new() | &rest_r]),
+                unify(&l, llist![true]),
+                splito(&rest_n, &rest_r, llist![], &h),
+            ],
+            all![
+                unify(&n, llist![b | &rest_n]),
+                unify(&r, llist![LVar::<bool>::new() | &rest_r]),
+                unify(&l, llist![b | &rest_l]),
+                poso(&rest_l),
+                splito(rest_n, rest_r, rest_l, &h),
+            ],
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::divo;
+    use crate::goals::nat::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query};
+
+    fn divide(n: u64, m: u64) -> Vec<(u64, u64)> {
+        let (q, r) = (LVar::new(), LVar::new());
+        let goal = divo(from_u64(n), from_u64(m), &q, &r);
+        goal.query((q, r))
+            .map(|(q, r)| (to_u64(&q).unwrap(), to_u64(&r).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn divides() {
+        assert_eq!(divide(17, 5), vec![(3, 2)]);
+        assert_eq!(divide(5, 17), vec![(0, 5)]);
+        assert_eq!(divide(100, 10), vec![(10, 0)]);
+        assert_eq!(divide(5, 0), vec![]);
+    }
+
+    #[test]
+    fn finds_dividends() {
+        let n = LVar::new();
+        let goal = divo(&n, from_u64(4), from_u64(3), from_u64(1));
+        let results: Vec<_> = goal.query(n).map(|n| to_u64(&n)).collect();
+        assert_eq!(results, vec![Some(13)]);
+    }
+}
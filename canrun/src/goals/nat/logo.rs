@@ -0,0 +1,221 @@
+use super::divo::splito;
+use super::lto::{eqlo, lelo, ltlo};
+use super::{appendo, divo, gt1o, leo, lto, pluso, poso, timeso, Nat};
+use crate::core::{LVar, Value};
+use crate::goals::{lazy, unify, Goal};
+use crate::{all, llist};
+
+/**
+Assert that `n = b^q + r` where `r < b^(q + 1) - b^q`, for [`Nat`] numbers.
+
+In other words, `q` is the integer logarithm of `n` in base `b`, and `r` is
+what's left over. This works with any combination of resolved and unresolved
+values, and will enumerate every solution.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, logo, to_u64};
+
+let (q, r) = (LVar::new(), LVar::new());
+let goal = logo(from_u64(14), from_u64(2), &q, &r);
+let results: Vec<_> = goal
+    .query((q, r))
+    .map(|(q, r)| (to_u64(&q), to_u64(&r)))
+    .collect();
+assert_eq!(results, vec![(Some(3), Some(6))]);
+```
+*/
+pub fn logo(
+    n: impl Into<Value<Nat>>,
+    b: impl Into<Value<Nat>>,
+    q: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, b, q, r) = (n.into(), b.into(), q.into(), r.into());
+    lazy(move || {
+        let (a, ad, add, dd, ddd) = (
+            LVar::<bool>::new(),
+            LVar::<bool>::new(),
+            LVar::<bool>::new(),
+            LVar::<Nat>::new(),
+            LVar::<Nat>::new(),
+        );
+        conde![
+            all![
+                unify(&n, llist![true]),
+                poso(&b),
+                unify(&q, llist![]),
+                unify(&r, llist![]),
+            ],
+            all![
+                unify(&q, llist![]),
+                lto(&n, &b),
+                pluso(&r, llist![true], &n),
+            ],
+            all![
+                unify(&q, llist![true]),
+                gt1o(&b),
+                eqlo(&n, &b),
+                pluso(&r, &b, &n),
+            ],
+            all![
+                unify(&b, llist![true]),
+                poso(&q),
+                pluso(&r, llist![true], &n),
+            ],
+            all![unify(&b, llist![]), poso(&q), unify(&r, &n)],
+            all![
+                unify(&b, llist![false, true]),
+                poso(&dd),
+                unify(&n, llist![a, ad | &dd]),
+                exp2(&n, llist![], &q),
+                splito(&n, dd, &r, LVar::<Nat>::new()),
+            ],
+            all![
+                conde![
+                    unify(&b, llist![true, true]),
+                    unify(&b, llist![a, ad, add | ddd]),
+                ],
+                ltlo(&b, &n),
+                large_base_logo(&n, &b, &q, &r),
+            ],
+        ]
+    })
+}
+
+/// The general case of [`logo`], for bases of three or more.
+#[allow(clippy::similar_names)]
+fn large_base_logo(
+    n: impl Into<Value<Nat>>,
+    b: impl Into<Value<Nat>>,
+    q: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, b, q, r) = (n.into(), b.into(), q.into(), r.into());
+    lazy(move || {
+        let (bw1, bw, nw, nw1, ql1, ql) = (
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+        );
+        let (q1, bwq1, bql, qh, qdh, qd) = (
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+            LVar::new(),
+        );
+        let (bqd, bq1, bq) = (LVar::new(), LVar::new(), LVar::new());
+        all![
+            exp2(&b, llist![], &bw1),
+            pluso(&bw1, llist![true], &bw),
+            ltlo(&q, &n),
+            pluso(&q, llist![true], &q1),
+            timeso(&bw, q1, &bwq1),
+            lto(&nw1, bwq1),
+            exp2(&n, llist![], &nw1),
+            pluso(nw1, llist![true], &nw),
+            divo(&nw, bw, &ql1, LVar::<Nat>::new()),
+            pluso(&ql, llist![true], ql1),
+            lelo(&ql, &q),
+            repeated_mul(&b, &ql, &bql),
+            divo(nw, bw1, &qh, LVar::<Nat>::new()),
+            pluso(&ql, &qdh, qh),
+            pluso(ql, &qd, &q),
+            leo(&qd, qdh),
+            repeated_mul(&b, qd, &bqd),
+            timeso(bql, bqd, &bq),
+            timeso(&b, &bq, &bq1),
+            pluso(bq, &r, &n),
+            lto(&n, bq1),
+        ]
+    })
+}
+
+/// Assert that `q` is the integer logarithm of `n` in base `2^(len(b) + 1)`.
+fn exp2(n: impl Into<Value<Nat>>, b: impl Into<Value<Nat>>, q: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, b, q) = (n.into(), b.into(), q.into());
+    lazy(move || {
+        let (q1, b2, nh) = (LVar::<Nat>::new(), LVar::<Nat>::new(), LVar::<Nat>::new());
+        conde![
+            all![unify(&n, llist![true]), unify(&q, llist![])],
+            all![
+                gt1o(&n),
+                unify(&q, llist![true]),
+                splito(&n, &b, LVar::<Nat>::new(), llist![true]),
+            ],
+            all![
+                unify(&q, llist![false | &q1]),
+                poso(&q1),
+                ltlo(&b, &n),
+                appendo(&b, llist![true | &b], &b2),
+                exp2(&n, &b2, &q1),
+            ],
+            all![
+                unify(&q, llist![true | &q1]),
+                poso(&q1),
+                poso(&nh),
+                splito(&n, &b, LVar::<Nat>::new(), &nh),
+                appendo(&b, llist![true | &b], &b2),
+                exp2(nh, b2, q1),
+            ],
+        ]
+    })
+}
+
+/// Assert that `nq = n^q`.
+fn repeated_mul(
+    n: impl Into<Value<Nat>>,
+    q: impl Into<Value<Nat>>,
+    nq: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, q, nq) = (n.into(), q.into(), nq.into());
+    lazy(move || {
+        let (q1, nq1) = (LVar::new(), LVar::new());
+        conde![
+            all![poso(&n), unify(&q, llist![]), unify(&nq, llist![true])],
+            all![unify(&q, llist![true]), unify(&n, &nq)],
+            all![
+                gt1o(&q),
+                pluso(&q1, llist![true], &q),
+                repeated_mul(&n, q1, &nq1),
+                timeso(nq1, &n, &nq),
+            ],
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::logo;
+    use crate::goals::nat::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query};
+
+    fn log(n: u64, b: u64) -> Vec<(u64, u64)> {
+        let (q, r) = (LVar::new(), LVar::new());
+        let goal = logo(from_u64(n), from_u64(b), &q, &r);
+        goal.query((q, r))
+            .map(|(q, r)| (to_u64(&q).unwrap(), to_u64(&r).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn takes_logarithms() {
+        assert_eq!(log(14, 2), vec![(3, 6)]);
+        assert_eq!(log(68, 3), vec![(3, 41)]);
+        assert_eq!(log(3, 5), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn finds_powers() {
+        let n = LVar::new();
+        let goal = logo(&n, from_u64(3), from_u64(2), from_u64(0));
+        let results: Vec<_> = goal.query(n).take(1).map(|n| to_u64(&n)).collect();
+        assert_eq!(results, vec![Some(9)]);
+    }
+}
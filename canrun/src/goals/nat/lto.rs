@@ -0,0 +1,125 @@
+use super::{gt1o, pluso, poso, Nat};
+use crate::core::{LVar, Value};
+use crate::goals::{lazy, unify, Goal};
+use crate::{all, llist};
+
+/**
+Assert that `n < m`, for [`Nat`] numbers.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, lto, to_u64};
+
+let x = LVar::new();
+let goal = lto(&x, from_u64(3));
+let mut results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+results.sort_unstable();
+assert_eq!(results, vec![Some(0), Some(1), Some(2)]);
+```
+*/
+pub fn lto(n: impl Into<Value<Nat>>, m: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, m) = (n.into(), m.into());
+    lazy(move || {
+        let x = LVar::new();
+        conde![ltlo(&n, &m), all![eqlo(&n, &m), poso(&x), pluso(&n, x, &m)],]
+    })
+}
+
+/**
+Assert that `n <= m`, for [`Nat`] numbers.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, leo, to_u64};
+
+let x = LVar::new();
+let goal = leo(&x, from_u64(3));
+let mut results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+results.sort_unstable();
+assert_eq!(results, vec![Some(0), Some(1), Some(2), Some(3)]);
+```
+*/
+pub fn leo(n: impl Into<Value<Nat>>, m: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, m) = (n.into(), m.into());
+    lazy(move || conde![unify(&n, &m), lto(&n, &m)])
+}
+
+/// The numbers have the same bit length.
+pub(super) fn eqlo(n: impl Into<Value<Nat>>, m: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, m) = (n.into(), m.into());
+    lazy(move || {
+        let (a, x, b, y) = (
+            LVar::<bool>::new(),
+            LVar::new(),
+            LVar::<bool>::new(),
+            LVar::new(),
+        );
+        conde![
+            all![unify(&n, llist![]), unify(&m, llist![])],
+            all![unify(&n, llist![true]), unify(&m, llist![true])],
+            all![
+                unify(&n, llist![a | &x]),
+                poso(&x),
+                unify(&m, llist![b | &y]),
+                poso(&y),
+                eqlo(x, y),
+            ],
+        ]
+    })
+}
+
+/// The bit length of `n` is less than that of `m`.
+pub(super) fn ltlo(n: impl Into<Value<Nat>>, m: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, m) = (n.into(), m.into());
+    lazy(move || {
+        let (a, x, b, y) = (
+            LVar::<bool>::new(),
+            LVar::new(),
+            LVar::<bool>::new(),
+            LVar::new(),
+        );
+        conde![
+            all![unify(&n, llist![]), poso(&m)],
+            all![unify(&n, llist![true]), gt1o(&m)],
+            all![
+                unify(&n, llist![a | &x]),
+                poso(&x),
+                unify(&m, llist![b | &y]),
+                poso(&y),
+                ltlo(x, y),
+            ],
+        ]
+    })
+}
+
+/// The bit length of `n` is less than or equal to that of `m`.
+pub(super) fn lelo(n: impl Into<Value<Nat>>, m: impl Into<Value<Nat>>) -> impl Goal {
+    let (n, m) = (n.into(), m.into());
+    lazy(move || conde![eqlo(&n, &m), ltlo(&n, &m)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{leo, lto};
+    use crate::goals::nat::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query};
+
+    #[test]
+    fn compares() {
+        assert_eq!(lto(from_u64(5), from_u64(6)).query(1).count(), 1);
+        assert_eq!(lto(from_u64(6), from_u64(6)).query(1).count(), 0);
+        assert_eq!(leo(from_u64(6), from_u64(6)).query(1).count(), 1);
+        assert_eq!(leo(from_u64(7), from_u64(6)).query(1).count(), 0);
+    }
+
+    #[test]
+    fn enumerates_smaller() {
+        let x = LVar::new();
+        let goal = leo(&x, from_u64(3));
+        let mut results: Vec<_> = goal.query(x).map(|x| to_u64(&x).unwrap()).collect();
+        results.sort_unstable();
+        assert_eq!(results, vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,252 @@
+/*! Fully relational arithmetic on binary natural numbers.
+
+The goals in [`ops`](crate::goals::ops) only run once enough of their values
+are resolved, so something like `add(x, y, 5)` is left waiting forever. The
+goals in this module work with any mix of resolved and unresolved values, and
+will enumerate every solution when they are not fully determined.
+
+Numbers are represented as little-endian lists of bits ([`Nat`]), with no
+trailing `false` bits. Zero is the empty list. This is a port of the relations
+described in ["Pure, Declarative, and Constructive Arithmetic
+Relations"](https://okmij.org/ftp/Prolog/Arithm/arithm.pdf) by Kiselyov,
+Byrd, Friedman and Shan. Use [`from_u64`] and [`to_u64`] to convert to and
+from regular numbers.
+
+Some solutions stand for a whole family of numbers. For example, every number
+with more bits than `m` is greater than `m`, so `lto(m, x)` leaves the low
+bits of such numbers unresolved. These solutions can't be reified, and so are
+skipped by [`Query`](crate::Query) unless other goals pin down the remaining
+bits.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, pluso, to_u64};
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = pluso(&x, &y, from_u64(3));
+let mut results: Vec<_> = goal
+    .query((x, y))
+    .map(|(x, y)| (to_u64(&x), to_u64(&y)))
+    .collect();
+results.sort_unstable();
+assert_eq!(results, vec![
+    (Some(0), Some(3)),
+    (Some(1), Some(2)),
+    (Some(2), Some(1)),
+    (Some(3), Some(0)),
+]);
+```
+*/
+
+use std::collections::VecDeque;
+use std::iter::{from_fn, once};
+use std::rc::Rc;
+
+use crate::core::{Fork, LVar, State, StateIter, StateIterator, Value};
+use crate::goals::{lazy, unify, Any, Goal};
+use crate::llist;
+use crate::llist::LList;
+
+/// Yield a state for every successful branch, interleaving the results of
+/// each branch so that one infinite branch can't starve the others.
+macro_rules! conde {
+    ($($branch:expr),+ $(,)?) => {
+        $crate::goals::nat::Interleave {
+            goals: vec![$(std::rc::Rc::new($branch) as std::rc::Rc<dyn $crate::goals::Goal>),+],
+        }
+    };
+}
+
+mod divo;
+mod logo;
+mod lto;
+mod pluso;
+mod timeso;
+
+pub use divo::divo;
+pub use logo::logo;
+pub use lto::{leo, lto};
+pub use pluso::{minuso, pluso};
+pub use timeso::timeso;
+
+/// A natural number made up of little-endian bits.
+///
+/// Zero is the empty list, and the last bit of any other number is always
+/// `true`.
+pub type Nat = LList<bool>;
+
+/// Convert a number into a [`Nat`].
+///
+/// ```
+/// use canrun::{LVar, Query};
+/// use canrun::goals::unify;
+/// use canrun::goals::nat::from_u64;
+///
+/// let x = LVar::new();
+/// let results: Vec<_> = unify(&x, from_u64(6)).query(x).collect();
+/// assert_eq!(results, vec![vec![false, true, true]]);
+/// ```
+pub fn from_u64(n: u64) -> Nat {
+    let bits = 64 - n.leading_zeros();
+    (0..bits).map(|bit| n & (1 << bit) != 0).collect()
+}
+
+/// Convert the reified bits of a [`Nat`] into a number.
+///
+/// Returns `None` if the number is too large to fit in a `u64`.
+///
+/// ```
+/// use canrun::goals::nat::to_u64;
+///
+/// assert_eq!(to_u64(&[false, true, true]), Some(6));
+/// assert_eq!(to_u64(&[true; 65]), None);
+/// ```
+pub fn to_u64(bits: &[bool]) -> Option<u64> {
+    bits.iter().enumerate().try_fold(0u64, |n, (index, bit)| {
+        match (
+            bit,
+            u32::try_from(index).ok().and_then(|i| 1u64.checked_shl(i)),
+        ) {
+            (false, _) => Some(n),
+            (true, Some(value)) => Some(n | value),
+            (true, None) => None,
+        }
+    })
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Interleave {
+    pub(crate) goals: Vec<Rc<dyn Goal>>,
+}
+
+impl Goal for Interleave {
+    fn apply(&self, state: State) -> Option<State> {
+        state.fork(self.clone())
+    }
+}
+
+impl Fork for Interleave {
+    fn fork(&self, state: &State) -> StateIter {
+        // Like a conjunction in miniKanren, each branch is fully expanded
+        // before moving on to the forks that were already pending. Otherwise
+        // later, unconstrained goals can run away before the earlier goals
+        // that constrain them get a chance to. Branches are only applied once
+        // they are first asked for a state, so that recursive relations don't
+        // expand forever up front.
+        let mut inner = state.clone();
+        let pending = std::mem::take(&mut inner.forks);
+        let mut branches: VecDeque<StateIter> = self
+            .goals
+            .iter()
+            .map(|goal| {
+                let (goal, inner, pending) = (goal.clone(), inner.clone(), pending.clone());
+                let states = once(()).flat_map(move |()| goal.apply(inner.clone()).into_states());
+                Box::new(states.map(move |mut state| {
+                    state.forks = pending.clone();
+                    state
+                })) as StateIter
+            })
+            .collect();
+        Box::new(from_fn(move || {
+            while let Some(mut branch) = branches.pop_front() {
+                if let Some(state) = branch.next() {
+                    branches.push_back(branch);
+                    return Some(state);
+                }
+            }
+            None
+        }))
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        let viable = self
+            .goals
+            .iter()
+            .filter(|goal| goal.apply(state.clone()).is_some());
+        Some(viable.count())
+    }
+}
+
+/// The number is greater than zero.
+fn poso(n: impl Into<Value<Nat>>) -> impl Goal {
+    unify(n, llist![LVar::<bool>::new() | LVar::<Nat>::new()])
+}
+
+/// The number is greater than one.
+fn gt1o(n: impl Into<Value<Nat>>) -> impl Goal {
+    unify(
+        n,
+        llist![
+            LVar::<bool>::new(),
+            LVar::<bool>::new() | LVar::<Nat>::new()
+        ],
+    )
+}
+
+/// Assert that the bits `ab` are the bits `a` followed by the bits `b`.
+///
+/// This relates the same lists as [`appendo`](crate::llist::appendo), but
+/// branches with [`conde!`] like the other relations in this module. The
+/// depth first [`either`](crate::goals::either) in the general version
+/// recurses forever when both `a` and `ab` are unbound, as they are inside
+/// [`logo`].
+fn appendo(
+    a: impl Into<Value<Nat>>,
+    b: impl Into<Value<Nat>>,
+    ab: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (a, b, ab) = (a.into(), b.into(), ab.into());
+    lazy(move || {
+        let (head, rest_a, rest_ab) = (LVar::<bool>::new(), LVar::new(), LVar::new());
+        conde![
+            crate::all![unify(&a, llist![]), unify(&b, &ab)],
+            crate::all![
+                unify(&a, llist![head | &rest_a]),
+                unify(&ab, llist![head | &rest_ab]),
+                appendo(rest_a, &b, rest_ab),
+            ],
+        ]
+    })
+}
+
+/// Add the bits `b`, `x` and `y`, giving the bit `r` and the carry `c`.
+fn full_addero(
+    b: impl Into<Value<bool>>,
+    x: impl Into<Value<bool>>,
+    y: impl Into<Value<bool>>,
+    r: impl Into<Value<bool>>,
+    c: impl Into<Value<bool>>,
+) -> Any {
+    let (b, x, y): (Value<bool>, Value<bool>, Value<bool>) = (b.into(), x.into(), y.into());
+    let (r, c): (Value<bool>, Value<bool>) = (r.into(), c.into());
+    (0u8..8)
+        .map(|row| {
+            let (b_bit, x_bit, y_bit) = (row & 1 != 0, row & 2 != 0, row & 4 != 0);
+            let sum = row.count_ones();
+            let goal = crate::all![
+                unify(&b, b_bit),
+                unify(&x, x_bit),
+                unify(&y, y_bit),
+                unify(&r, sum % 2 == 1),
+                unify(&c, sum >= 2),
+            ];
+            Rc::new(goal) as Rc<dyn Goal>
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query, goals::unify};
+
+    #[test]
+    fn round_trips() {
+        for n in [0, 1, 2, 5, 1024, u64::MAX] {
+            let x = LVar::new();
+            let bits: Vec<_> = unify(&x, from_u64(n)).query(x).collect();
+            assert_eq!(to_u64(&bits[0]), Some(n));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::{full_addero, gt1o, poso, Nat};
+use crate::core::{LVar, Value};
+use crate::goals::{lazy, unify, Goal};
+use crate::{all, llist};
+
+/**
+Assert that `n + m = k`, for [`Nat`] numbers.
+
+This works with any combination of resolved and unresolved values, and will
+enumerate every solution.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, pluso, to_u64};
+
+let x = LVar::new();
+let goal = pluso(from_u64(2), &x, from_u64(5));
+let results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+assert_eq!(results, vec![Some(3)]);
+```
+*/
+pub fn pluso(
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    k: impl Into<Value<Nat>>,
+) -> impl Goal {
+    addero(false, n, m, k)
+}
+
+/**
+Assert that `n - m = k`, for [`Nat`] numbers.
+
+This is [`pluso`] in reverse, and fails if `m` is larger than `n`.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, minuso, to_u64};
+
+let x = LVar::new();
+let goal = minuso(from_u64(5), from_u64(2), &x);
+let results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+assert_eq!(results, vec![Some(3)]);
+```
+*/
+pub fn minuso(
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    k: impl Into<Value<Nat>>,
+) -> impl Goal {
+    pluso(m, k, n)
+}
+
+/// Assert that `d + n + m = r`, where `d` is a carry bit.
+fn addero(
+    d: impl Into<Value<bool>>,
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (d, n, m, r) = (d.into(), n.into(), m.into(), r.into());
+    lazy(move || {
+        let (a, c) = (LVar::<bool>::new(), LVar::<bool>::new());
+        conde![
+            all![unify(&d, false), unify(&m, llist![]), unify(&n, &r)],
+            all![
+                unify(&d, false),
+                unify(&n, llist![]),
+                unify(&m, &r),
+                poso(&m),
+            ],
+            all![
+                unify(&d, true),
+                unify(&m, llist![]),
+                addero(false, &n, llist![true], &r),
+            ],
+            all![
+                unify(&d, true),
+                unify(&n, llist![]),
+                poso(&m),
+                addero(false, llist![true], &m, &r),
+            ],
+            all![
+                unify(&n, llist![true]),
+                unify(&m, llist![true]),
+                unify(&r, llist![a, c]),
+                full_addero(&d, true, true, a, c),
+            ],
+            all![unify(&n, llist![true]), gen_addero(&d, &n, &m, &r)],
+            all![
+                unify(&m, llist![true]),
+                gt1o(&n),
+                gt1o(&r),
+                addero(&d, llist![true], &n, &r),
+            ],
+            all![gt1o(&n), gen_addero(&d, &n, &m, &r)],
+        ]
+    })
+}
+
+/// Add two numbers of at least two bits each, one bit at a time.
+fn gen_addero(
+    d: impl Into<Value<bool>>,
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    r: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (d, n, m, r) = (d.into(), n.into(), m.into(), r.into());
+    lazy(move || {
+        let (a, b, c, e) = (LVar::new(), LVar::new(), LVar::new(), LVar::new());
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        all![
+            unify(&n, llist![a | &x]),
+            unify(&m, llist![b | &y]),
+            poso(&y),
+            unify(&r, llist![c | &z]),
+            poso(&z),
+            full_addero(&d, a, b, c, e),
+            addero(e, x, y, z),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minuso, pluso};
+    use crate::goals::nat::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query};
+
+    fn numbers(bits: impl Iterator<Item = (Vec<bool>, Vec<bool>)>) -> Vec<(u64, u64)> {
+        let mut numbers: Vec<_> = bits
+            .map(|(a, b)| (to_u64(&a).unwrap(), to_u64(&b).unwrap()))
+            .collect();
+        numbers.sort_unstable();
+        numbers
+    }
+
+    #[test]
+    fn adds() {
+        let x = LVar::new();
+        let goal = pluso(from_u64(29), from_u64(13), &x);
+        let results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+        assert_eq!(results, vec![Some(42)]);
+    }
+
+    #[test]
+    fn enumerates_addends() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goal = pluso(&x, &y, from_u64(5));
+        let results = numbers(goal.query((x, y)));
+        assert_eq!(
+            results,
+            vec![(0, 5), (1, 4), (2, 3), (3, 2), (4, 1), (5, 0)]
+        );
+    }
+
+    #[test]
+    fn subtracts() {
+        let x = LVar::new();
+        let goal = minuso(from_u64(3), from_u64(5), &x);
+        assert_eq!(goal.query(x.clone()).count(), 0);
+        let goal = minuso(from_u64(42), &x, from_u64(13));
+        let results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+        assert_eq!(results, vec![Some(29)]);
+    }
+
+    #[test]
+    fn generates_sums() {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goal = pluso(&x, &y, &z);
+        let results: Vec<_> = goal.query((x, y, z)).take(20).collect();
+        assert_eq!(results.len(), 20);
+        for (x, y, z) in results {
+            let (x, y, z) = (to_u64(&x), to_u64(&y), to_u64(&z));
+            assert_eq!(x.unwrap() + y.unwrap(), z.unwrap());
+        }
+    }
+}
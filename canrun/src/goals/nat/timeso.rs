@@ -0,0 +1,151 @@
+use super::{gt1o, pluso, poso, Nat};
+use crate::core::{LVar, Value};
+use crate::goals::{lazy, unify, Goal};
+use crate::{all, llist};
+
+/**
+Assert that `n * m = p`, for [`Nat`] numbers.
+
+This works with any combination of resolved and unresolved values, and will
+enumerate every solution.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::goals::nat::{from_u64, timeso, to_u64};
+
+let (x, y) = (LVar::new(), LVar::new());
+let goal = timeso(&x, &y, from_u64(6));
+let mut results: Vec<_> = goal
+    .query((x, y))
+    .map(|(x, y)| (to_u64(&x).unwrap(), to_u64(&y).unwrap()))
+    .collect();
+results.sort_unstable();
+assert_eq!(results, vec![(1, 6), (2, 3), (3, 2), (6, 1)]);
+```
+*/
+pub fn timeso(
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    p: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (n, m, p) = (n.into(), m.into(), p.into());
+    lazy(move || {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        conde![
+            all![unify(&n, llist![]), unify(&p, llist![])],
+            all![poso(&n), unify(&m, llist![]), unify(&p, llist![])],
+            all![unify(&n, llist![true]), poso(&m), unify(&m, &p)],
+            all![gt1o(&n), unify(&m, llist![true]), unify(&n, &p)],
+            all![
+                unify(&n, llist![false | &x]),
+                poso(&x),
+                unify(&p, llist![false | &z]),
+                poso(&z),
+                gt1o(&m),
+                timeso(&x, &m, z),
+            ],
+            all![
+                unify(&n, llist![true | &x]),
+                poso(&x),
+                unify(&m, llist![false | &y]),
+                poso(&y),
+                timeso(&m, &n, &p),
+            ],
+            all![
+                unify(&n, llist![true | &x]),
+                poso(&x),
+                unify(&m, llist![true | &y]),
+                poso(y),
+                odd_timeso(x, &n, &m, &p),
+            ],
+        ]
+    })
+}
+
+/// Multiply an odd `n = 2x + 1` by `m`.
+fn odd_timeso(
+    x: impl Into<Value<Nat>>,
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+    p: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (x, n, m, p) = (x.into(), n.into(), m.into(), p.into());
+    lazy(move || {
+        let q = LVar::new();
+        all![
+            bound_timeso(&q, &p, &n, &m),
+            timeso(&x, &m, &q),
+            pluso(llist![false | q], &m, &p),
+        ]
+    })
+}
+
+/// Bound the length of `q` by the length of `p`, and the length of `p` by the
+/// combined lengths of `n` and `m`, so that multiplication always terminates.
+fn bound_timeso(
+    q: impl Into<Value<Nat>>,
+    p: impl Into<Value<Nat>>,
+    n: impl Into<Value<Nat>>,
+    m: impl Into<Value<Nat>>,
+) -> impl Goal {
+    let (q, p, n, m) = (q.into(), p.into(), n.into(), m.into());
+    lazy(move || {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        conde![
+            all![unify(&q, llist![]), poso(&p)],
+            all![
+                unify(&q, llist![LVar::<bool>::new() | &x]),
+                unify(&p, llist![LVar::<bool>::new() | &y]),
+                conde![
+                    all![
+                        unify(&n, llist![]),
+                        unify(&m, llist![LVar::<bool>::new() | &z]),
+                        bound_timeso(&x, &y, &z, llist![]),
+                    ],
+                    all![
+                        unify(&n, llist![LVar::<bool>::new() | &z]),
+                        bound_timeso(x, y, z, &m),
+                    ],
+                ],
+            ],
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timeso;
+    use crate::goals::nat::{from_u64, to_u64};
+    use crate::{core::LVar, core::Query};
+
+    #[test]
+    fn multiplies() {
+        let x = LVar::new();
+        let goal = timeso(from_u64(7), from_u64(6), &x);
+        let results: Vec<_> = goal.query(x).map(|x| to_u64(&x)).collect();
+        assert_eq!(results, vec![Some(42)]);
+    }
+
+    #[test]
+    fn divides_exactly() {
+        let x = LVar::new();
+        let goal = timeso(from_u64(7), &x, from_u64(42));
+        let results: Vec<_> = goal.query(x.clone()).map(|x| to_u64(&x)).collect();
+        assert_eq!(results, vec![Some(6)]);
+        let goal = timeso(from_u64(5), &x, from_u64(42));
+        assert_eq!(goal.query(x).count(), 0);
+    }
+
+    #[test]
+    fn finds_no_factors_of_primes() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goal = timeso(&x, &y, from_u64(13));
+        let mut results: Vec<_> = goal
+            .query((x, y))
+            .map(|(x, y)| (to_u64(&x).unwrap(), to_u64(&y).unwrap()))
+            .collect();
+        results.sort_unstable();
+        assert_eq!(results, vec![(1, 13), (13, 1)]);
+    }
+}
@@ -0,0 +1,66 @@
+use super::{checked::CheckedArith, neg::neg};
+use crate::cmp::{gte, lt};
+use crate::goals::{both, either, unify, Goal};
+use crate::{Unify, Value};
+
+/** Get the absolute value of a value.
+
+When only `b` is known, this forks into the positive and negative values it
+could have come from.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::ops::abs;
+
+let x = LVar::new();
+let goal = abs(x, 3);
+let mut results: Vec<_> = goal.query(x).collect();
+results.sort_unstable();
+assert_eq!(results, vec![-3, 3]);
+```
+*/
+pub fn abs<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + CheckedArith + PartialOrd + Copy,
+{
+    let a = a.into();
+    let b = b.into();
+    either(
+        both(gte(a.clone(), T::ZERO), unify(a.clone(), b.clone())),
+        both(lt(a.clone(), T::ZERO), neg(a, b)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::abs;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, -2), abs(x, y)];
+        goals.assert_permutations_resolve_to(&y, vec![2]);
+        let goals = goal_vec![unify(x, 2), abs(x, y)];
+        goals.assert_permutations_resolve_to(&y, vec![2]);
+    }
+
+    #[test]
+    fn forks_on_sign() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(y, 2), abs(x, y)];
+        goals.assert_permutations_resolve_to(&x, vec![2, -2]);
+        let goals = goal_vec![unify(y, 0), abs(x, y)];
+        goals.assert_permutations_resolve_to(&x, vec![0]);
+        let goals = goal_vec![unify(y, -2), abs(x, y)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn fails_out_of_range() {
+        let x = LVar::new();
+        let goals = goal_vec![abs(i8::MIN, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+}
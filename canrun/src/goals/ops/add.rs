@@ -1,11 +1,12 @@
-use crate::{
-    goals::{map_2, Goal},
-    Unify, Value,
-};
-use std::ops::{Add, Sub};
+use super::checked::{arith, CheckedArith};
+use crate::export::Formula;
+use crate::{goals::Goal, Unify, Value};
 
 /** Add two values together.
 
+If only the result is known and either input has a
+[domain](crate::domain), this goal will fork over its possible values.
+
 # Example:
 ```
 use canrun::{unify, LVar, Query};
@@ -19,15 +20,23 @@ assert_eq!(results, vec![3]);
 */
 pub fn add<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
 where
-    T: Add<Output = T> + Sub<Output = T> + Unify + Copy,
+    T: Unify + CheckedArith + Copy,
 {
-    map_2(a, b, c, |a, b| *a + *b, |a, c| *c - *a, |b, c| *c - *b)
+    arith(
+        a,
+        b,
+        c,
+        T::checked_add,
+        |a, c| c.checked_sub(a),
+        |b, c| c.checked_sub(b),
+        |a, b, c| Formula::Eq(a + b, c),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::add;
-    use crate::{goal_vec, unify, LVar};
+    use crate::{domain::in_range, goal_vec, lvec, unify, LVar};
 
     #[test]
     fn succeeds() {
@@ -46,4 +55,31 @@ mod tests {
         let goals = goal_vec![unify(x, 1), unify(y, 2), unify(z, 4), add(x, y, z)];
         goals.assert_permutations_resolve_to(&(x, y, z), vec![]);
     }
+
+    #[test]
+    fn enumerates_domains() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![in_range(x, 0..=3), in_range(y, 0..=3), add(x, y, 4)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 3), (2, 2), (3, 1)]);
+    }
+
+    #[test]
+    fn enumerates_member_sets() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![
+            lvec::member(x, lvec![1, 2, 3]),
+            lvec::member(y, lvec![3, 4]),
+            add(x, y, 5),
+        ];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(1, 4), (2, 3)]);
+    }
+
+    #[test]
+    fn fails_on_overflow() {
+        let x = LVar::<u8>::new();
+        let goals = goal_vec![add(250, 10, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![add(5, x, 3)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
 }
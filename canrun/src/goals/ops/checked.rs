@@ -0,0 +1,236 @@
+use super::enumerate::enumerate;
+use crate::constraints::{Constraint, ResolveFn, TwoOfThree};
+use crate::export::{Describe, Formula, NumExpr, UnsupportedGoal};
+use crate::goals::{both, Goal};
+use crate::{LVarList, State, Unify, Value};
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/** Arithmetic that returns `None` instead of panicking or giving a wrong
+answer.
+
+The [`ops`](super) goals are generic over this trait, so that overflow and
+division by zero simply fail the branch they happen in. It is implemented
+for all of the primitive integer and float types, and can be implemented for
+custom numeric types to use them with the [`ops`](super) goals.
+
+# Example:
+```
+use canrun::ops::CheckedArith;
+
+assert_eq!(CheckedArith::checked_add(250u8, 5), Some(255));
+assert_eq!(CheckedArith::checked_add(250u8, 6), None);
+assert_eq!(CheckedArith::checked_div(6, 3), Some(2));
+assert_eq!(CheckedArith::checked_div(7, 3), None);
+```
+*/
+pub trait CheckedArith: Sized {
+    /// The additive identity, used as the [`sum`](super::sum) of no values.
+    const ZERO: Self;
+    /// The multiplicative identity, used as the [`product`](super::product)
+    /// of no values.
+    const ONE: Self;
+    /// `self + other`, or `None` if the result is out of range.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// `self - other`, or `None` if the result is out of range.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// `self * other`, or `None` if the result is out of range.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// `self / other`, or `None` if `other` is zero.
+    ///
+    /// Integer division must also be exact, so that the result times `other`
+    /// gives back `self`. This keeps [`div`](super::div) consistent with
+    /// [`mul`](super::mul) no matter which way it is run.
+    fn checked_div(self, other: Self) -> Option<Self>;
+    /// The remainder of `self / other`, or `None` if `other` is zero.
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    /// `self` raised to the power of `exp`, or `None` if the result is out of
+    /// range.
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+}
+
+macro_rules! impl_checked_arith_int {
+    ($($type:ty),+) => {
+        $(
+            impl CheckedArith for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$type>::checked_sub(self, other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$type>::checked_mul(self, other)
+                }
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    if <$type>::checked_rem(self, other)? == 0 {
+                        <$type>::checked_div(self, other)
+                    } else {
+                        None
+                    }
+                }
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$type>::checked_rem(self, other)
+                }
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    <$type>::checked_pow(self, exp)
+                }
+            }
+        )+
+    };
+}
+
+impl_checked_arith_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_checked_arith_float {
+    ($($type:ty),+) => {
+        $(
+            impl CheckedArith for $type {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    Some(self * other)
+                }
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    if other == 0.0 {
+                        None
+                    } else {
+                        Some(self / other)
+                    }
+                }
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    if other == 0.0 {
+                        None
+                    } else {
+                        Some(self % other)
+                    }
+                }
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    i32::try_from(exp).ok().map(|exp| self.powi(exp))
+                }
+            }
+        )+
+    };
+}
+
+impl_checked_arith_float!(f32, f64);
+
+type ArithFn<T> = fn(T, T) -> Option<T>;
+type DescribeFn = fn(NumExpr, NumExpr, NumExpr) -> Formula;
+
+/// Derive any one of `a`, `b` and `c` from the other two, failing if the
+/// derivation does. Also [`enumerate`]s domains when only `c` is known.
+///
+/// The one exception is when the two known values are both zero, such as `b`
+/// in `0 * b = 0`. Then any value could work, so the goal waits for the last
+/// one to be resolved and checks it against `ab_to_c` instead.
+///
+/// When [exported](crate::export), the goal is described by `describe`.
+pub(super) fn arith<T: Unify + CheckedArith + Copy>(
+    a: impl Into<Value<T>>,
+    b: impl Into<Value<T>>,
+    c: impl Into<Value<T>>,
+    ab_to_c: ArithFn<T>,
+    ac_to_b: ArithFn<T>,
+    bc_to_a: ArithFn<T>,
+    describe: DescribeFn,
+) -> impl Goal {
+    let (a, b, c) = (a.into(), b.into(), c.into());
+    let derive = Arith {
+        a: a.clone(),
+        b: b.clone(),
+        c: c.clone(),
+        ab_to_c,
+        ac_to_b,
+        bc_to_a,
+        describe,
+    };
+    both(derive, enumerate(a, b, c))
+}
+
+struct Arith<T: Unify> {
+    a: Value<T>,
+    b: Value<T>,
+    c: Value<T>,
+    ab_to_c: ArithFn<T>,
+    ac_to_b: ArithFn<T>,
+    bc_to_a: ArithFn<T>,
+    describe: DescribeFn,
+}
+
+impl<T: Unify> Debug for Arith<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Arith {:?} {:?} {:?}", self.a, self.b, self.c)
+    }
+}
+
+impl<T: Unify> Clone for Arith<T> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            c: self.c.clone(),
+            ab_to_c: self.ab_to_c,
+            ac_to_b: self.ac_to_b,
+            bc_to_a: self.bc_to_a,
+            describe: self.describe,
+        }
+    }
+}
+
+impl<T: Unify + CheckedArith + Copy> Goal for Arith<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.constrain(Rc::new(self.clone()))
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<T: Unify + CheckedArith + Copy> Describe for Arith<T> {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        match (
+            NumExpr::of(&self.a),
+            NumExpr::of(&self.b),
+            NumExpr::of(&self.c),
+        ) {
+            (Some(a), Some(b), Some(c)) => Ok((self.describe)(a, b, c)),
+            _ => Err(UnsupportedGoal::new(self)),
+        }
+    }
+}
+
+impl<T: Unify + CheckedArith + Copy> Constraint for Arith<T> {
+    fn attempt(&self, state: &State) -> Result<ResolveFn, LVarList> {
+        let (derived, target, known) = match TwoOfThree::resolve(&self.a, &self.b, &self.c, state)?
+        {
+            TwoOfThree::AB(a, b, c) => ((self.ab_to_c)(*a, *b), c, None),
+            TwoOfThree::AC(a, b, c) => ((self.ac_to_b)(*a, *c), b, Some((*a, *c))),
+            TwoOfThree::BC(a, b, c) => ((self.bc_to_a)(*b, *c), a, Some((*b, *c))),
+        };
+        match (derived, target) {
+            (Some(derived), target) => Ok(Box::new(move |state| {
+                state.unify(&Value::new(derived), &target)
+            })),
+            (None, Value::Var(var)) if known.is_some_and(|(x, y)| is_zero(x) && is_zero(y)) => {
+                Err(LVarList::one(&var))
+            }
+            (None, _) => Ok(Box::new(|_| None)),
+        }
+    }
+}
+
+/// Dividing a value by itself only fails for zero, so this works for any
+/// [`CheckedArith`] type without needing a separate zero constant.
+fn is_zero<T: CheckedArith + Copy>(value: T) -> bool {
+    value.checked_div(value).is_none()
+}
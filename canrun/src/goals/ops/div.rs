@@ -1,33 +1,51 @@
-use crate::{
-    goals::{map_2, Goal},
-    Unify, Value,
-};
-use std::ops::{Div, Mul};
+use super::checked::{arith, CheckedArith};
+use crate::export::Formula;
+use crate::{goals::Goal, Unify, Value};
 
 /** Divide one value with another.
 
+Integer division must be exact, so `div(3, 2, x)` fails rather than giving
+`1`. Dividing by zero also fails. See [`CheckedArith`].
+
+If only the result is known and either input has a
+[domain](crate::domain), this goal will fork over its possible values.
+
 # Example:
 ```
 use canrun::{LVar, Query};
 use canrun::ops::div;
 
 let x = LVar::new();
-let goal = div(3, 2, x);
+let goal = div(6, 2, x);
 let results: Vec<_> = goal.query(x).collect();
-assert_eq!(results, vec![1]);
+assert_eq!(results, vec![3]);
 ```
 */
 pub fn div<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
 where
-    T: Unify + Mul<Output = T> + Div<Output = T> + Copy,
+    T: Unify + CheckedArith + Copy,
 {
-    map_2(a, b, c, |a, b| *a / *b, |a, c| *a / *c, |b, c| *b * *c)
+    arith(
+        a,
+        b,
+        c,
+        T::checked_div,
+        T::checked_div,
+        T::checked_mul,
+        |a, b, c| {
+            let zero = b.zero_like();
+            Formula::And(vec![
+                Formula::Not(Box::new(Formula::Eq(b.clone(), zero))),
+                Formula::Eq(a, b * c),
+            ])
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::div;
-    use crate::{goal_vec, goals::unify, LVar};
+    use crate::{goal_vec, goals::unify, Goal, LVar, State};
 
     #[test]
     fn succeeds() {
@@ -42,4 +60,38 @@ mod tests {
         let goals = goal_vec![unify(x, 6), unify(y, 3), unify(z, 5), div(x, y, z)];
         goals.assert_permutations_resolve_to(&(x, y, z), vec![]);
     }
+
+    #[test]
+    fn rejects_inexact_division() {
+        let x = LVar::new();
+        let goals = goal_vec![div(3, 2, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![div(x, 2, 1), div(x, 3, 1)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn fails_on_division_by_zero() {
+        let x = LVar::<i32>::new();
+        let goals = goal_vec![div(3, 0, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![div(3, x, 0)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn fails_without_waiting() {
+        let x = LVar::<i32>::new();
+        assert!(div(3, x, 0).apply(State::new()).is_none());
+        assert!(div(7, x, 2).apply(State::new()).is_none());
+    }
+
+    #[test]
+    fn checks_divisors_of_zero() {
+        let x = LVar::new();
+        let goals = goal_vec![div(0, x, 0), unify(x, 5)];
+        goals.assert_permutations_resolve_to(&x, vec![5]);
+        let goals = goal_vec![div(0, x, 0), unify(x, 0)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
 }
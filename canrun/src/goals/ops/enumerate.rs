@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+use std::iter::once;
+
+use crate::core::{Fork, LVar, State, StateIter};
+use crate::export::{Describe, Formula, UnsupportedGoal};
+use crate::goals::Goal;
+use crate::{Unify, Value};
+
+/// Fork over the [domain](crate::domain) of `a` or `b` once `c` is the only
+/// resolved value, so that the arithmetic goals can be run backwards.
+///
+/// The variable with the smallest domain is picked. Each branch only binds
+/// that variable, leaving the arithmetic goal itself to derive the other one.
+/// If neither has a domain the state is passed through untouched.
+pub(super) fn enumerate<T: Unify + Clone>(a: Value<T>, b: Value<T>, c: Value<T>) -> Enumerate<T> {
+    Enumerate { a, b, c }
+}
+
+#[derive(Debug)]
+pub(super) struct Enumerate<T: Unify> {
+    a: Value<T>,
+    b: Value<T>,
+    c: Value<T>,
+}
+
+impl<T: Unify> Clone for Enumerate<T> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            c: self.c.clone(),
+        }
+    }
+}
+
+impl<T: Unify + Clone> Enumerate<T> {
+    fn candidates<'s>(&self, state: &'s State) -> Option<(LVar<T>, &'s [T])> {
+        state.resolve(&self.c).resolved()?;
+        let (Value::Var(a), Value::Var(b)) = (state.resolve(&self.a), state.resolve(&self.b))
+        else {
+            return None;
+        };
+        [a, b]
+            .into_iter()
+            .filter_map(|var| {
+                let values = state.domain(&var)?;
+                Some((var, values))
+            })
+            .min_by_key(|(_, values)| values.len())
+    }
+}
+
+impl<T: Unify + Clone> Goal for Enumerate<T> {
+    fn apply(&self, state: State) -> Option<State> {
+        state.fork(self.clone())
+    }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<T: Unify + Clone> Describe for Enumerate<T> {
+    /// Only guides the search, so it doesn't add anything to the formula.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(Formula::Bool(true))
+    }
+}
+
+impl<T: Unify + Clone> Fork for Enumerate<T> {
+    fn fork(&self, state: &State) -> StateIter {
+        let Some((var, values)) = self.candidates(state) else {
+            return Box::new(once(state.clone()));
+        };
+        let (state, values) = (state.clone(), values.to_vec());
+        let var = Value::Var(var);
+        Box::new(
+            values
+                .into_iter()
+                .filter_map(move |value| state.clone().unify(&var, &Value::new(value))),
+        )
+    }
+
+    fn branching(&self, state: &State) -> Option<usize> {
+        Some(self.candidates(state).map_or(1, |(_, values)| values.len()))
+    }
+}
@@ -1,12 +1,33 @@
 //! Operator goals including [`add`](add::add), [`sub`](sub::sub),
-//! [`mul`](mul::mul) and [`div`](div::div).
+//! [`mul`](mul::mul), [`div`](div::div), [`rem`](rem::rem),
+//! [`neg`](neg::neg), [`abs`](abs::abs) and [`pow`](pow::pow), plus
+//! [`sum`](sum::sum) and [`product`](product::product) over an
+//! [`LVec`](crate::lvec::LVec).
+//!
+//! Operations that overflow or divide by zero fail instead of panicking. See
+//! [`CheckedArith`].
 
+mod abs;
 mod add;
+mod checked;
 mod div;
+mod enumerate;
 mod mul;
+mod neg;
+mod pow;
+mod product;
+mod rem;
 mod sub;
+mod sum;
 
+pub use abs::abs;
 pub use add::add;
+pub use checked::CheckedArith;
 pub use div::div;
 pub use mul::mul;
+pub use neg::neg;
+pub use pow::pow;
+pub use product::product;
+pub use rem::rem;
 pub use sub::sub;
+pub use sum::sum;
@@ -1,11 +1,12 @@
-use crate::{
-    goals::{map_2, Goal},
-    Unify, Value,
-};
-use std::ops::{Div, Mul};
+use super::checked::{arith, CheckedArith};
+use crate::export::Formula;
+use crate::{goals::Goal, Unify, Value};
 
 /** Multiply two values together.
 
+If only the result is known and either input has a
+[domain](crate::domain), this goal will fork over its possible values.
+
 # Example:
 ```
 use canrun::{unify, LVar, Query};
@@ -19,15 +20,23 @@ assert_eq!(results, vec![6]);
 */
 pub fn mul<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
 where
-    T: Unify + Mul<Output = T> + Div<Output = T> + Copy,
+    T: Unify + CheckedArith + Copy,
 {
-    map_2(a, b, c, |a, b| *a * *b, |a, c| *c / *a, |b, c| *c / *b)
+    arith(
+        a,
+        b,
+        c,
+        T::checked_mul,
+        |a, c| c.checked_div(a),
+        |b, c| c.checked_div(b),
+        |a, b, c| Formula::Eq(a * b, c),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::mul;
-    use crate::{goal_vec, unify, LVar};
+    use crate::{domain::in_range, goal_vec, unify, Goal, LVar, State};
 
     #[test]
     fn succeeds() {
@@ -42,4 +51,36 @@ mod tests {
         let goals = goal_vec![unify(x, 2), unify(y, 3), unify(z, 5), mul(x, y, z)];
         goals.assert_permutations_resolve_to(&(x, y, z), vec![]);
     }
+
+    #[test]
+    fn enumerates_domains() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![in_range(x, 1..=6), in_range(y, 2..=4), mul(x, y, 12)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(3, 4), (4, 3), (6, 2)]);
+    }
+
+    #[test]
+    fn fails_without_exact_factor() {
+        let x = LVar::<i32>::new();
+        let goals = goal_vec![mul(2, x, 7)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![mul(0, x, 7)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![mul(i32::MAX, 2, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn fails_without_waiting() {
+        let x = LVar::<i32>::new();
+        assert!(mul(x, 2, 5).apply(State::new()).is_none());
+        assert!(mul(i32::MAX, x, -1).apply(State::new()).is_none());
+    }
+
+    #[test]
+    fn checks_factors_of_zero() {
+        let x = LVar::new();
+        let goals = goal_vec![mul(0, x, 0), unify(x, 5)];
+        goals.assert_permutations_resolve_to(&x, vec![5]);
+    }
 }
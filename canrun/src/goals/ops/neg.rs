@@ -0,0 +1,52 @@
+use super::{add::add, checked::CheckedArith};
+use crate::{goals::Goal, Unify, Value};
+
+/** Negate a value.
+
+This works in both directions, and fails if the negation is out of range
+(such as `-i32::MIN`, or any unsigned value other than zero).
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::ops::neg;
+
+let x = LVar::new();
+let goal = neg(x, 3);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![-3]);
+```
+*/
+pub fn neg<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + CheckedArith + Copy,
+{
+    add(a, b, T::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::neg;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 2), neg(x, y)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(2, -2)]);
+        let goals = goal_vec![unify(y, 2), neg(x, y)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(-2, 2)]);
+    }
+
+    #[test]
+    fn fails_out_of_range() {
+        let x = LVar::new();
+        let goals = goal_vec![neg(i32::MIN, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let x = LVar::<u8>::new();
+        let goals = goal_vec![neg(3, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![neg(0, x)];
+        goals.assert_permutations_resolve_to(&x, vec![0]);
+    }
+}
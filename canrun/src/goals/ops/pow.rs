@@ -0,0 +1,56 @@
+use super::checked::CheckedArith;
+use crate::goals::{project_2, unify, Fail, Goal};
+use crate::{Unify, Value};
+
+/** Raise a value to a power.
+
+This waits for both `a` and `exp` to be resolved before deriving `c`, and
+fails if the result is out of range.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::ops::pow;
+
+let x = LVar::new();
+let goal = pow(2, 10, x);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1024]);
+```
+*/
+pub fn pow<T>(
+    a: impl Into<Value<T>>,
+    exp: impl Into<Value<u32>>,
+    c: impl Into<Value<T>>,
+) -> impl Goal
+where
+    T: Unify + CheckedArith + Copy,
+{
+    let c = c.into();
+    project_2(a, exp, move |a, exp| match a.checked_pow(*exp) {
+        Some(pow) => Box::new(unify(pow, c.clone())),
+        None => Box::new(Fail),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pow;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, e, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 3), unify(e, 3), pow(x, e, z)];
+        goals.assert_permutations_resolve_to(&z, vec![27]);
+        let goals = goal_vec![unify(x, 3), unify(e, 0), pow(x, e, z)];
+        goals.assert_permutations_resolve_to(&z, vec![1]);
+    }
+
+    #[test]
+    fn fails_on_overflow() {
+        let z = LVar::<u8>::new();
+        let goals = goal_vec![pow(2, 8, z)];
+        goals.assert_permutations_resolve_to(&z, vec![]);
+    }
+}
@@ -0,0 +1,58 @@
+use super::{checked::CheckedArith, mul::mul};
+use crate::goals::Goal;
+use crate::lvec::{fold, LVec};
+use crate::{Unify, Value};
+
+/** Multiply together all of the items in an [`LVec`].
+
+This is a [`mul`](super::mul) for each item, so any one unknown item can be
+derived from the total. The product of an empty [`LVec`] is one.
+
+# Example:
+```
+use canrun::{lvec, LVar, Query};
+use canrun::ops::product;
+
+let x = LVar::new();
+let goal = product(lvec![2, x, 3], 24);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![4]);
+```
+*/
+pub fn product<T>(items: impl Into<Value<LVec<T>>>, total: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + CheckedArith + Copy,
+{
+    fold(items, T::ONE, total, mul)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::product;
+    use crate::{goal_vec, lvec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, total) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 2), product(lvec![3, x, 4], total)];
+        goals.assert_permutations_resolve_to(&total, vec![24]);
+        let goals = goal_vec![product(lvec![], total)];
+        goals.assert_permutations_resolve_to(&total, vec![1]);
+    }
+
+    #[test]
+    fn derives_an_item() {
+        let x = LVar::new();
+        let goals = goal_vec![product(lvec![x, 2, 3], 30)];
+        goals.assert_permutations_resolve_to(&x, vec![5]);
+        let goals = goal_vec![product(lvec![x, 4], 30)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+
+    #[test]
+    fn checks_items_of_a_zero_product() {
+        let x = LVar::new();
+        let goals = goal_vec![unify(x, 7), product(lvec![0, x], 0)];
+        goals.assert_permutations_resolve_to(&x, vec![7]);
+    }
+}
@@ -0,0 +1,54 @@
+use super::checked::CheckedArith;
+use crate::goals::{project_2, unify, Fail, Goal};
+use crate::{Unify, Value};
+
+/** Get the remainder of dividing one value by another.
+
+Many values of `a` and `b` give the same remainder, so this goal waits for
+both of them to be resolved before deriving `c`. Dividing by zero fails.
+
+# Example:
+```
+use canrun::{LVar, Query};
+use canrun::ops::rem;
+
+let x = LVar::new();
+let goal = rem(7, 3, x);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![1]);
+```
+*/
+pub fn rem<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + CheckedArith + Copy,
+{
+    let c = c.into();
+    project_2(a, b, move |a, b| match a.checked_rem(*b) {
+        Some(rem) => Box::new(unify(rem, c.clone())),
+        None => Box::new(Fail),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rem;
+    use crate::{goal_vec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (LVar::new(), LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 7), unify(y, 3), rem(x, y, z)];
+        goals.assert_permutations_resolve_to(&z, vec![1]);
+        let goals = goal_vec![unify(x, -7), unify(y, 3), rem(x, y, z)];
+        goals.assert_permutations_resolve_to(&z, vec![-1]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, z) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 7), unify(z, 2), rem(x, 3, z)];
+        goals.assert_permutations_resolve_to(&z, vec![]);
+        let goals = goal_vec![unify(x, 7), rem(x, 0, z)];
+        goals.assert_permutations_resolve_to(&z, vec![]);
+    }
+}
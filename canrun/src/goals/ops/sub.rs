@@ -1,11 +1,12 @@
-use crate::{
-    goals::{map_2, Goal},
-    Unify, Value,
-};
-use std::ops::{Add, Sub};
+use super::checked::{arith, CheckedArith};
+use crate::export::Formula;
+use crate::{goals::Goal, Unify, Value};
 
 /** Subtract one value from another.
 
+If only the result is known and either input has a
+[domain](crate::domain), this goal will fork over its possible values.
+
 # Example:
 ```
 use canrun::{LVar, Query};
@@ -19,15 +20,23 @@ assert_eq!(results, vec![1]);
 */
 pub fn sub<T>(a: impl Into<Value<T>>, b: impl Into<Value<T>>, c: impl Into<Value<T>>) -> impl Goal
 where
-    T: Unify + Add<Output = T> + Sub<Output = T> + Copy,
+    T: Unify + CheckedArith + Copy,
 {
-    map_2(a, b, c, |a, b| *a - *b, |a, c| *a - *c, |b, c| *b + *c)
+    arith(
+        a,
+        b,
+        c,
+        T::checked_sub,
+        T::checked_sub,
+        T::checked_add,
+        |a, b, c| Formula::Eq(a - b, c),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::sub;
-    use crate::{goal_vec, unify, LVar};
+    use crate::{domain::domain, goal_vec, unify, LVar};
 
     #[test]
     fn succeeds() {
@@ -42,4 +51,20 @@ mod tests {
         let goals = goal_vec![unify(x, 3), unify(y, 2), unify(z, 4), sub(x, y, z)];
         goals.assert_permutations_resolve_to(&(x, y, z), vec![]);
     }
+
+    #[test]
+    fn enumerates_domains() {
+        let (x, y) = (LVar::new(), LVar::new());
+        let goals = goal_vec![domain(y, [1, 2, 8]), sub(x, y, 3)];
+        goals.assert_permutations_resolve_to(&(x, y), vec![(4, 1), (5, 2), (11, 8)]);
+    }
+
+    #[test]
+    fn fails_on_overflow() {
+        let x = LVar::<u8>::new();
+        let goals = goal_vec![sub(2, 3, x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![sub(x, 200, 100)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
 }
@@ -0,0 +1,58 @@
+use super::{add::add, checked::CheckedArith};
+use crate::goals::Goal;
+use crate::lvec::{fold, LVec};
+use crate::{Unify, Value};
+
+/** Add up all of the items in an [`LVec`].
+
+This is an [`add`](super::add) for each item, so any one unknown item can be
+derived from the total. The sum of an empty [`LVec`] is zero.
+
+# Example:
+```
+use canrun::{lvec, LVar, Query};
+use canrun::ops::sum;
+
+let x = LVar::new();
+let goal = sum(lvec![1, x, 3], 10);
+let results: Vec<_> = goal.query(x).collect();
+assert_eq!(results, vec![6]);
+```
+*/
+pub fn sum<T>(items: impl Into<Value<LVec<T>>>, total: impl Into<Value<T>>) -> impl Goal
+where
+    T: Unify + CheckedArith + Copy,
+{
+    fold(items, T::ZERO, total, add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sum;
+    use crate::{goal_vec, lvec, unify, LVar};
+
+    #[test]
+    fn succeeds() {
+        let (x, total) = (LVar::new(), LVar::new());
+        let goals = goal_vec![unify(x, 2), sum(lvec![1, x, 3], total)];
+        goals.assert_permutations_resolve_to(&total, vec![6]);
+        let goals = goal_vec![sum(lvec![], total)];
+        goals.assert_permutations_resolve_to(&total, vec![0]);
+    }
+
+    #[test]
+    fn derives_an_item() {
+        let x = LVar::new();
+        let goals = goal_vec![sum(lvec![x, 2, 3], 10)];
+        goals.assert_permutations_resolve_to(&x, vec![5]);
+    }
+
+    #[test]
+    fn fails() {
+        let x = LVar::<u8>::new();
+        let goals = goal_vec![sum(lvec![200, 100], x)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+        let goals = goal_vec![unify(x, 2), sum(lvec![1, x], 4)];
+        goals.assert_permutations_resolve_to(&x, vec![]);
+    }
+}
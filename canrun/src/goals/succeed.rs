@@ -1,4 +1,5 @@
 use crate::core::State;
+use crate::export::{Describe, Formula, UnsupportedGoal};
 use crate::goals::Goal;
 
 /** A [`Goal`] that always succeeds.
@@ -20,6 +21,16 @@ impl Goal for Succeed {
     fn apply(&self, state: State) -> Option<State> {
         Some(state)
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl Describe for Succeed {
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        Ok(Formula::Bool(true))
+    }
 }
 
 #[cfg(test)]
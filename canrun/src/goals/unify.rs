@@ -1,7 +1,9 @@
+use std::any::Any;
 use std::fmt::Debug;
 
 use crate::core;
 use crate::core::{State, Value};
+use crate::export::{Describe, Formula, NumExpr, UnsupportedGoal};
 
 use super::Goal;
 
@@ -19,6 +21,28 @@ impl<T: core::Unify> Goal for Unify<T> {
     fn apply(&self, state: State) -> Option<State> {
         state.unify(&self.a, &self.b)
     }
+
+    fn describe(&self) -> Option<&dyn Describe> {
+        Some(self)
+    }
+}
+
+impl<T: core::Unify> Describe for Unify<T> {
+    /// Only unifying `bool` values or numbers that can be described as a
+    /// [`NumExpr`] is supported.
+    fn formula(&self) -> Result<Formula, UnsupportedGoal> {
+        let as_bool = |value: &Value<T>| {
+            let value: &dyn Any = value;
+            value.downcast_ref::<Value<bool>>().map(Formula::from)
+        };
+        if let (Some(a), Some(b)) = (as_bool(&self.a), as_bool(&self.b)) {
+            return Ok(Formula::iff(a, b));
+        }
+        match (NumExpr::of(&self.a), NumExpr::of(&self.b)) {
+            (Some(a), Some(b)) => Ok(Formula::Eq(a, b)),
+            _ => Err(UnsupportedGoal::new(self)),
+        }
+    }
 }
 
 /**
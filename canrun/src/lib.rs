@@ -13,6 +13,8 @@ pub mod docs;
 
 pub mod collections;
 pub mod core;
+pub mod export;
+pub mod float;
 pub mod goals;
 #[doc(hidden)]
 pub mod util;
@@ -20,4 +22,7 @@ pub mod util;
 pub use crate::core::*;
 pub use collections::*;
 pub use goals::Goal;
-pub use goals::{both, cmp, custom, either, lazy, not, ops, project, unify, Fail, Succeed};
+pub use goals::{
+    aggregate, both, cmp, custom, domain, either, lazy, linear, nat, not, ops, project, unify,
+    Fail, Succeed,
+};
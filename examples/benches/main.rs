@@ -1,4 +1,5 @@
-use canrun_examples::zebra::zebra;
+use canrun::ForkPolicy;
+use canrun_examples::{reindeer::order_reindeer_with, zebra::zebra};
 use criterion::{criterion_group, criterion_main, Criterion};
 
 pub fn zebra_benchmark(c: &mut Criterion) {
@@ -8,5 +9,17 @@ pub fn zebra_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, zebra_benchmark);
+pub fn reindeer_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reindeer");
+    group.sample_size(10);
+    group.bench_function("fifo", |b| {
+        b.iter(|| order_reindeer_with(ForkPolicy::Fifo));
+    });
+    group.bench_function("first_fail", |b| {
+        b.iter(|| order_reindeer_with(ForkPolicy::FirstFail));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, zebra_benchmark, reindeer_benchmark);
 criterion_main!(benches);
@@ -8,10 +8,16 @@ use canrun::{
     cmp::{gt, lt},
     ltup, lvec,
     lvec::{member, LVec},
-    LVar, Query, Value,
+    ForkPolicy, Goal, LVar, Query, State, Value,
 };
 
 pub fn order_reindeer() -> Vec<(&'static str, i32)> {
+    order_reindeer_with(ForkPolicy::Fifo)
+}
+
+/// Solve the puzzle, expanding the `member` forks according to the given
+/// [`ForkPolicy`].
+pub fn order_reindeer_with(policy: ForkPolicy) -> Vec<(&'static str, i32)> {
     // Create a variable to hold the position of each reindeer
     let vixen = LVar::new();
     let rudolph = LVar::new();
@@ -90,6 +96,7 @@ pub fn order_reindeer() -> Vec<(&'static str, i32)> {
     // creating a few logic structures with placeholders and using query to fill
     // them in.
     ordering
+        .apply(State::new().with_fork_policy(policy))
         .query(lvec![
             ltup!("vixen", vixen),
             ltup!("rudolph", rudolph),
@@ -112,18 +119,17 @@ pub fn order_reindeer() -> Vec<(&'static str, i32)> {
 
 #[test]
 fn test_reindeer() {
-    assert_eq!(
-        order_reindeer(),
-        vec![
-            ("prancer", 1),
-            ("cupid", 2),
-            ("rudolph", 3),
-            ("dasher", 4),
-            ("blitzen", 5),
-            ("vixen", 6),
-            ("comet", 7),
-            ("donder", 8),
-            ("dancer", 9),
-        ]
-    )
+    let expected = vec![
+        ("prancer", 1),
+        ("cupid", 2),
+        ("rudolph", 3),
+        ("dasher", 4),
+        ("blitzen", 5),
+        ("vixen", 6),
+        ("comet", 7),
+        ("donder", 8),
+        ("dancer", 9),
+    ];
+    assert_eq!(order_reindeer(), expected);
+    assert_eq!(order_reindeer_with(ForkPolicy::FirstFail), expected);
 }